@@ -0,0 +1,806 @@
+/*!
+JavaScript/TypeScript source backend
+
+`JsCoherenceChecker` checks JavaScript and TypeScript functions against their JSDoc
+comments, the way `CodeCoherenceChecker` checks Python functions against their
+docstrings. Function declarations, arrow functions and function expressions bound to
+variables, and class methods are checked. The JSDoc text is read like a docstring
+(`sorted`, `O(n)`, `@requires`/`@ensures`), with the tags JS teams already write
+mapped onto it:
+
+```text
+/**
+ * Ranks the scores.
+ * @param {number} limit - must be positive     → limit > 0
+ * @returns {number[]} the scores, sorted      → result_is_sorted, output type number[]
+ * @ensures result >= 0
+ */
+```
+
+TypeScript annotations take precedence over JSDoc `{type}`s. The body is analysed
+into the same `ImplementationLogic` as Python code; `.reverse()` reverses an array in
+place and returns it, so both a returned `xs.sort().reverse()` chain and a returned
+variable last reversed are reversed results.
+
+A function documented to return a value (a `@returns` tag or a return type other than
+`void`/`undefined`) that can run off the end of its body or `return;` returns
+`undefined`, the JS counterpart of Python's implicit None.
+*/
+
+use anyhow::Result;
+use coherence_verifier::backend::Backend;
+use swc_common::comments::{CommentKind, Comments, SingleThreadedComments};
+use swc_common::sync::Lrc;
+use swc_common::{BytePos, FileName, SourceMap, SourceMapper, Span, Spanned};
+use swc_ecma_ast::{
+    ArrowFunctionBody, AssignTarget, Callee, Class, ClassMember, Decl, DefaultDecl, Expr, Function, Lit, MemberProp,
+    ModuleDecl, ModuleItem, Pat, PropName, SimpleAssignTarget, Stmt, TsTypeAnn,
+};
+use swc_ecma_parser::{Parser, StringInput, Syntax, TsSyntax};
+use swc_ecma_visit::{Visit, VisitWith};
+#[cfg(feature = "z3")]
+use z3::Context;
+
+use crate::{
+    CodeCoherenceChecker, CodeVerificationResult, CoherenceViolation, ContractExtractor, FunctionContract,
+    ImplementationLogic, Severity, SourcePosition, TranslationRule, ViolationType,
+};
+
+/// Coherence checking of JavaScript/TypeScript functions against their JSDoc contracts
+pub struct JsCoherenceChecker<'ctx> {
+    checker: CodeCoherenceChecker<'ctx>,
+}
+
+impl<'ctx> JsCoherenceChecker<'ctx> {
+    #[cfg(feature = "z3")]
+    pub fn new(context: &'ctx Context) -> Self {
+        Self { checker: CodeCoherenceChecker::new(context) }
+    }
+
+    /// A checker deciding contracts with `backend`, see [`CodeCoherenceChecker::with_backend`]
+    pub fn with_backend(backend: impl Backend + 'ctx) -> Self {
+        Self { checker: CodeCoherenceChecker::with_backend(backend) }
+    }
+
+    /// Add a contract-to-Z3 translation rule to those checked for every function
+    pub fn register_rule(&mut self, rule: impl TranslationRule + 'static) {
+        self.checker.register_rule(rule);
+    }
+
+    /// Verify coherence of the first function in a JavaScript/TypeScript snippet
+    pub fn verify_function(&mut self, js_code: &str) -> Result<CodeVerificationResult> {
+        let source = Source::parse(js_code)?;
+        let functions = source.functions();
+        let function = functions.first().ok_or_else(|| anyhow::anyhow!("No function found in JavaScript code"))?;
+        self.verify(&source, function)
+    }
+
+    /// Verify each function of a JavaScript/TypeScript file separately, in source
+    /// order, naming class methods `Class.method`
+    pub fn verify_file(&mut self, js_code: &str) -> Result<Vec<CodeVerificationResult>> {
+        let source = Source::parse(js_code)?;
+        source.functions().iter().map(|function| self.verify(&source, function)).collect()
+    }
+
+    fn verify(&mut self, source: &Source, function: &JsFunction) -> Result<CodeVerificationResult> {
+        let (contract, documents_return) = source.extract_contract(function)?;
+        let implementation = source.analyze_implementation(function);
+        let mut result = self.checker.verify_contract(&contract, &implementation)?;
+
+        if documents_return && function.returns_undefined_implicitly() {
+            let documented = contract.output_type.as_deref().unwrap_or("a value");
+            result.is_coherent = false;
+            result.violations.push(CoherenceViolation {
+                violation_type: ViolationType::ContractImplementationMismatch,
+                severity: Severity::Error,
+                description: format!(
+                    "Documented to return {} but some path returns undefined (a bare `return` or the end of the body)",
+                    documented
+                ),
+                location: contract.name.clone(),
+                line: contract.position.line,
+                column: contract.position.column,
+                formal_contradiction: format!("∃ path: result = undefined ∉ {}", documented),
+            });
+        }
+        Ok(result)
+    }
+}
+
+/// A parsed file with the source map and comments its spans refer to
+struct Source {
+    source_map: Lrc<SourceMap>,
+    comments: SingleThreadedComments,
+    items: Vec<ModuleItem>,
+}
+
+/// A function found in a source file
+struct JsFunction<'a> {
+    qualified_name: String,
+    /// Where the statement carrying the JSDoc comment starts (`export`, `const`, ...)
+    doc_anchor: BytePos,
+    position: BytePos,
+    params: Vec<&'a Pat>,
+    body: JsBody<'a>,
+    return_type: Option<&'a TsTypeAnn>,
+    is_generator: bool,
+}
+
+enum JsBody<'a> {
+    Block(&'a [Stmt]),
+    Expr(&'a Expr),
+    /// Overload signatures and `declare`d functions
+    None,
+}
+
+impl Source {
+    fn parse(code: &str) -> Result<Self> {
+        let source_map: Lrc<SourceMap> = Default::default();
+        let file = source_map.new_source_file(Lrc::new(FileName::Anon), code.to_string());
+        let comments = SingleThreadedComments::default();
+        // TypeScript syntax also accepts plain JavaScript
+        let mut parser = Parser::new(Syntax::Typescript(TsSyntax::default()), StringInput::from(&*file), Some(&comments));
+        let module = parser
+            .parse_module()
+            .map_err(|e| anyhow::anyhow!("Cannot parse JavaScript code: {}", e.kind().msg()))?;
+        if let Some(error) = parser.take_errors().first() {
+            anyhow::bail!("Cannot parse JavaScript code: {}", error.kind().msg());
+        }
+        Ok(Self { source_map, comments, items: module.body })
+    }
+
+    fn functions(&self) -> Vec<JsFunction<'_>> {
+        let mut functions = Vec::new();
+        for item in &self.items {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => self.collect_decl(decl, decl.span_lo(), &mut functions),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                    self.collect_decl(&export.decl, export.span.lo, &mut functions)
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match &export.decl {
+                    DefaultDecl::Fn(function) => {
+                        let name = function.ident.as_ref().map_or("default".to_string(), |ident| ident.sym.to_string());
+                        functions.push(from_function(name, export.span.lo, &function.function));
+                    }
+                    DefaultDecl::Class(class) => {
+                        let name = class.ident.as_ref().map_or("default".to_string(), |ident| ident.sym.to_string());
+                        self.collect_class(&name, &class.class, &mut functions);
+                    }
+                    DefaultDecl::TsInterfaceDecl(_) => {}
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                    functions.extend(from_expr("default".to_string(), export.span.lo, &export.expr));
+                }
+                _ => {}
+            }
+        }
+        functions
+    }
+
+    fn collect_decl<'a>(&self, decl: &'a Decl, doc_anchor: BytePos, functions: &mut Vec<JsFunction<'a>>) {
+        match decl {
+            Decl::Fn(function) => functions.push(from_function(function.ident.sym.to_string(), doc_anchor, &function.function)),
+            Decl::Var(var) => {
+                // The comment documents the first declarator; later ones have none
+                for (i, declarator) in var.decls.iter().enumerate() {
+                    let (Pat::Ident(name), Some(init)) = (&declarator.name, &declarator.init) else { continue };
+                    let anchor = if i == 0 { doc_anchor } else { declarator.span.lo };
+                    functions.extend(from_expr(name.id.sym.to_string(), anchor, init));
+                }
+            }
+            Decl::Class(class) => self.collect_class(class.ident.sym.as_ref(), &class.class, functions),
+            _ => {}
+        }
+    }
+
+    fn collect_class<'a>(&self, class_name: &str, class: &'a Class, functions: &mut Vec<JsFunction<'a>>) {
+        for member in &class.body {
+            match member {
+                ClassMember::Method(method) => {
+                    let name = format!("{}.{}", class_name, self.prop_name(&method.key));
+                    functions.push(from_function(name, method.span.lo, &method.function));
+                }
+                ClassMember::ClassProp(prop) => {
+                    let Some(value) = &prop.value else { continue };
+                    let name = format!("{}.{}", class_name, self.prop_name(&prop.key));
+                    functions.extend(from_expr(name, prop.span.lo, value));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn prop_name(&self, key: &PropName) -> String {
+        match key {
+            PropName::Ident(ident) => ident.sym.to_string(),
+            _ => self.text(key.span()),
+        }
+    }
+
+    /// Contract from the signature and JSDoc comment, and whether it documents a
+    /// return value
+    fn extract_contract(&self, function: &JsFunction) -> Result<(FunctionContract, bool)> {
+        let mut contract = FunctionContract {
+            name: function.qualified_name.clone(),
+            preconditions: Vec::new(),
+            postconditions: Vec::new(),
+            input_types: Vec::new(),
+            output_type: function.return_type.map(|ty| self.text(ty.type_ann.span())),
+            docstring: None,
+            raises: Vec::new(),
+            complexity: None,
+            parameter_types: Vec::new(),
+            invariants: Vec::new(),
+            pure: false,
+            position: self.position(function.position),
+            docstring_position: None,
+        };
+
+        let parameters: Vec<String> = function.params.iter().filter_map(|pat| param_name(pat)).collect();
+        for pat in &function.params {
+            let annotation = param_type(pat).map(|ty| self.text(ty.type_ann.span()));
+            contract.input_types.push(annotation.clone().unwrap_or_else(|| "any".to_string()));
+            if let (Some(name), Some(ty)) = (param_name(pat), annotation) {
+                contract.parameter_types.push((name, ty));
+            }
+        }
+
+        // The JSDoc block is the last `/** ... */` comment before the definition
+        let jsdoc = self.comments
+            .get_leading(function.doc_anchor)
+            .unwrap_or_default()
+            .into_iter()
+            .rfind(|comment| comment.kind == CommentKind::Block && comment.text.starts_with('*'));
+        let Some(jsdoc) = jsdoc else {
+            let documents_return = contract.output_type.as_deref().is_some_and(returns_value);
+            return Ok((contract, documents_return));
+        };
+
+        let doc = JsDoc::parse(&jsdoc.text);
+        for (name, ty) in &doc.parameter_types {
+            if !contract.parameter_types.iter().any(|(annotated, _)| annotated == name) {
+                contract.parameter_types.push((name.clone(), ty.clone()));
+            }
+        }
+        if contract.output_type.is_none() {
+            contract.output_type = doc.return_type.clone();
+        }
+        contract.docstring_position = Some(self.position(jsdoc.span.lo));
+        ContractExtractor.parse_docstring_contracts(&mut contract, &doc.text, &parameters)?;
+        contract.docstring = Some(doc.text);
+
+        let documents_return = match contract.output_type.as_deref() {
+            Some(ty) => returns_value(ty),
+            None => doc.has_returns,
+        };
+        Ok((contract, documents_return))
+    }
+
+    fn analyze_implementation(&self, function: &JsFunction) -> ImplementationLogic {
+        let parameters: Vec<String> = function.params.iter().filter_map(|pat| param_name(pat)).collect();
+        let short_name = function.qualified_name.rsplit('.').next().unwrap_or_default().to_string();
+        let mut scan = BodyScan {
+            name: short_name,
+            parameters,
+            returns: Vec::new(),
+            orderings: Vec::new(),
+            loop_depth: 0,
+            input_loop_depth: 0,
+            iterates_input: false,
+            calls_sorted: false,
+            is_recursive: false,
+            mutations: Vec::new(),
+            nested_depth: 0,
+            source: self,
+        };
+        match function.body {
+            JsBody::Block(stmts) => stmts.iter().for_each(|stmt| stmt.visit_with(&mut scan)),
+            JsBody::Expr(expr) => {
+                expr.visit_with(&mut scan);
+                scan.record_return(expr);
+            }
+            JsBody::None => {}
+        }
+
+        let mut logic = ImplementationLogic {
+            function_name: function.qualified_name.clone(),
+            logical_assertions: Vec::new(),
+            state_changes: Vec::new(),
+            return_conditions: Vec::new(),
+            raised_exceptions: Vec::new(),
+            return_expressions: Vec::new(),
+            return_types: Vec::new(),
+            return_positions: Vec::new(),
+            input_loop_depth: scan.input_loop_depth,
+            iterates_input: scan.iterates_input,
+            calls_sorted: scan.calls_sorted,
+            is_recursive: scan.is_recursive,
+            parameter_mutations: scan.mutations,
+            // Checked against JS return types by `JsCoherenceChecker`; the shared check
+            // only knows Python annotations
+            returns_none_implicitly: false,
+        };
+        for (text, position, condition) in scan.returns {
+            logic.return_conditions.push(condition.to_string());
+            logic.return_expressions.push(text);
+            logic.return_types.push(None);
+            logic.return_positions.push(position);
+        }
+        logic
+    }
+
+    fn text(&self, span: Span) -> String {
+        self.source_map.span_to_snippet(span).unwrap_or_default()
+    }
+
+    fn position(&self, at: BytePos) -> SourcePosition {
+        let location = self.source_map.lookup_char_pos(at);
+        SourcePosition { line: location.line, column: location.col.0 + 1 }
+    }
+}
+
+fn from_function(qualified_name: String, doc_anchor: BytePos, function: &Function) -> JsFunction<'_> {
+    JsFunction {
+        qualified_name,
+        doc_anchor,
+        position: function.span.lo,
+        params: function.params.iter().map(|param| &param.pat).collect(),
+        body: function.body.as_ref().map_or(JsBody::None, |body| JsBody::Block(&body.stmts)),
+        return_type: function.return_type.as_deref(),
+        is_generator: function.is_generator,
+    }
+}
+
+/// The function an initialiser or default export defines, if it is one
+fn from_expr(qualified_name: String, doc_anchor: BytePos, expr: &Expr) -> Option<JsFunction<'_>> {
+    match expr {
+        Expr::Paren(paren) => from_expr(qualified_name, doc_anchor, &paren.expr),
+        Expr::Fn(function) => Some(from_function(qualified_name, doc_anchor, &function.function)),
+        Expr::Arrow(arrow) => Some(JsFunction {
+            qualified_name,
+            doc_anchor,
+            position: arrow.span.lo,
+            params: arrow.params.iter().collect(),
+            body: match arrow.body.as_ref() {
+                ArrowFunctionBody::FunctionBody(body) => JsBody::Block(&body.stmts),
+                ArrowFunctionBody::Expr(expr) => JsBody::Expr(expr),
+            },
+            return_type: arrow.return_type.as_deref(),
+            is_generator: arrow.is_generator,
+        }),
+        _ => None,
+    }
+}
+
+impl JsFunction<'_> {
+    /// Whether some path ends the function without a value. Generators and functions
+    /// with an empty or no body (stubs, overload signatures) are never flagged.
+    fn returns_undefined_implicitly(&self) -> bool {
+        let JsBody::Block(stmts) = self.body else { return false };
+        !self.is_generator && !stmts.is_empty() && (bare_return(stmts) || completes_normally(stmts))
+    }
+}
+
+/// Whether a return type admits a value other than `undefined`
+fn returns_value(ty: &str) -> bool {
+    let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+    let ty = ty.strip_prefix("Promise<").and_then(|inner| inner.strip_suffix('>')).unwrap_or(&ty);
+    !ty.split('|').any(|member| matches!(member, "void" | "undefined" | "any" | "unknown" | "never"))
+}
+
+fn param_name(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(ident) => Some(ident.id.sym.to_string()),
+        Pat::Assign(assign) => param_name(&assign.left),
+        Pat::Rest(rest) => param_name(&rest.arg),
+        _ => None,
+    }
+}
+
+fn param_type(pat: &Pat) -> Option<&TsTypeAnn> {
+    match pat {
+        Pat::Ident(ident) => ident.type_ann.as_deref(),
+        Pat::Assign(assign) => param_type(&assign.left),
+        Pat::Rest(rest) => rest.type_ann.as_deref(),
+        _ => None,
+    }
+}
+
+/// A JSDoc comment rewritten into docstring text: `@param {T} x - must be positive`
+/// becomes "x must be positive", `@returns {T} a positive number` becomes "Returns a
+/// positive number" and `@throws {E}` becomes "Raises E". `@requires`/`@ensures` and
+/// prose are kept as they are.
+struct JsDoc {
+    text: String,
+    parameter_types: Vec<(String, String)>,
+    return_type: Option<String>,
+    has_returns: bool,
+}
+
+impl JsDoc {
+    fn parse(comment: &str) -> Self {
+        let mut doc = JsDoc { text: String::new(), parameter_types: Vec::new(), return_type: None, has_returns: false };
+        let mut lines = Vec::new();
+        for line in comment.lines() {
+            let line = line.trim().trim_start_matches('*').trim();
+            let (tag, rest) = match line.strip_prefix('@') {
+                Some(tagged) => tagged.split_once(char::is_whitespace).unwrap_or((tagged, "")),
+                None => {
+                    lines.push(line.to_string());
+                    continue;
+                }
+            };
+            let (ty, rest) = split_type(rest.trim());
+            match tag {
+                "param" | "arg" | "argument" => {
+                    let (name, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                    let name = name.trim_matches(['[', ']']).split('=').next().unwrap_or_default().to_string();
+                    if let Some(ty) = ty {
+                        doc.parameter_types.push((name.clone(), ty));
+                    }
+                    lines.push(format!("{} {}", name, description.trim().trim_start_matches('-').trim()));
+                }
+                "returns" | "return" => {
+                    doc.has_returns = true;
+                    doc.return_type = ty;
+                    lines.push(format!("Returns {}", rest.trim_start_matches('-').trim()));
+                }
+                "throws" | "exception" => {
+                    lines.push(format!("Raises {} {}", ty.unwrap_or_default(), rest));
+                }
+                _ => lines.push(line.to_string()),
+            }
+        }
+        doc.text = lines.join("\n").trim().to_string();
+        doc
+    }
+}
+
+/// Split a leading `{type}` off a tag's text, allowing nested braces
+fn split_type(text: &str) -> (Option<String>, &str) {
+    if !text.starts_with('{') {
+        return (None, text);
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (Some(text[1..i].trim().to_string()), text[i + 1..].trim());
+                }
+            }
+            _ => {}
+        }
+    }
+    (None, text)
+}
+
+/// Whether a `return;` without a value ends the function somewhere in `stmts`
+fn bare_return(stmts: &[Stmt]) -> bool {
+    struct BareReturn {
+        found: bool,
+    }
+    impl Visit for BareReturn {
+        fn visit_return_stmt(&mut self, node: &swc_ecma_ast::ReturnStmt) {
+            self.found |= node.arg.is_none();
+        }
+        fn visit_function(&mut self, _: &Function) {}
+        fn visit_arrow_expr(&mut self, _: &swc_ecma_ast::ArrowExpr) {}
+    }
+    let mut visitor = BareReturn { found: false };
+    stmts.iter().for_each(|stmt| stmt.visit_with(&mut visitor));
+    visitor.found
+}
+
+/// Whether execution can run past the end of `stmts`: some path neither returns,
+/// throws nor loops forever
+fn completes_normally(stmts: &[Stmt]) -> bool {
+    stmts.iter().all(|stmt| match stmt {
+        Stmt::Return(_) | Stmt::Throw(_) => false,
+        Stmt::Block(block) => completes_normally(&block.stmts),
+        Stmt::If(s) => completes_normally(std::slice::from_ref(&s.cons)) || s.alt.as_ref().is_none_or(|alt| completes_normally(std::slice::from_ref(alt))),
+        Stmt::While(s) => !is_true(&s.test) || breaks(std::slice::from_ref(&s.body)),
+        Stmt::DoWhile(s) => !is_true(&s.test) || breaks(std::slice::from_ref(&s.body)),
+        Stmt::For(s) => s.test.as_ref().is_some_and(|test| !is_true(test)) || breaks(std::slice::from_ref(&s.body)),
+        Stmt::Labeled(s) => completes_normally(std::slice::from_ref(&s.body)),
+        Stmt::Try(s) => {
+            let handled = s.handler.as_ref().is_some_and(|handler| completes_normally(&handler.body.stmts));
+            s.finalizer.as_ref().is_none_or(|finalizer| completes_normally(&finalizer.stmts))
+                && (completes_normally(&s.block.stmts) || handled)
+        }
+        Stmt::Switch(s) => {
+            let exhaustive = s.cases.iter().any(|case| case.test.is_none());
+            !exhaustive
+                || s.cases.iter().any(|case| breaks(&case.cons))
+                || s.cases.last().is_some_and(|case| completes_normally(&case.cons))
+        }
+        _ => true,
+    })
+}
+
+/// Whether an unlabelled `break` in `stmts` leaves the loop or switch they belong to
+fn breaks(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Stmt::Break(s) => s.label.is_none(),
+        Stmt::Block(block) => breaks(&block.stmts),
+        Stmt::If(s) => breaks(std::slice::from_ref(&s.cons)) || s.alt.as_ref().is_some_and(|alt| breaks(std::slice::from_ref(alt))),
+        Stmt::Labeled(s) => breaks(std::slice::from_ref(&s.body)),
+        Stmt::Try(s) => {
+            breaks(&s.block.stmts)
+                || s.handler.as_ref().is_some_and(|handler| breaks(&handler.body.stmts))
+                || s.finalizer.as_ref().is_some_and(|finalizer| breaks(&finalizer.stmts))
+        }
+        _ => false,
+    })
+}
+
+fn is_true(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(Lit::Bool(value)) if value.value)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Ordering {
+    Sorted,
+    Reversed,
+}
+
+/// Facts about a function body gathered in one pass. Nested functions and arrows have
+/// returns of their own, which are not the function's.
+struct BodyScan<'s> {
+    name: String,
+    parameters: Vec<String>,
+    /// Source text, position and `return_condition` of each returned expression
+    returns: Vec<(String, SourcePosition, &'static str)>,
+    /// In-place `sort` / `reverse` calls by receiver variable, in source order
+    orderings: Vec<(String, Ordering, BytePos)>,
+    loop_depth: usize,
+    input_loop_depth: usize,
+    iterates_input: bool,
+    calls_sorted: bool,
+    is_recursive: bool,
+    mutations: Vec<String>,
+    nested_depth: usize,
+    source: &'s Source,
+}
+
+impl BodyScan<'_> {
+    fn is_parameter(&self, name: &str) -> bool {
+        self.parameters.iter().any(|parameter| parameter == name)
+    }
+
+    fn mentions_parameter(&self, expr: &Expr) -> bool {
+        struct Mentions<'a> {
+            parameters: &'a [String],
+            found: bool,
+        }
+        impl Visit for Mentions<'_> {
+            fn visit_ident(&mut self, node: &swc_ecma_ast::Ident) {
+                self.found |= self.parameters.iter().any(|parameter| node.sym == **parameter);
+            }
+        }
+        let mut mentions = Mentions { parameters: &self.parameters, found: false };
+        expr.visit_with(&mut mentions);
+        mentions.found
+    }
+
+    /// Visit a loop body, counting the loop toward the loop depth when its iteration
+    /// depends on a parameter
+    fn visit_loop(&mut self, over_input: bool, visit: impl FnOnce(&mut Self)) {
+        if over_input {
+            self.loop_depth += 1;
+            self.input_loop_depth = self.input_loop_depth.max(self.loop_depth);
+            self.iterates_input = true;
+        }
+        visit(self);
+        if over_input {
+            self.loop_depth -= 1;
+        }
+    }
+
+    fn record_return(&mut self, expr: &Expr) {
+        let condition = self.return_condition(expr);
+        self.returns.push((self.source.text(expr.span()), self.source.position(expr.span_lo()), condition));
+    }
+
+    fn record_mutation(&mut self, target: &Expr, span: Span) {
+        if root_variable(target).is_some_and(|root| self.is_parameter(&root)) {
+            self.mutations.push(self.source.text(span));
+        }
+    }
+
+    /// What a returned expression says about the result: the outermost `sort`/`reverse`
+    /// of a returned call chain, or of a returned variable the last one applied to it
+    fn return_condition(&self, expr: &Expr) -> &'static str {
+        if let Some(ordering) = method_chain(expr).find_map(|method| ordering(&method)) {
+            return condition(ordering);
+        }
+        let Some(variable) = variable(expr) else { return "returns_value" };
+        let returned_at = expr.span_lo();
+        self.orderings
+            .iter()
+            .rev()
+            .find(|(name, _, at)| *name == variable && *at < returned_at)
+            .map_or("returns_value", |(_, ordering, _)| condition(*ordering))
+    }
+}
+
+fn condition(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Reversed => "returns_reversed_result",
+        Ordering::Sorted => "returns_sorted_result",
+    }
+}
+
+fn ordering(method: &str) -> Option<Ordering> {
+    match method {
+        "reverse" | "toReversed" => Some(Ordering::Reversed),
+        "sort" | "toSorted" => Some(Ordering::Sorted),
+        _ => None,
+    }
+}
+
+impl Visit for BodyScan<'_> {
+    fn visit_return_stmt(&mut self, node: &swc_ecma_ast::ReturnStmt) {
+        if self.nested_depth == 0 {
+            if let Some(arg) = &node.arg {
+                self.record_return(arg);
+            }
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, node: &Function) {
+        self.nested_depth += 1;
+        node.visit_children_with(self);
+        self.nested_depth -= 1;
+    }
+
+    fn visit_arrow_expr(&mut self, node: &swc_ecma_ast::ArrowExpr) {
+        self.nested_depth += 1;
+        node.visit_children_with(self);
+        self.nested_depth -= 1;
+    }
+
+    fn visit_for_of_stmt(&mut self, node: &swc_ecma_ast::ForOfStmt) {
+        let over_input = self.mentions_parameter(&node.right);
+        self.visit_loop(over_input, |scan| node.visit_children_with(scan));
+    }
+
+    fn visit_for_in_stmt(&mut self, node: &swc_ecma_ast::ForInStmt) {
+        let over_input = self.mentions_parameter(&node.right);
+        self.visit_loop(over_input, |scan| node.visit_children_with(scan));
+    }
+
+    fn visit_for_stmt(&mut self, node: &swc_ecma_ast::ForStmt) {
+        let over_input = node.test.as_ref().is_some_and(|test| self.mentions_parameter(test));
+        self.visit_loop(over_input, |scan| node.visit_children_with(scan));
+    }
+
+    fn visit_while_stmt(&mut self, node: &swc_ecma_ast::WhileStmt) {
+        let over_input = self.mentions_parameter(&node.test);
+        self.visit_loop(over_input, |scan| node.visit_children_with(scan));
+    }
+
+    fn visit_do_while_stmt(&mut self, node: &swc_ecma_ast::DoWhileStmt) {
+        let over_input = self.mentions_parameter(&node.test);
+        self.visit_loop(over_input, |scan| node.visit_children_with(scan));
+    }
+
+    fn visit_call_expr(&mut self, node: &swc_ecma_ast::CallExpr) {
+        if let Callee::Expr(callee) = &node.callee {
+            match callee.as_ref() {
+                Expr::Ident(ident) if ident.sym == *self.name => self.is_recursive = true,
+                Expr::Member(member) => {
+                    if let MemberProp::Ident(method) = &member.prop {
+                        let method = method.sym.to_string();
+                        let root = root_variable(&member.obj);
+                        if method == "sort" || method == "toSorted" {
+                            self.calls_sorted = true;
+                        }
+                        if let Some(root) = &root {
+                            if let Some(ordering) = ordering(&method).filter(|_| matches!(method.as_str(), "sort" | "reverse")) {
+                                self.orderings.push((root.clone(), ordering, node.span.lo));
+                            }
+                            if self.is_parameter(root) && ITERATING_METHODS.contains(&method.as_str()) {
+                                self.iterates_input = true;
+                            }
+                            if root == "this" && method == self.name {
+                                self.is_recursive = true;
+                            }
+                        }
+                        if MUTATING_METHODS.contains(&method.as_str()) {
+                            self.record_mutation(&member.obj, node.span);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, node: &swc_ecma_ast::AssignExpr) {
+        // Rebinding a parameter changes nothing the caller sees; writing through it does
+        if let AssignTarget::Simple(SimpleAssignTarget::Member(member)) = &node.left {
+            self.record_mutation(&member.obj, node.span);
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_update_expr(&mut self, node: &swc_ecma_ast::UpdateExpr) {
+        if let Expr::Member(member) = node.arg.as_ref() {
+            self.record_mutation(&member.obj, node.span);
+        }
+        node.visit_children_with(self);
+    }
+}
+
+/// Array, Map and Set methods that change their receiver
+const MUTATING_METHODS: &[&str] = &[
+    "push", "pop", "shift", "unshift", "splice", "sort", "reverse", "fill", "copyWithin", "set", "delete", "clear", "add",
+];
+
+/// Array methods that walk their receiver
+const ITERATING_METHODS: &[&str] = &[
+    "map", "filter", "forEach", "reduce", "reduceRight", "some", "every", "find", "findIndex", "includes", "indexOf",
+    "flatMap",
+];
+
+/// The variable an expression reads or writes through: `items` for `items[0]`, `this`
+/// for `this.scores.push(x)`
+fn root_variable(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::This(_) => Some("this".to_string()),
+        Expr::Member(member) => root_variable(&member.obj),
+        Expr::Call(call) => match &call.callee {
+            Callee::Expr(callee) => match callee.as_ref() {
+                Expr::Member(member) => root_variable(&member.obj),
+                _ => None,
+            },
+            _ => None,
+        },
+        Expr::Paren(paren) => root_variable(&paren.expr),
+        Expr::TsNonNull(non_null) => root_variable(&non_null.expr),
+        _ => None,
+    }
+}
+
+/// A plain variable, possibly parenthesised
+fn variable(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Paren(paren) => variable(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Names of the methods called along an `a.b().c()` chain, outermost first
+fn method_chain(expr: &Expr) -> impl Iterator<Item = String> + '_ {
+    std::iter::successors(Some(expr), |expr| match expr {
+        Expr::Call(call) => match &call.callee {
+            Callee::Expr(callee) => match callee.as_ref() {
+                Expr::Member(member) => Some(member.obj.as_ref()),
+                _ => None,
+            },
+            _ => None,
+        },
+        Expr::Paren(paren) => Some(paren.expr.as_ref()),
+        _ => None,
+    })
+    .filter_map(|expr| match expr {
+        Expr::Call(call) => match &call.callee {
+            Callee::Expr(callee) => match callee.as_ref() {
+                Expr::Member(member) => match &member.prop {
+                    MemberProp::Ident(method) => Some(method.sym.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
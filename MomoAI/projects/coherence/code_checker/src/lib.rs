@@ -1,26 +1,97 @@
 /*!
-Code Coherence Checker - Simplified text-based approach
+Code Coherence Checker
 
 This module provides mathematical guarantees of code logical consistency by:
-1. Extracting contracts from docstrings using text patterns
-2. Analyzing implementation using simple pattern matching
-3. Verifying consistency using Z3 theorem prover
-4. Detecting logical contradictions in code
+1. Parsing Python source into a syntax tree with `rustpython-parser`
+2. Extracting contracts from signatures, annotations and docstrings
+3. Analyzing implementation by walking the function body's syntax tree (returns,
+   loops, calls and mutations of the arguments)
+4. Verifying consistency using Z3 theorem prover
+5. Detecting logical contradictions in code
 
 Core principle: Code is logically consistent if and only if it can be formally verified.
 */
 
-use coherence_verifier::{CoherenceVerifier, Statement, Predicate, VerificationResult};
+use coherence_verifier::backend::Backend;
+use coherence_verifier::{Statement, StatementBody, Predicate, VerificationResult, VerificationStatus};
+use coherence_verifier::arithmetic::{self, Comparison, Term};
+use coherence_verifier::reporter::{Finding, Level, ReportEntry};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "z3")]
 use z3::Context;
-use anyhow::{Result, anyhow};
+use anyhow::Result;
+use regex::Regex;
+use rustpython_parser::ast::{self, Ranged, Visitor};
+use rustpython_parser::Parse;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+pub mod js_source;
+pub mod lsp;
+#[cfg(feature = "python")]
+mod python;
+pub mod rules;
+pub mod rust_source;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch;
+
+pub use js_source::JsCoherenceChecker;
+pub use rules::TranslationRule;
+pub use rust_source::RustCoherenceChecker;
+
+/// A docstring opening with "Deprecated" or holding a `.. deprecated::` directive
+static DEPRECATED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?im)\A\s*deprecated\b|^\s*\.\.\s+deprecated::").unwrap());
+/// "Deprecated, use `total()` instead": the suggested replacement, a quoted or called name
+static REPLACEMENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\buse\s+(?:`([A-Za-z_][\w.]*)(?:\(\))?`|([A-Za-z_][\w.]*)\(\))").unwrap()
+});
+/// "Raises ConfigError caused by an IOError" / "Raises: ValueError" in a docstring
+static DOCUMENTED_RAISES_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[Rr]aises?:?\s+([A-Z]\w*(?:\.\w+)*)(?:[:,]?\s+(?:caused by|chained from|from)\s+(?:an?\s+)?([A-Z]\w*(?:\.\w+)*))?").unwrap()
+});
+/// `self.balance` in an assigned value: the attribute read
+static SELF_ATTRIBUTE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"self\.(\w+)").unwrap());
+/// "Invariant: balance >= 0" in a class docstring
+static INVARIANT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^\s*invariant:\s*(.+?)\s*$").unwrap());
+/// "Returns the items in descending order"
+static DESCENDING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bdescending\b").unwrap());
+/// "Returns a positive number" in a lowercased docstring
+static RETURNS_SIGN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\breturns?:?\s+(?:an?\s+)?(non-negative|positive)\b").unwrap());
+/// "x must be non-negative" / "a positive x" in a lowercased docstring: the name and sign
+static PARAMETER_SIGN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(\w+)\s+(?:must be|should be|is)\s+(non-negative|positive)\b|\b(non-negative|positive)\s+(\w+)\b").unwrap()
+});
+/// Explicit contract lines: "@requires x >= 0", "@ensures result > 0"
+static ANNOTATION_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\s*@(requires|ensures)\s+(.+?)\s*$").unwrap());
+/// Google-style argument types: "x (uint8): the input"
+static ARGUMENT_TYPE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b([A-Za-z_]\w*)\s+\(([\w.\[\], ]+)\)\s*:").unwrap());
+/// "A pure function", "does not modify", "no side effects" and the like
+static PURE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:is|a) pure\b|\bpure function\b|\bno side[- ]effects\b|\bside[- ]effect[- ]free\b|\bdoes(?: not|n't) (?:modify|mutate|change)\b|\bwithout (?:modifying|mutating|changing)\b",
+    )
+    .unwrap()
+});
+/// Big-O bound such as `O(n log(n))`, one level of nested parentheses allowed
+static COMPLEXITY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bO\([^()]*(?:\([^()]*\)[^()]*)*\)").unwrap());
+/// "Runs in constant time"
+static CONSTANT_TIME_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bconstant[- ]time\b").unwrap());
+/// Documented fixed-width return type: "Returns a uint8" / "Returns: int16"
+static RETURNS_WIDTH_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[Rr]eturns?:?\s+(?:an?\s+)?((?:\w+\.)?u?int(?:8|16|32|64))\b").unwrap());
+/// Fixed-width integer type name such as `uint8` or `np.int32`
+static FIXED_WIDTH_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:\w+\.)?u?int(?:8|16|32|64)$").unwrap());
 
 /// Main code coherence checking engine
 pub struct CodeCoherenceChecker<'ctx> {
-    verifier: CoherenceVerifier<'ctx>,
+    verifier: Box<dyn Backend + 'ctx>,
     contract_extractor: ContractExtractor,
     predicate_translator: PredicateTranslator,
-    context: &'ctx Context,
+    /// Background statements asserted with the predicates of every function
+    axioms: Vec<Statement>,
 }
 
 /// Represents a function contract extracted from docstring and type hints
@@ -32,6 +103,25 @@ pub struct FunctionContract {
     pub input_types: Vec<String>,
     pub output_type: Option<String>,
     pub docstring: Option<String>,
+    pub raises: Vec<ExceptionChain>,
+    /// Documented Big-O bound, e.g. `O(n log n)`
+    pub complexity: Option<String>,
+    /// `(parameter, type)` pairs from signature annotations and docstring `Args:` entries
+    pub parameter_types: Vec<(String, String)>,
+    /// Invariants of the enclosing class over `self` attributes, e.g. `balance >= 0`,
+    /// which hold before and must hold after the method
+    #[serde(default)]
+    pub invariants: Vec<String>,
+    /// Whether the docstring promises not to modify the arguments ("pure", "does not
+    /// modify the input")
+    #[serde(default)]
+    pub pure: bool,
+    /// Where the definition starts
+    #[serde(default)]
+    pub position: SourcePosition,
+    /// Where the docstring starts, which contract-side violations point at
+    #[serde(default)]
+    pub docstring_position: Option<SourcePosition>,
 }
 
 /// Represents logical predicates extracted from code implementation
@@ -41,17 +131,55 @@ pub struct ImplementationLogic {
     pub logical_assertions: Vec<String>,
     pub state_changes: Vec<String>,
     pub return_conditions: Vec<String>,
+    pub raised_exceptions: Vec<ExceptionChain>,
+    /// Source text of each returned expression
+    pub return_expressions: Vec<String>,
+    /// Python type of each returned expression, when it is a literal (`"a"` is `str`,
+    /// `[x]` is `list`), in the order of `return_expressions`
+    #[serde(default)]
+    pub return_types: Vec<Option<String>>,
+    /// Where each `return` statement is, in the order of `return_expressions`
+    #[serde(default)]
+    pub return_positions: Vec<SourcePosition>,
+    /// Deepest nesting of loops iterating over or conditioned on the parameters
+    pub input_loop_depth: usize,
+    /// Whether a loop or comprehension iterates over or is conditioned on a parameter
+    pub iterates_input: bool,
+    /// Whether the body calls `sorted`
+    pub calls_sorted: bool,
+    /// Whether the function calls itself
+    pub is_recursive: bool,
+    /// In-place mutations of argument objects, e.g. `items.reverse()` or `items[0] = x`
+    #[serde(default)]
+    pub parameter_mutations: Vec<String>,
+    /// Whether some path returns None without saying so, through a bare `return` or by
+    /// running off the end of the body. Never set for generators and stub bodies.
+    #[serde(default)]
+    pub returns_none_implicitly: bool,
+}
+
+/// An exception a function raises, together with the exception it is chained from
+/// (`raise X from Y`), if any
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExceptionChain {
+    pub exception: String,
+    pub cause: Option<String>,
 }
 
 /// Extracts formal contracts from Python function signatures and docstrings
 pub struct ContractExtractor;
 
 /// Translates code semantics into logical predicates for Z3 verification
-pub struct PredicateTranslator;
+pub struct PredicateTranslator {
+    rules: Vec<Box<dyn TranslationRule>>,
+}
 
 /// Result of code coherence verification
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CodeVerificationResult {
+    /// Name of the verified function, qualified by enclosing functions (`outer.inner`)
+    #[serde(default)]
+    pub location: String,
     pub is_coherent: bool,
     pub confidence: f64,
     pub violations: Vec<CoherenceViolation>,
@@ -62,50 +190,784 @@ pub struct CodeVerificationResult {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CoherenceViolation {
     pub violation_type: ViolationType,
+    pub severity: Severity,
     pub description: String,
     pub location: String,
+    /// 1-based line of the offending code: the `return` a mismatch is about, the
+    /// docstring for contract-side violations, otherwise the definition. 0 when unknown.
+    #[serde(default)]
+    pub line: usize,
+    /// 1-based column on `line`
+    #[serde(default)]
+    pub column: usize,
     pub formal_contradiction: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 1-based line and column in the verified source
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourcePosition {
+    /// Position of the byte `offset` of `code`
+    fn at(code: &str, offset: usize) -> Self {
+        let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: code[..offset].matches('\n').count() + 1,
+            column: code[line_start..offset].chars().count() + 1,
+        }
+    }
+
+    /// Position where `node` starts in `code`
+    fn of(code: &str, node: &impl Ranged) -> Self {
+        Self::at(code, usize::from(node.range().start()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ViolationType {
     ContractImplementationMismatch,
     LogicalImpossibility,
     TypeIncoherence,
     StateContradiction,
+    DeprecatedUsage,
+    ComplexityMismatch,
+}
+
+/// Whether a violation makes the code incoherent or is only worth flagging
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+impl ViolationType {
+    /// Human-readable name, e.g. `Contract Mismatch`
+    pub fn label(&self) -> &'static str {
+        match self {
+            ViolationType::ContractImplementationMismatch => "Contract Mismatch",
+            ViolationType::LogicalImpossibility => "Logical Impossibility",
+            ViolationType::TypeIncoherence => "Type Incoherence",
+            ViolationType::StateContradiction => "State Contradiction",
+            ViolationType::DeprecatedUsage => "Deprecated Usage",
+            ViolationType::ComplexityMismatch => "Complexity Mismatch",
+        }
+    }
+
+    /// Stable rule id used by machine-readable reports
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            ViolationType::ContractImplementationMismatch => "contract-mismatch",
+            ViolationType::LogicalImpossibility => "logical-impossibility",
+            ViolationType::TypeIncoherence => "type-incoherence",
+            ViolationType::StateContradiction => "state-contradiction",
+            ViolationType::DeprecatedUsage => "deprecated-usage",
+            ViolationType::ComplexityMismatch => "complexity-mismatch",
+        }
+    }
+}
+
+impl CodeVerificationResult {
+    /// Convert to the shared report entry consumed by `--format` reporters
+    pub fn to_report_entry(&self, name: &str) -> ReportEntry {
+        let status = if self.is_coherent {
+            VerificationStatus::Consistent
+        } else {
+            VerificationStatus::Inconsistent
+        };
+
+        ReportEntry {
+            name: name.to_string(),
+            status,
+            verdict: if self.is_coherent { "COHERENT" } else { "INCOHERENT" }.to_string(),
+            summary: if self.is_coherent {
+                "Function is logically consistent"
+            } else {
+                "Logical contradictions detected"
+            }.to_string(),
+            confidence: self.confidence,
+            proof: self.formal_proof.clone(),
+            notes: vec![],
+            findings: self.violations.iter().map(|violation| Finding {
+                rule: violation.violation_type.rule_id().to_string(),
+                title: violation.violation_type.label().to_string(),
+                level: match violation.severity {
+                    Severity::Error => Level::Error,
+                    Severity::Warning => Level::Warning,
+                },
+                message: violation.description.clone(),
+                location: Some(violation.location.clone()),
+                file: None,
+                line: (violation.line > 0).then_some(violation.line),
+                column: (violation.line > 0).then_some(violation.column),
+                details: match violation.severity {
+                    Severity::Error => vec![format!("Formal contradiction: {}", violation.formal_contradiction)],
+                    Severity::Warning => vec![],
+                },
+            }).collect(),
+            inputs: vec![],
+            result: serde_json::to_value(self).ok(),
+        }
+    }
+
+    /// Like `to_report_entry` for a result of `verify_module` on the file at `path`:
+    /// the entry is named `path:location` and its findings point into the file
+    pub fn to_file_report_entry(&self, path: &str) -> ReportEntry {
+        let mut entry = self.to_report_entry(&format!("{}:{}", path, self.location));
+        for finding in &mut entry.findings {
+            finding.file = Some(path.to_string());
+        }
+        entry
+    }
 }
 
 impl<'ctx> CodeCoherenceChecker<'ctx> {
+    /// A checker deciding contracts with Z3 in `context`
+    #[cfg(feature = "z3")]
     pub fn new(context: &'ctx Context) -> Self {
+        Self::with_backend(coherence_verifier::CoherenceVerifier::new(context))
+    }
+
+    /// A checker deciding contracts with `backend`, e.g. the propositional
+    /// [`PropositionalVerifier`](coherence_verifier::propositional::PropositionalVerifier)
+    /// where Z3 is unavailable
+    pub fn with_backend(backend: impl Backend + 'ctx) -> Self {
         Self {
-            verifier: CoherenceVerifier::new(context),
+            verifier: Box::new(backend),
             contract_extractor: ContractExtractor,
-            predicate_translator: PredicateTranslator,
-            context,
+            predicate_translator: PredicateTranslator::default(),
+            axioms: Vec::new(),
+        }
+    }
+
+    /// Add a contract-to-Z3 translation rule to those checked for every function
+    pub fn register_rule(&mut self, rule: impl TranslationRule + 'static) {
+        self.predicate_translator.register(rule);
+    }
+
+    /// Assert `axioms`, background knowledge such as "sorted implies ordered", alongside
+    /// the predicates of every function verified from now on, replacing earlier axioms.
+    /// Their ids are prefixed with `axiom_`. Axioms that contradict each other are an
+    /// error, since every function would be incoherent under them.
+    pub fn set_axioms(&mut self, axioms: Vec<Statement>) -> Result<()> {
+        let axioms: Vec<Statement> = axioms
+            .into_iter()
+            .map(|axiom| Statement { id: format!("axiom_{}", axiom.id), ..axiom })
+            .collect();
+
+        let result = self.verifier.verify_statements(&axioms)?;
+        if result.status == VerificationStatus::Inconsistent {
+            let conflicting = result.contradictions
+                .first()
+                .map(|contradiction| contradiction.statements.join(", "))
+                .unwrap_or_default();
+            anyhow::bail!("Axioms are contradictory: {}", conflicting);
         }
+
+        self.axioms = axioms;
+        Ok(())
     }
 
     /// Verify coherence of a Python function
     pub fn verify_function(&mut self, python_code: &str) -> Result<CodeVerificationResult> {
+        self.verify_function_with_invariants(python_code, &[])
+    }
+
+    /// Verify a method against its own contract and the invariants of its class
+    fn verify_function_with_invariants(&mut self, python_code: &str, invariants: &[String]) -> Result<CodeVerificationResult> {
         // Extract contracts from comments and basic pattern matching
-        let contract = self.contract_extractor.extract_contract_from_text(python_code)?;
+        let mut contract = self.contract_extractor.extract_contract_from_text(python_code)?;
+        contract.invariants = invariants.to_vec();
         let implementation = self.analyze_implementation_from_text(python_code)?;
-        
-        // Translate to logical predicates
-        let predicates = self.predicate_translator.translate_to_predicates(&contract, &implementation)?;
-        
-        // Verify with Z3
-        let verification_result = self.verifier.verify_statements(&predicates)?;
-        
-        // Convert to code verification result
-        self.convert_to_code_result(verification_result, &contract, &implementation)
+        self.verify_contract(&contract, &implementation)
+    }
+
+    /// Verify an analysed implementation against its contract, whichever language they
+    /// were extracted from
+    fn verify_contract(&mut self, contract: &FunctionContract, implementation: &ImplementationLogic) -> Result<CodeVerificationResult> {
+        // A contract nothing can satisfy makes every other check vacuous
+        let mut contract_violations = self.check_preconditions(contract)?;
+        if contract_violations.is_empty() {
+            contract_violations = self.check_postconditions(contract)?;
+        }
+        // Only conflicts between explicit conditions are errors; a keyword-derived one
+        // is a warning and the body is still checked
+        let satisfiable = !contract_violations.iter().any(|v| v.severity == Severity::Error);
+        let mut result = if satisfiable {
+            // Translate to logical predicates
+            let (mut predicates, rule_types) = self.predicate_translator.translate(contract, implementation);
+            predicates.extend(self.axioms.iter().cloned());
+
+            // Verify with Z3
+            let verification_result = self.verifier.verify_statements(&predicates)?;
+
+            // Convert to code verification result
+            let mut result = self.convert_to_code_result(verification_result, contract, implementation, &rule_types)?;
+            result.violations.extend(contract_violations);
+            result
+        } else {
+            CodeVerificationResult {
+                location: contract.name.clone(),
+                is_coherent: false,
+                confidence: 1.0,
+                violations: contract_violations,
+                formal_proof: Some(format!("{} verification: {:?}", self.verifier.name().to_uppercase(), VerificationStatus::Inconsistent)),
+            }
+        };
+
+        // The loop-depth heuristic would only repeat a proven impossibility
+        if !result.violations.iter().any(|v| matches!(v.violation_type, ViolationType::LogicalImpossibility)) {
+            result.violations.extend(check_complexity(contract, implementation));
+        }
+
+        let mut return_violations = check_return_types(contract, implementation);
+        return_violations.extend(check_implicit_none(contract, implementation));
+        if !return_violations.is_empty() {
+            result.is_coherent = false;
+        }
+        result.violations.extend(return_violations);
+
+        let range_violations = self.check_integer_ranges(contract, implementation)?;
+        if range_violations.iter().any(|v| v.severity == Severity::Error) {
+            result.is_coherent = false;
+        }
+        result.violations.extend(range_violations);
+
+        if satisfiable {
+            let invariant_violations = self.check_invariants(contract, implementation)?;
+            if !invariant_violations.is_empty() {
+                result.is_coherent = false;
+            }
+            result.violations.extend(invariant_violations);
+        }
+
+        Ok(result)
+    }
+
+    /// Check that the arithmetic preconditions can hold together, on their own. No
+    /// caller can meet preconditions with no satisfying input, whatever the body does.
+    fn check_preconditions(&mut self, contract: &FunctionContract) -> Result<Vec<CoherenceViolation>> {
+        let mut statements = Vec::new();
+        for (i, precondition) in contract.preconditions.iter().enumerate() {
+            let Ok((lhs, op, rhs)) = arithmetic::parse_comparison(precondition) else { continue };
+            statements.push(Statement {
+                id: format!("precond_{}", i),
+                text: precondition.clone(),
+                predicates: vec![],
+                body: Some(StatementBody::Comparison { lhs, op, rhs }),
+                quantifier: None,
+                weight: None,
+            });
+        }
+        if statements.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let verification = self.verifier.verify_statements(&statements)?;
+        if verification.status != VerificationStatus::Inconsistent {
+            return Ok(Vec::new());
+        }
+
+        // Name the smallest conflicting set the verifier found, else all of them
+        let conflicting: Vec<&str> = verification.contradictions
+            .first()
+            .map(|contradiction| statements
+                .iter()
+                .filter(|statement| contradiction.statements.contains(&statement.id))
+                .map(|statement| statement.text.as_str())
+                .collect())
+            .filter(|conflicting: &Vec<&str>| !conflicting.is_empty())
+            .unwrap_or_else(|| statements.iter().map(|statement| statement.text.as_str()).collect());
+        let position = contract.docstring_position.unwrap_or(contract.position);
+        Ok(vec![CoherenceViolation {
+            violation_type: ViolationType::LogicalImpossibility,
+            severity: Severity::Error,
+            description: format!("Unsatisfiable precondition: no input satisfies {}", conflicting.join(" and ")),
+            location: contract.name.clone(),
+            line: position.line,
+            column: position.column,
+            formal_contradiction: format!("{} ⊢ ⊥", conflicting.join(" ∧ ")),
+        }])
+    }
+
+    /// Check that the postconditions the docstring promises can hold together, on their
+    /// own. A contract contradicting itself ("@ensures result > 0" and "@ensures result
+    /// < 0") is wrong whatever the body returns. Conflicts involving postconditions read
+    /// from keywords such as "ascending" are only warnings, since prose may offer them
+    /// as alternatives ("ascending, or descending when reverse is set").
+    fn check_postconditions(&mut self, contract: &FunctionContract) -> Result<Vec<CoherenceViolation>> {
+        if contract.postconditions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut statements: Vec<Statement> = contract.postconditions
+            .iter()
+            .enumerate()
+            .map(|(i, postcondition)| postcondition_statement(format!("postcond_{}", i), postcondition.clone(), postcondition))
+            .collect();
+        for (first, second) in EXCLUSIVE_POSTCONDITIONS {
+            if contract.postconditions.iter().any(|p| p == first) && contract.postconditions.iter().any(|p| p == second) {
+                let atom = |name: &str| StatementBody::Atom(Predicate {
+                    name: name.to_string(),
+                    args: vec!["output".to_string()],
+                    negated: false,
+                });
+                statements.push(Statement {
+                    id: format!("exclusive_{}", statements.len()),
+                    text: format!("{} and {} exclude each other", first, second),
+                    predicates: vec![],
+                    body: Some(StatementBody::Not(Box::new(StatementBody::And(vec![atom(first), atom(second)])))),
+                    quantifier: None,
+                    weight: None,
+                });
+            }
+        }
+
+        let verification = self.verifier.verify_statements(&statements)?;
+        if verification.status != VerificationStatus::Inconsistent {
+            return Ok(Vec::new());
+        }
+
+        let conflicting: Vec<&str> = verification.contradictions
+            .first()
+            .map(|contradiction| contract.postconditions
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| contradiction.statements.contains(&format!("postcond_{}", i)))
+                .map(|(_, postcondition)| postcondition.as_str())
+                .collect())
+            .filter(|conflicting: &Vec<&str>| !conflicting.is_empty())
+            .unwrap_or_else(|| contract.postconditions.iter().map(String::as_str).collect());
+        let explicit = conflicting.iter().all(|postcondition| arithmetic::parse_comparison(postcondition).is_ok());
+        let position = contract.docstring_position.unwrap_or(contract.position);
+        Ok(vec![CoherenceViolation {
+            violation_type: ViolationType::LogicalImpossibility,
+            severity: if explicit { Severity::Error } else { Severity::Warning },
+            description: format!("Contradictory docstring: no result satisfies {}", conflicting.join(" and ")),
+            location: contract.name.clone(),
+            line: position.line,
+            column: position.column,
+            formal_contradiction: format!("{} ⊢ ⊥", conflicting.join(" ∧ ")),
+        }])
+    }
+
+    /// Check the last assignment to each `self` attribute against the class invariants,
+    /// assuming the invariants and the arithmetic preconditions hold on entry. An
+    /// assignment after which no invariant-satisfying state is possible is a state
+    /// contradiction. Attributes assigned more than once are only checked when their
+    /// final value does not depend on an earlier assignment.
+    fn check_invariants(
+        &mut self,
+        contract: &FunctionContract,
+        implementation: &ImplementationLogic,
+    ) -> Result<Vec<CoherenceViolation>> {
+        let invariants: Vec<String> = contract.invariants
+            .iter()
+            .map(|invariant| invariant.replace("self.", ""))
+            .filter(|invariant| arithmetic::parse_comparison(invariant).is_ok())
+            .collect();
+        if invariants.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let statement = |id: String, condition: &str| -> Result<Statement> {
+            let (lhs, op, rhs) = arithmetic::parse_comparison(condition)?;
+            Ok(Statement {
+                id,
+                text: condition.to_string(),
+                predicates: vec![],
+                body: Some(StatementBody::Comparison { lhs, op, rhs }),
+                quantifier: None,
+                weight: None,
+            })
+        };
+
+        let mut entry = Vec::new();
+        for (i, invariant) in invariants.iter().enumerate() {
+            entry.push(statement(format!("invariant_{}", i), invariant)?);
+        }
+        for (i, precondition) in contract.preconditions.iter().enumerate() {
+            if arithmetic::parse_comparison(precondition).is_ok() {
+                entry.push(statement(format!("precond_{}", i), precondition)?);
+            }
+        }
+
+        // Final assignment per attribute, with the attributes assigned before it
+        let mut assigned: Vec<String> = Vec::new();
+        let mut last: Vec<(String, String, Vec<String>)> = Vec::new();
+        for change in &implementation.state_changes {
+            let Some((target, value)) = change.split_once(" = ") else { continue };
+            let Some(attribute) = target.trim().strip_prefix("self.") else { continue };
+            last.retain(|(name, _, _)| name != attribute);
+            last.push((attribute.to_string(), value.to_string(), assigned.clone()));
+            assigned.push(attribute.to_string());
+        }
+
+        let mut violations = Vec::new();
+        for (attribute, value, assigned_before) in last {
+            if SELF_ATTRIBUTE_RE.captures_iter(&value).any(|caps| assigned_before.contains(&caps[1].to_string())) {
+                continue;
+            }
+            let pre_value = SELF_ATTRIBUTE_RE.replace_all(&value, "$1").to_string();
+            if arithmetic::parse_term(&pre_value).is_err() {
+                continue;
+            }
+
+            let post = format!("{}__post", attribute);
+            for invariant in invariants.iter().filter(|invariant| words(invariant).any(|word| word == attribute)) {
+                let mut statements = entry.clone();
+                statements.push(statement("assignment".to_string(), &format!("{} = {}", post, pre_value))?);
+                statements.push(statement("invariant_after".to_string(), &replace_word(invariant, &attribute, &post))?);
+
+                if self.verifier.verify_statements(&statements)?.status == VerificationStatus::Inconsistent {
+                    violations.push(CoherenceViolation {
+                        violation_type: ViolationType::StateContradiction,
+                        severity: Severity::Error,
+                        description: format!("Sets self.{} = {} which always breaks class invariant {}", attribute, value, invariant),
+                        location: contract.name.clone(),
+                        line: contract.position.line,
+                        column: contract.position.column,
+                        formal_contradiction: format!("{} ∧ {}' = {} ⊢ ¬({})", invariant, attribute, pre_value, replace_word(invariant, &attribute, &format!("{}'", attribute))),
+                    });
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Check returned expressions against a documented fixed-width integer return type,
+    /// with parameters bounded by their own fixed-width types. A return that can never
+    /// fit the range is an error; one that can overflow for some inputs, or that Z3
+    /// cannot decide, is a warning.
+    fn check_integer_ranges(
+        &mut self,
+        contract: &FunctionContract,
+        implementation: &ImplementationLogic,
+    ) -> Result<Vec<CoherenceViolation>> {
+        let Some((lo, hi)) = contract.output_type.as_deref().and_then(integer_range) else {
+            return Ok(Vec::new());
+        };
+        let output_type = contract.output_type.as_deref().unwrap_or_default();
+
+        let comparison = |op: &str, lhs: &str, rhs: &str| Predicate {
+            name: op.to_string(),
+            args: vec![lhs.to_string(), rhs.to_string()],
+            negated: false,
+        };
+        let statement = |id: &str, predicates: Vec<Predicate>| Statement {
+            id: id.to_string(),
+            text: String::new(),
+            predicates,
+            body: None,
+            quantifier: None,
+            weight: None,
+        };
+
+        let mut bounds = Vec::new();
+        for (name, ty) in &contract.parameter_types {
+            if let Some((param_lo, param_hi)) = integer_range(ty) {
+                bounds.push(statement(&format!("param_{}", name), vec![
+                    comparison(">=", name, &param_lo.to_string()),
+                    comparison("<=", name, &param_hi.to_string()),
+                ]));
+            }
+        }
+
+        let mut violations = Vec::new();
+        for (i, expr) in implementation.return_expressions.iter().enumerate() {
+            if coherence_verifier::arithmetic::parse_term(expr).is_err() {
+                continue;
+            }
+            let position = implementation.return_positions.get(i).copied().unwrap_or(contract.position);
+
+            let with = |extra: Vec<Predicate>| {
+                let mut statements = bounds.clone();
+                statements.push(statement("return", vec![comparison("=", "__return", expr)]));
+                statements.push(statement("range", extra));
+                statements
+            };
+
+            let fits = self.verifier.verify_statements(&with(vec![
+                comparison(">=", "__return", &lo.to_string()),
+                comparison("<=", "__return", &hi.to_string()),
+            ]))?;
+            if fits.status == VerificationStatus::Inconsistent {
+                violations.push(CoherenceViolation {
+                    violation_type: ViolationType::LogicalImpossibility,
+                    severity: Severity::Error,
+                    description: format!("Returns {} which can never fit in {} [{}, {}]", expr, output_type, lo, hi),
+                    location: contract.name.clone(),
+                    line: position.line,
+                    column: position.column,
+                    formal_contradiction: format!("∀ inputs: ({}) ∉ [{}, {}]", expr, lo, hi),
+                });
+                continue;
+            }
+
+            let below = self.verifier.verify_statements(&with(vec![comparison("<", "__return", &lo.to_string())]))?;
+            let above = self.verifier.verify_statements(&with(vec![comparison(">", "__return", &hi.to_string())]))?;
+            if below.status == VerificationStatus::Consistent || above.status == VerificationStatus::Consistent {
+                violations.push(CoherenceViolation {
+                    violation_type: ViolationType::TypeIncoherence,
+                    severity: Severity::Warning,
+                    description: format!("Returns {} which can overflow {} [{}, {}] for some inputs", expr, output_type, lo, hi),
+                    location: contract.name.clone(),
+                    line: position.line,
+                    column: position.column,
+                    formal_contradiction: format!("∃ inputs: ({}) ∉ [{}, {}]", expr, lo, hi),
+                });
+            } else if [&fits, &below, &above].iter().any(|check| check.status == VerificationStatus::Unknown) {
+                // An undecided check proves nothing either way, but should not pass silently
+                violations.push(CoherenceViolation {
+                    violation_type: ViolationType::TypeIncoherence,
+                    severity: Severity::Warning,
+                    description: format!("Cannot decide whether {} fits {} [{}, {}]", expr, output_type, lo, hi),
+                    location: contract.name.clone(),
+                    line: position.line,
+                    column: position.column,
+                    formal_contradiction: format!("unknown: ({}) ∈ [{}, {}]", expr, lo, hi),
+                });
+            }
+        }
+
+        Ok(violations)
     }
 
-    /// Verify coherence of entire Python module
+    /// Verify each function of a Python module separately, nested functions and class
+    /// methods (`Class.method`) included, in source order. Calls to deprecated functions
+    /// are reported on the calling function, or on an extra `<module>` result for
+    /// top-level code. Calls that may break the callee's preconditions make the calling
+    /// function incoherent.
     pub fn verify_module(&mut self, python_code: &str) -> Result<Vec<CodeVerificationResult>> {
-        // For now, treat the entire module as one function
-        let result = self.verify_function(python_code)?;
-        Ok(vec![result])
+        let suite = ast::Suite::parse(python_code, "<module>")
+            .map_err(|e| anyhow::anyhow!("Cannot parse Python code: {}", e))?;
+        let mut functions = Vec::new();
+        collect_functions(python_code, &suite, "", &[], &mut functions);
+
+        let mut results = Vec::new();
+        for function in functions {
+            let mut result = self.verify_function_with_invariants(&function.source, &function.invariants)?;
+            for violation in &mut result.violations {
+                if violation.location == result.location {
+                    violation.location = function.qualified_name.clone();
+                }
+                if violation.line > 0 {
+                    violation.column += function.indent;
+                    violation.line += function.line - 1;
+                }
+            }
+            result.location = function.qualified_name;
+            results.push(result);
+        }
+
+        for violation in self.check_call_contracts(python_code)? {
+            if let Some(result) = results.iter_mut().find(|result| result.location == violation.location) {
+                result.is_coherent = false;
+                result.violations.push(violation);
+            }
+        }
+
+        let mut module_level = Vec::new();
+        for violation in self.find_deprecated_calls(python_code)? {
+            match results.iter_mut().find(|result| result.location == violation.location) {
+                Some(result) => result.violations.push(violation),
+                None => module_level.push(violation),
+            }
+        }
+        if !module_level.is_empty() {
+            results.push(CodeVerificationResult {
+                location: "<module>".to_string(),
+                is_coherent: true,
+                confidence: 1.0,
+                violations: module_level,
+                formal_proof: None,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Flag calls made inside the module to functions marked deprecated, by a `@deprecated`
+    /// decorator or a docstring opening with "Deprecated" or holding a `.. deprecated::`
+    /// directive: `name(...)` calls of a top-level function and `self.name(...)` /
+    /// `cls.name(...)` calls of a method of the caller's class. Calls a deprecated function
+    /// makes itself are not flagged. These are warnings: they don't make the module
+    /// incoherent on their own.
+    pub fn find_deprecated_calls(&self, python_code: &str) -> Result<Vec<CoherenceViolation>> {
+        let suite = ast::Suite::parse(python_code, "<module>")
+            .map_err(|e| anyhow::anyhow!("Cannot parse Python code: {}", e))?;
+        let mut functions = Vec::new();
+        collect_functions(python_code, &suite, "", &[], &mut functions);
+        let mut scan = ScopedCalls::default();
+        for stmt in suite {
+            scan.visit_stmt(stmt);
+        }
+
+        let mut violations = Vec::new();
+        for deprecated in &functions {
+            let contract = self.contract_extractor.extract_contract_from_text(&deprecated.source)?;
+            let docstring = contract.docstring.unwrap_or_default();
+            if !deprecated.marked_deprecated && !DEPRECATED_RE.is_match(&docstring) {
+                continue;
+            }
+            let replacement = REPLACEMENT_RE
+                .captures(&docstring)
+                .and_then(|caps| caps.get(1).or(caps.get(2)))
+                .map(|name| name.as_str().to_string());
+            let name = deprecated.qualified_name.rsplit('.').next().unwrap_or_default();
+
+            for site in &scan.calls {
+                if site.caller == deprecated.qualified_name || site.caller.starts_with(&format!("{}.", deprecated.qualified_name)) {
+                    continue;
+                }
+                let target = match site.call.func.as_ref() {
+                    ast::Expr::Name(callee) => callee.id.to_string(),
+                    ast::Expr::Attribute(attribute) => match (attribute.value.as_ref(), &site.class) {
+                        (ast::Expr::Name(receiver), Some(class)) if matches!(receiver.id.as_str(), "self" | "cls") => {
+                            format!("{}.{}", class, attribute.attr)
+                        }
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+                if target != deprecated.qualified_name {
+                    continue;
+                }
+
+                let position = SourcePosition::of(python_code, &site.call);
+                violations.push(CoherenceViolation {
+                    violation_type: ViolationType::DeprecatedUsage,
+                    severity: Severity::Warning,
+                    description: match &replacement {
+                        Some(new) => format!("Calls deprecated function {}(); use {} instead", name, new),
+                        None => format!("Calls deprecated function {}()", name),
+                    },
+                    location: site.caller.clone(),
+                    line: position.line,
+                    column: position.column,
+                    formal_contradiction: format!("deprecated({}) ∧ called({}, {})", deprecated.qualified_name, site.caller, deprecated.qualified_name),
+                });
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Check every call from a function of the module to a top-level function of the
+    /// module against the callee's arithmetic preconditions, with the arguments put in
+    /// for its parameters. What the caller knows at the call is its own preconditions,
+    /// the `if`, `while` and `assert` conditions guarding the call, and locals assigned
+    /// once, either to an arithmetic expression or to the result of a call whose
+    /// postconditions then hold of it. A precondition these facts do not imply is a
+    /// contract mismatch on the caller. Calls to functions defined elsewhere, and
+    /// arguments mentioning names the caller knows nothing about, are skipped.
+    pub fn check_call_contracts(&mut self, python_code: &str) -> Result<Vec<CoherenceViolation>> {
+        let suite = ast::Suite::parse(python_code, "<module>")
+            .map_err(|e| anyhow::anyhow!("Cannot parse Python code: {}", e))?;
+        let mut functions = Vec::new();
+        collect_functions(python_code, &suite, "", &[], &mut functions);
+
+        let mut callees = HashMap::new();
+        for function in functions.iter().filter(|function| !function.qualified_name.contains('.')) {
+            let Some(def) = parse_definition(&function.source) else { continue };
+            let contract = self.contract_extractor.extract_contract_from_text(&function.source)?;
+            let comparisons = |conditions: &[String]| -> Vec<(Term, Comparison, Term)> {
+                conditions.iter().filter_map(|condition| arithmetic::parse_comparison(condition).ok()).collect()
+            };
+            callees.insert(function.qualified_name.clone(), Callee {
+                parameters: positional_parameters(&def),
+                preconditions: comparisons(&contract.preconditions),
+                postconditions: comparisons(&contract.postconditions),
+            });
+        }
+
+        let mut violations = Vec::new();
+        for function in &functions {
+            let Some(def) = parse_definition(&function.source) else { continue };
+            let (arguments, body) = match &def {
+                ast::Stmt::FunctionDef(def) => (&def.args, &def.body),
+                ast::Stmt::AsyncFunctionDef(def) => (&def.args, &def.body),
+                _ => continue,
+            };
+            let contract = self.contract_extractor.extract_contract_from_text(&function.source)?;
+
+            let mut scan = CallScan::new(&function.source);
+            scan.scan(body, &[]);
+
+            // Facts holding throughout the body. A name bound more than once, or a
+            // parameter bound again, has no single value, so nothing said about it holds
+            // everywhere: preconditions, guards and assignments mentioning it are dropped.
+            let parameters = argument_names(arguments);
+            let reassigned = scan.reassigned(&parameters);
+            let holds = |fact: &Fact| !reassigned.iter().any(|name| fact.mentions(name));
+            let mut known: Vec<String> = parameters.clone();
+            let mut facts: Vec<Fact> = contract.preconditions
+                .iter()
+                .filter_map(|precondition| arithmetic::parse_comparison(precondition).ok())
+                .map(|(lhs, op, rhs)| Fact::comparison(lhs, op, rhs))
+                .filter(|fact| holds(fact))
+                .collect();
+            for (local, value) in scan.single_assignments(&parameters) {
+                known.push(local.to_string());
+                if let Some((name, call)) = called_function(value) {
+                    let Some(callee) = callees.get(name) else { continue };
+                    let Some(mut bindings) = callee.bind(&function.source, call) else { continue };
+                    bindings.insert("result".to_string(), Term::Var(local.to_string()));
+                    facts.extend(callee.postconditions
+                        .iter()
+                        .filter_map(|(lhs, op, rhs)| Some(Fact::comparison(substitute(lhs, &bindings)?, *op, substitute(rhs, &bindings)?)))
+                        .filter(|fact| holds(fact)));
+                } else if let Ok(term) = arithmetic::parse_term(&source_text(&function.source, value)) {
+                    facts.extend(Some(Fact::comparison(Term::Var(local.to_string()), Comparison::Eq, term)).filter(|fact| holds(fact)));
+                }
+            }
+
+            for site in &scan.calls {
+                let ast::Expr::Name(name) = site.call.func.as_ref() else { continue };
+                let Some(callee) = callees.get(name.id.as_str()) else { continue };
+                let Some(bindings) = callee.bind(&function.source, &site.call) else { continue };
+
+                for (lhs, op, rhs) in &callee.preconditions {
+                    let (Some(lhs), Some(rhs)) = (substitute(lhs, &bindings), substitute(rhs, &bindings)) else { continue };
+                    if lhs.variables().into_iter().chain(rhs.variables()).any(|variable| !known.iter().any(|name| name == variable)) {
+                        continue;
+                    }
+                    let required = Fact::comparison(lhs, *op, rhs);
+
+                    let conditions: Vec<&Fact> = site.conditions.iter().filter(|fact| holds(fact)).collect();
+                    let mut statements: Vec<Statement> = facts
+                        .iter()
+                        .chain(conditions.iter().copied())
+                        .enumerate()
+                        .map(|(i, fact)| fact.statement(format!("fact_{}", i)))
+                        .collect();
+                    let mut obligation = required.statement("obligation".to_string());
+                    obligation.body = obligation.body.map(|body| StatementBody::Not(Box::new(body)));
+                    statements.push(obligation);
+
+                    if self.verifier.verify_statements(&statements)?.status == VerificationStatus::Consistent {
+                        let position = SourcePosition::of(&function.source, &site.call);
+                        let call = source_text(&function.source, &site.call);
+                        let premises: Vec<&str> = facts.iter().chain(conditions.iter().copied()).map(|fact| fact.text.as_str()).collect();
+                        violations.push(CoherenceViolation {
+                            violation_type: ViolationType::ContractImplementationMismatch,
+                            severity: Severity::Error,
+                            description: format!("Calls {} which may break its precondition {}", call, required.text),
+                            location: function.qualified_name.clone(),
+                            line: position.line + function.line - 1,
+                            column: position.column + function.indent,
+                            formal_contradiction: format!("{} ⊬ {}", if premises.is_empty() { "⊤".to_string() } else { premises.join(" ∧ ") }, required.text),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(violations)
     }
 
     fn analyze_implementation_from_text(&self, code: &str) -> Result<ImplementationLogic> {
@@ -125,26 +987,44 @@ impl<'ctx> CodeCoherenceChecker<'ctx> {
             logical_assertions: Vec::new(),
             state_changes: Vec::new(),
             return_conditions: Vec::new(),
+            raised_exceptions: Vec::new(),
+            return_expressions: Vec::new(),
+            return_types: Vec::new(),
+            return_positions: Vec::new(),
+            input_loop_depth: 0,
+            iterates_input: false,
+            calls_sorted: false,
+            is_recursive: false,
+            parameter_mutations: Vec::new(),
+            returns_none_implicitly: false,
         };
 
-        // Simple pattern matching for common constructs
-        for line in code.lines() {
-            let line = line.trim();
-            
-            if line.starts_with("return ") {
-                if line.contains("sorted(") {
-                    logic.return_conditions.push("returns_sorted_result".to_string());
-                } else if line.contains("[::-1]") {
-                    logic.return_conditions.push("returns_reversed_result".to_string());
-                } else {
-                    logic.return_conditions.push("returns_value".to_string());
-                }
-            }
-            
-            if line.starts_with("assert ") {
-                logic.logical_assertions.push("has_assertion".to_string());
-            }
+        // Returns, raises, assertions and assignments come from the parsed function body
+        let suite = ast::Suite::parse(code, "<function>")
+            .map_err(|e| anyhow::anyhow!("Cannot parse Python code: {}", e))?;
+        let (body, params) = suite
+            .iter()
+            .find_map(|stmt| match stmt {
+                ast::Stmt::FunctionDef(def) => Some((def.body.as_slice(), def.args.as_ref())),
+                ast::Stmt::AsyncFunctionDef(def) => Some((def.body.as_slice(), def.args.as_ref())),
+                _ => None,
+            })
+            .map_or((suite.as_slice(), Vec::new()), |(body, args)| (body, argument_names(args)));
+        analyze_body(code, body, &HashMap::new(), &mut logic);
+
+        // Loops, `sorted` calls and recursion anywhere in the body, expressions included
+        let mut scan = BodyScan::new(code, &logic.function_name, &params);
+        for stmt in body {
+            scan.visit_stmt(stmt.clone());
         }
+        logic.iterates_input = scan.iterates_input;
+        logic.input_loop_depth = scan.input_loop_depth;
+        logic.calls_sorted = scan.calls_sorted;
+        logic.is_recursive = scan.is_recursive;
+        logic.parameter_mutations = scan.mutations;
+        logic.returns_none_implicitly = !scan.is_generator
+            && !is_stub(body)
+            && (scan.bare_return || completes_normally(body));
 
         Ok(logic)
     }
@@ -153,13 +1033,54 @@ impl<'ctx> CodeCoherenceChecker<'ctx> {
         &self,
         verification_result: VerificationResult,
         contract: &FunctionContract,
-        _implementation: &ImplementationLogic,
+        implementation: &ImplementationLogic,
+        rule_types: &HashMap<String, ViolationType>,
     ) -> Result<CodeVerificationResult> {
-        let violations = if !verification_result.is_consistent {
+        let violations = if verification_result.status == VerificationStatus::Inconsistent {
+            let involves = |prefix: &str| verification_result.contradictions
+                .iter()
+                .flat_map(|contradiction| &contradiction.statements)
+                .any(|id| id.starts_with(prefix));
+            // The rules behind the contradicted statements name the violation; an
+            // impossible contract outranks whatever else it conflicts with
+            let types: Vec<ViolationType> = verification_result.contradictions
+                .iter()
+                .flat_map(|contradiction| &contradiction.statements)
+                .filter_map(|id| rule_types.get(id).copied())
+                .collect();
+            let violation_type = if types.contains(&ViolationType::LogicalImpossibility) {
+                ViolationType::LogicalImpossibility
+            } else {
+                types.into_iter()
+                    .find(|violation_type| *violation_type != ViolationType::ContractImplementationMismatch)
+                    .unwrap_or(ViolationType::ContractImplementationMismatch)
+            };
+            let description = match violation_type {
+                ViolationType::LogicalImpossibility => "Contract is logically impossible for this implementation".to_string(),
+                ViolationType::StateContradiction if !implementation.parameter_mutations.is_empty() => {
+                    format!("Promises not to modify its arguments but mutates {}", implementation.parameter_mutations.join(", "))
+                }
+                _ => "Implementation does not satisfy contract".to_string(),
+            };
+            // Point at the return the contract is contradicted by, else at the contract
+            let return_index = if involves("reversed_return") {
+                implementation.return_conditions.iter().position(|condition| condition == "returns_reversed_result")
+            } else if involves("impl_result_") || involves("impl_return_") {
+                Some(0)
+            } else {
+                None
+            };
+            let position = return_index
+                .and_then(|i| implementation.return_positions.get(i).copied())
+                .or(contract.docstring_position)
+                .unwrap_or(contract.position);
             vec![CoherenceViolation {
-                violation_type: ViolationType::ContractImplementationMismatch,
-                description: "Implementation does not satisfy contract".to_string(),
+                violation_type,
+                severity: Severity::Error,
+                description,
                 location: contract.name.clone(),
+                line: position.line,
+                column: position.column,
                 formal_contradiction: format!("{:?}", verification_result.contradictions),
             }]
         } else {
@@ -167,10 +1088,12 @@ impl<'ctx> CodeCoherenceChecker<'ctx> {
         };
 
         Ok(CodeVerificationResult {
-            is_coherent: verification_result.is_consistent,
+            location: contract.name.clone(),
+            // Undecided checks are not evidence of incoherence; their confidence is 0
+            is_coherent: verification_result.status != VerificationStatus::Inconsistent,
             confidence: verification_result.confidence,
             violations,
-            formal_proof: Some(format!("Z3 verification: {}", verification_result.is_consistent)),
+            formal_proof: Some(format!("{} verification: {:?}", self.verifier.name().to_uppercase(), verification_result.status)),
         })
     }
 }
@@ -184,6 +1107,13 @@ impl ContractExtractor {
             input_types: Vec::new(),
             output_type: None,
             docstring: None,
+            raises: Vec::new(),
+            complexity: None,
+            parameter_types: Vec::new(),
+            invariants: Vec::new(),
+            pure: false,
+            position: SourcePosition { line: 1, column: 1 },
+            docstring_position: None,
         };
 
         // Extract function name
@@ -195,6 +1125,36 @@ impl ContractExtractor {
             }
         }
 
+        // Type hints from the signature; unannotated parameters are `Any`. Code that does
+        // not parse has no signature types, and fails later in implementation analysis.
+        let suite = ast::Suite::parse(code, "<function>").unwrap_or_default();
+        let signature = suite.iter().find_map(|stmt| match stmt {
+            ast::Stmt::FunctionDef(def) => Some((stmt, def.args.as_ref(), def.returns.as_deref(), def.body.as_slice())),
+            ast::Stmt::AsyncFunctionDef(def) => Some((stmt, def.args.as_ref(), def.returns.as_deref(), def.body.as_slice())),
+            _ => None,
+        });
+        if let Some((def, args, returns, body)) = signature {
+            contract.position = SourcePosition::of(code, def);
+            contract.docstring_position = match body.first() {
+                Some(docstring @ ast::Stmt::Expr(expr))
+                    if matches!(expr.value.as_ref(), ast::Expr::Constant(constant) if matches!(constant.value, ast::Constant::Str(_))) =>
+                {
+                    Some(SourcePosition::of(code, docstring))
+                }
+                _ => None,
+            };
+
+            let params = args.posonlyargs.iter().chain(&args.args).chain(&args.kwonlyargs).map(|arg| &arg.def);
+            for arg in params.chain(args.vararg.as_deref()).chain(args.kwarg.as_deref()) {
+                let annotation = arg.annotation.as_deref().map(|annotation| source_text(code, annotation));
+                contract.input_types.push(annotation.clone().unwrap_or_else(|| "Any".to_string()));
+                if let Some(annotation) = annotation {
+                    contract.parameter_types.push((arg.arg.to_string(), annotation));
+                }
+            }
+            contract.output_type = returns.map(|returns| source_text(code, returns));
+        }
+
         // Extract docstring (look for triple quotes)
         let mut in_docstring = false;
         let mut docstring_lines = Vec::new();
@@ -208,29 +1168,44 @@ impl ContractExtractor {
                 } else {
                     // Start of docstring
                     in_docstring = true;
-                    if trimmed.len() > 3 {
+                    let content = trimmed.trim_start_matches("\"\"\"").trim_start_matches("'''");
+                    if content.ends_with("\"\"\"") || content.ends_with("'''") {
                         // Single line docstring
-                        let content = trimmed.trim_start_matches("\"\"\"").trim_start_matches("'''")
-                                           .trim_end_matches("\"\"\"").trim_end_matches("'''");
+                        let content = content.trim_end_matches("\"\"\"").trim_end_matches("'''");
                         docstring_lines.push(content.to_string());
                         break;
+                    } else if !content.is_empty() {
+                        // Summary on the opening line of a multi-line docstring
+                        docstring_lines.push(content.to_string());
                     }
                 }
             } else if in_docstring {
+                if let Some(content) = trimmed.strip_suffix("\"\"\"").or_else(|| trimmed.strip_suffix("'''")) {
+                    // Last line of text closes the docstring
+                    docstring_lines.push(content.trim_end().to_string());
+                    break;
+                }
                 docstring_lines.push(trimmed.to_string());
             }
         }
 
         if !docstring_lines.is_empty() {
-            let docstring = docstring_lines.join(" ");
+            // Keep line breaks so line-oriented annotations such as `@requires` stay apart
+            let docstring = docstring_lines.join("\n");
             contract.docstring = Some(docstring.clone());
-            self.parse_docstring_contracts(&mut contract, &docstring)?;
+            let parameters: Vec<String> = signature
+                .map(|(_, args, _, _)| argument_names(args))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|name| name != "self" && name != "cls")
+                .collect();
+            self.parse_docstring_contracts(&mut contract, &docstring, &parameters)?;
         }
 
         Ok(contract)
     }
 
-    fn parse_docstring_contracts(&self, contract: &mut FunctionContract, docstring: &str) -> Result<()> {
+    fn parse_docstring_contracts(&self, contract: &mut FunctionContract, docstring: &str, parameters: &[String]) -> Result<()> {
         // Parse docstring for formal contracts
         // Look for patterns like "Returns:", "Args:", "Raises:", etc.
         
@@ -241,34 +1216,184 @@ impl ContractExtractor {
         if docstring.to_lowercase().contains("ascending") {
             contract.postconditions.push("result_ascending_order".to_string());
         }
+
+        if DESCENDING_RE.is_match(docstring) {
+            contract.postconditions.push("result_descending_order".to_string());
+        }
         
-        if docstring.to_lowercase().contains("non-negative") || docstring.to_lowercase().contains("positive") {
-            contract.preconditions.push("input_non_negative".to_string());
+        // Sign constraints become arithmetic comparisons: "Returns a positive number" →
+        // `result > 0`, "x must be non-negative" / "a non-negative x" → `x >= 0`
+        let lower = docstring.to_lowercase();
+        let sign_bound = |sign: &str| if sign == "positive" { "> 0" } else { ">= 0" };
+        if let Some(caps) = RETURNS_SIGN_RE.captures(&lower) {
+            contract.postconditions.push(format!("result {}", sign_bound(&caps[1])));
+        }
+        let signs: Vec<(&str, &str)> = PARAMETER_SIGN_RE
+            .captures_iter(&lower)
+            .filter_map(|caps| Some((caps.get(1).or(caps.get(4))?.as_str(), caps.get(2).or(caps.get(3))?.as_str())))
+            .collect();
+        for parameter in parameters {
+            if let Some((_, sign)) = signs.iter().find(|(name, _)| *name == parameter.to_lowercase()) {
+                contract.preconditions.push(format!("{} {}", parameter, sign_bound(sign)));
+            }
+        }
+
+        // Explicit contract lines: "@requires x >= 0", "@ensures result > 0"
+        for caps in ANNOTATION_RE.captures_iter(docstring) {
+            let condition = caps[2].split_whitespace().collect::<Vec<_>>().join(" ");
+            arithmetic::parse_comparison(&condition)
+                .map_err(|e| anyhow::anyhow!("Invalid @{} contract '{}': {}", &caps[1], condition, e))?;
+            let conditions = if &caps[1] == "requires" {
+                &mut contract.preconditions
+            } else {
+                &mut contract.postconditions
+            };
+            if !conditions.contains(&condition) {
+                conditions.push(condition);
+            }
+        }
+
+        // Google-style argument types: "x (uint8): the input"
+        for caps in ARGUMENT_TYPE_RE.captures_iter(docstring) {
+            if !contract.parameter_types.iter().any(|(name, _)| name == &caps[1]) {
+                contract.parameter_types.push((caps[1].to_string(), caps[2].trim().to_string()));
+            }
+        }
+
+        contract.pure = PURE_RE.is_match(docstring);
+
+        if let Some(m) = COMPLEXITY_RE.find(docstring) {
+            contract.complexity = Some(m.as_str().to_string());
+        } else if CONSTANT_TIME_RE.is_match(docstring) {
+            contract.complexity = Some("O(1)".to_string());
+        }
+
+        // Documented fixed-width return type: "Returns a uint8" / "Returns: int16"
+        if let Some(caps) = RETURNS_WIDTH_RE.captures(docstring) {
+            contract.output_type = Some(caps[1].to_string());
+        }
+
+        for caps in DOCUMENTED_RAISES_RE.captures_iter(docstring) {
+            contract.raises.push(ExceptionChain {
+                exception: caps[1].to_string(),
+                cause: caps.get(2).map(|c| c.as_str().to_string()),
+            });
         }
 
         Ok(())
     }
 }
 
+/// A postcondition as a comparison when it is one, else as an opaque property of the
+/// output
+fn postcondition_statement(id: String, text: String, postcondition: &str) -> Statement {
+    let (predicates, body) = match arithmetic::parse_comparison(postcondition) {
+        Ok((lhs, op, rhs)) => (vec![], Some(StatementBody::Comparison { lhs, op, rhs })),
+        Err(_) => (
+            vec![Predicate {
+                name: postcondition.to_string(),
+                args: vec!["output".to_string()],
+                negated: false,
+            }],
+            None,
+        ),
+    };
+    Statement {
+        id,
+        text,
+        predicates,
+        body,
+        quantifier: None,
+        weight: None,
+    }
+}
+
+/// Opaque postconditions no output satisfies together
+const EXCLUSIVE_POSTCONDITIONS: &[(&str, &str)] = &[("result_ascending_order", "result_descending_order")];
+
+impl Default for PredicateTranslator {
+    /// A translator with the built-in rules
+    fn default() -> Self {
+        Self {
+            rules: vec![Box::new(rules::ConstantTime), Box::new(rules::Purity), Box::new(rules::SortedVersusReversed)],
+        }
+    }
+}
+
 impl PredicateTranslator {
+    /// Add a rule, applied after the built-in rules and those registered before it
+    pub fn register(&mut self, rule: impl TranslationRule + 'static) {
+        self.rules.push(Box::new(rule));
+    }
+
     pub fn translate_to_predicates(
         &self,
         contract: &FunctionContract,
         implementation: &ImplementationLogic,
     ) -> Result<Vec<Statement>> {
+        Ok(self.translate(contract, implementation).0)
+    }
+
+    /// The statements for a contract and implementation, with the violation type of
+    /// each statement contributed by a rule, keyed by statement ID
+    fn translate(
+        &self,
+        contract: &FunctionContract,
+        implementation: &ImplementationLogic,
+    ) -> (Vec<Statement>, HashMap<String, ViolationType>) {
         let mut statements = Vec::new();
+        let mut rule_types = HashMap::new();
         let mut statement_id = 0;
 
+        let comparison = |id: String, text: String, condition: &str| {
+            let (lhs, op, rhs) = arithmetic::parse_comparison(condition).ok()?;
+            Some(Statement {
+                id,
+                text,
+                predicates: vec![],
+                body: Some(StatementBody::Comparison { lhs, op, rhs }),
+                quantifier: None,
+                weight: None,
+            })
+        };
+
+        // Translate arithmetic preconditions; others are not checked yet
+        for precondition in &contract.preconditions {
+            let id = format!("precond_{}", statement_id);
+            let text = format!("Contract precondition: {}", precondition);
+            if let Some(statement) = comparison(id, text, precondition) {
+                statements.push(statement);
+                statement_id += 1;
+            }
+        }
+
         // Translate contract postconditions
         for postcondition in &contract.postconditions {
+            let id = format!("postcond_{}", statement_id);
+            let text = format!("Contract postcondition: {}", postcondition);
+            statements.push(postcondition_statement(id, text, postcondition));
+            statement_id += 1;
+        }
+
+        // Arithmetic returns: `result` equals one of the returned expressions
+        let returns: Option<Vec<StatementBody>> = implementation.return_expressions
+            .iter()
+            .map(|expr| {
+                Some(StatementBody::Comparison {
+                    lhs: Term::Var("result".to_string()),
+                    op: Comparison::Eq,
+                    rhs: arithmetic::parse_term(expr).ok()?,
+                })
+            })
+            .collect();
+        if let Some(returns) = returns.filter(|returns| !returns.is_empty()) {
             statements.push(Statement {
-                id: format!("postcond_{}", statement_id),
-                text: format!("Contract postcondition: {}", postcondition),
-                predicates: vec![Predicate {
-                    name: postcondition.clone(),
-                    args: vec!["output".to_string()],
-                    negated: false,
-                }],
+                id: format!("impl_result_{}", statement_id),
+                text: format!("Implementation returns {}", implementation.return_expressions.join(" or ")),
+                predicates: vec![],
+                body: Some(StatementBody::Or(returns)),
+                quantifier: None,
+                weight: None,
             });
             statement_id += 1;
         }
@@ -283,41 +1408,1459 @@ impl PredicateTranslator {
                     args: vec!["implementation".to_string()],
                     negated: false,
                 }],
+                body: None,
+                quantifier: None,
+                weight: None,
             });
             statement_id += 1;
         }
 
-        // Add consistency checks
-        if contract.postconditions.contains(&"result_is_sorted".to_string()) 
-            && implementation.return_conditions.contains(&"returns_reversed_result".to_string()) {
-            // This is a contradiction!
+        // Translate documented exception causes and how the implementation chains them
+        for documented in &contract.raises {
+            let Some(cause) = &documented.cause else { continue };
             statements.push(Statement {
-                id: format!("contradiction_{}", statement_id),
-                text: "Contract says sorted, implementation returns reversed".to_string(),
-                predicates: vec![
-                    Predicate {
-                        name: "result_is_sorted".to_string(),
-                        args: vec!["output".to_string()],
-                        negated: false,
-                    },
-                    Predicate {
-                        name: "result_is_sorted".to_string(),
-                        args: vec!["output".to_string()],
-                        negated: true, // This creates a contradiction
-                    },
-                ],
+                id: format!("raises_{}", statement_id),
+                text: format!("Contract: raises {} caused by {}", documented.exception, cause),
+                predicates: vec![Predicate {
+                    name: "exception_caused_by".to_string(),
+                    args: vec![documented.exception.clone(), cause.clone()],
+                    negated: false,
+                }],
+                body: None,
+                quantifier: None,
+                weight: None,
             });
+            statement_id += 1;
+
+            let raised: Vec<&ExceptionChain> = implementation.raised_exceptions
+                .iter()
+                .filter(|raised| raised.exception == documented.exception)
+                .collect();
+            if !raised.is_empty() && !raised.iter().any(|raised| raised.cause.as_ref() == Some(cause)) {
+                let actual = raised.iter()
+                    .map(|raised| raised.cause.clone().unwrap_or_else(|| "no cause".to_string()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                statements.push(Statement {
+                    id: format!("impl_raise_{}", statement_id),
+                    text: format!("Implementation raises {} from {}", documented.exception, actual),
+                    predicates: vec![Predicate {
+                        name: "exception_caused_by".to_string(),
+                        args: vec![documented.exception.clone(), cause.clone()],
+                        negated: true,
+                    }],
+                    body: None,
+                    quantifier: None,
+                    weight: None,
+                });
+                statement_id += 1;
+            }
         }
 
-        Ok(statements)
+        for rule in &self.rules {
+            let contributed = rule.apply(contract, implementation);
+            rule_types.extend(contributed.iter().map(|statement| (statement.id.clone(), rule.violation_type())));
+            statements.extend(contributed);
+        }
+
+        (statements, rule_types)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Literal returns whose type the return annotation does not admit. Unannotated
+/// functions and returns of non-literal expressions are never flagged.
+fn check_return_types(contract: &FunctionContract, implementation: &ImplementationLogic) -> Vec<CoherenceViolation> {
+    let Some(output_type) = contract.output_type.as_deref() else { return Vec::new() };
+    let Some(admitted) = admitted_types(output_type) else { return Vec::new() };
+
+    implementation.return_expressions
+        .iter()
+        .zip(&implementation.return_types)
+        .enumerate()
+        .filter_map(|(i, (expr, ty))| {
+            let ty = ty.as_deref()?;
+            let position = implementation.return_positions.get(i).copied().unwrap_or(contract.position);
+            (!admitted.contains(&ty)).then(|| CoherenceViolation {
+                violation_type: ViolationType::TypeIncoherence,
+                severity: Severity::Error,
+                description: format!("Annotated to return {} but returns {} of type {}", output_type, expr, ty),
+                location: contract.name.clone(),
+                line: position.line,
+                column: position.column,
+                formal_contradiction: format!("type({}) = {} ∉ {{{}}}", expr, ty, admitted.join(", ")),
+            })
+        })
+        .collect()
+}
+
+/// A path returning None from a function whose return annotation rules None out.
+/// Annotations this does not understand are given the benefit of the doubt.
+fn check_implicit_none(contract: &FunctionContract, implementation: &ImplementationLogic) -> Option<CoherenceViolation> {
+    if !implementation.returns_none_implicitly {
+        return None;
+    }
+    let output_type = contract.output_type.as_deref()?;
+    if admitted_types(output_type)?.contains(&"None") {
+        return None;
+    }
+
+    Some(CoherenceViolation {
+        violation_type: ViolationType::ContractImplementationMismatch,
+        severity: Severity::Error,
+        description: format!(
+            "Annotated to return {} but some path returns None (a bare `return` or the end of the body)",
+            output_type
+        ),
+        location: contract.name.clone(),
+        line: contract.position.line,
+        column: contract.position.column,
+        formal_contradiction: format!("∃ path: result = None ∉ {}", output_type),
+    })
+}
+
+/// Compare a documented Big-O bound with the loop nesting over the inputs. This is a
+/// heuristic, not a proof, so a mismatch is only ever reported as a warning.
+fn check_complexity(contract: &FunctionContract, implementation: &ImplementationLogic) -> Option<CoherenceViolation> {
+    let documented = contract.complexity.as_deref()?;
+    let degree = polynomial_degree(documented)?;
+    let depth = implementation.input_loop_depth;
+    if depth <= degree {
+        return None;
+    }
+
+    let position = contract.docstring_position.unwrap_or(contract.position);
+    let suggested = if depth == 1 { "O(n)".to_string() } else { format!("O(n^{})", depth) };
+    Some(CoherenceViolation {
+        violation_type: ViolationType::ComplexityMismatch,
+        severity: Severity::Warning,
+        description: format!(
+            "Documented {} but loops over the inputs nest {} deep, suggesting {} (heuristic)",
+            documented, depth, suggested
+        ),
+        location: contract.name.clone(),
+        line: position.line,
+        column: position.column,
+        formal_contradiction: format!("heuristic: loop depth {} > polynomial degree {} of {}", depth, degree, documented),
+    })
+}
+
+/// Whether a Big-O expression is `O(1)`
+fn is_constant_time(big_o: &str) -> bool {
+    big_o.chars().filter(|c| !c.is_whitespace()).collect::<String>() == "O(1)"
+}
+
+/// Polynomial degree in `n` of a Big-O expression: `O(1)` and `O(log n)` are 0,
+/// `O(n)` and `O(n log n)` are 1, `O(n^2)`/`O(n**2)`/`O(n²)`/`O(n*m)` are 2.
+/// Returns None for anything it does not recognise.
+fn polynomial_degree(big_o: &str) -> Option<usize> {
+    let inner: String = big_o
+        .trim_start_matches("O(")
+        .trim_end_matches(')')
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let inner = inner.replace("log(n)", "logn").replace("**", "^").replace('²', "^2").replace('³', "^3");
+
+    if inner == "1" || inner.starts_with("log") {
+        return Some(0);
+    }
+
+    let mut degree = 0;
+    for factor in inner.trim_end_matches("logn").trim_end_matches('*').split('*') {
+        match factor.split_once('^') {
+            Some((base, exp)) if base.len() == 1 && base.chars().all(char::is_alphabetic) => degree += exp.parse::<usize>().ok()?,
+            None if factor.len() == 1 && factor.chars().all(char::is_alphabetic) => degree += 1,
+            _ => return None,
+        }
+    }
+    Some(degree)
+}
+
+/// A function or method found in a module
+struct ModuleFunction {
+    /// Name qualified by enclosing classes and functions, e.g. `Account.deposit`
+    qualified_name: String,
+    /// Dedented source of the definition
+    source: String,
+    /// 1-based line of the definition in the module
+    line: usize,
+    /// Indentation removed from the lines of `source`
+    indent: usize,
+    /// Invariants of the enclosing class; empty for functions, static and class methods
+    invariants: Vec<String>,
+    /// Whether the definition carries a `@deprecated` decorator
+    marked_deprecated: bool,
+}
+
+/// Every function defined in `body`, in source order, each followed by the functions
+/// nested in it. Methods of a class carry the invariants from its docstring unless they
+/// are static or class methods, which have no `self` to constrain.
+fn collect_functions(
+    code: &str,
+    body: &[ast::Stmt],
+    prefix: &str,
+    invariants: &[String],
+    functions: &mut Vec<ModuleFunction>,
+) {
+    for stmt in body {
+        let (name, inner, decorators) = match stmt {
+            ast::Stmt::FunctionDef(def) => (def.name.as_str(), &def.body, &def.decorator_list),
+            ast::Stmt::AsyncFunctionDef(def) => (def.name.as_str(), &def.body, &def.decorator_list),
+            ast::Stmt::ClassDef(class) => {
+                let invariants = class_invariants(&class.body);
+                collect_functions(code, &class.body, &format!("{}{}.", prefix, class.name.as_str()), &invariants, functions);
+                continue;
+            }
+            _ => continue,
+        };
+        let binds_instance = !decorators.iter().any(|decorator| {
+            matches!(decorator, ast::Expr::Name(name) if matches!(name.id.as_str(), "staticmethod" | "classmethod"))
+        });
+
+        let qualified_name = format!("{}{}", prefix, name);
+        functions.push(ModuleFunction {
+            qualified_name: qualified_name.clone(),
+            source: statement_source(code, stmt),
+            line: SourcePosition::of(code, stmt).line,
+            indent: statement_indent(code, stmt),
+            invariants: if binds_instance { invariants.to_vec() } else { Vec::new() },
+            marked_deprecated: decorators.iter().any(is_deprecated_decorator),
+        });
+        collect_functions(code, inner, &format!("{}.", qualified_name), &[], functions);
+    }
+}
+
+/// `@deprecated`, `@warnings.deprecated(...)` and the like
+fn is_deprecated_decorator(decorator: &ast::Expr) -> bool {
+    match decorator {
+        ast::Expr::Name(name) => name.id.as_str() == "deprecated",
+        ast::Expr::Attribute(attribute) => attribute.attr.as_str() == "deprecated",
+        ast::Expr::Call(call) => is_deprecated_decorator(&call.func),
+        _ => false,
+    }
+}
+
+/// `Invariant: <condition>` lines of a class docstring
+fn class_invariants(body: &[ast::Stmt]) -> Vec<String> {
+    let Some(ast::Stmt::Expr(expr)) = body.first() else { return Vec::new() };
+    let ast::Expr::Constant(constant) = expr.value.as_ref() else { return Vec::new() };
+    let ast::Constant::Str(docstring) = &constant.value else { return Vec::new() };
+
+    docstring
+        .lines()
+        .filter_map(|line| INVARIANT_RE.captures(line).map(|caps| caps[1].to_string()))
+        .collect()
+}
+
+/// Full lines of a statement's source, with the indentation of its first line removed
+fn statement_source(code: &str, stmt: &ast::Stmt) -> String {
+    let range = stmt.range();
+    let start = code[..usize::from(range.start())].rfind('\n').map_or(0, |i| i + 1);
+    let lines: Vec<&str> = code[start..usize::from(range.end())].lines().collect();
+    let indent = statement_indent(code, stmt);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Width of the indentation of the first line of a statement
+fn statement_indent(code: &str, stmt: &ast::Stmt) -> usize {
+    let start = usize::from(stmt.range().start());
+    let line = &code[code[..start].rfind('\n').map_or(0, |i| i + 1)..];
+    line.len() - line.trim_start().len()
+}
+
+/// Record the returns, raises, assertions and assignments of a function body, descending
+/// into compound statements but not into nested function or class definitions. `caught`
+/// maps the names bound by enclosing `except X as name:` handlers to X, so that
+/// `raise ... from name` resolves to the caught exception.
+fn analyze_body(code: &str, body: &[ast::Stmt], caught: &HashMap<String, String>, logic: &mut ImplementationLogic) {
+    for stmt in body {
+        match stmt {
+            ast::Stmt::Return(ast::StmtReturn { value: Some(value), .. }) => {
+                logic.return_expressions.push(source_text(code, value.as_ref()));
+                logic.return_types.push(literal_type(value).map(str::to_string));
+                logic.return_positions.push(SourcePosition::of(code, stmt));
+                logic.return_conditions.push(return_condition(code, value).to_string());
+            }
+            // A bare `raise` re-raises whatever is being handled
+            ast::Stmt::Raise(ast::StmtRaise { exc: Some(exc), cause, .. }) => {
+                let cause = cause
+                    .as_deref()
+                    .map(|cause| exception_name(code, cause))
+                    .filter(|cause| cause != "None")
+                    .map(|cause| caught.get(&cause).cloned().unwrap_or(cause));
+                logic.raised_exceptions.push(ExceptionChain { exception: exception_name(code, exc), cause });
+            }
+            ast::Stmt::Assert(_) => logic.logical_assertions.push("has_assertion".to_string()),
+            ast::Stmt::Assign(assign) => {
+                let value = source_text(code, assign.value.as_ref());
+                for target in &assign.targets {
+                    logic.state_changes.push(format!("{} = {}", source_text(code, target), value));
+                }
+            }
+            ast::Stmt::AugAssign(assign) => {
+                let target = source_text(code, assign.target.as_ref());
+                let value = source_text(code, assign.value.as_ref());
+                let op = match assign.op {
+                    ast::Operator::Add => "+",
+                    ast::Operator::Sub => "-",
+                    ast::Operator::Mult => "*",
+                    ast::Operator::Div => "/",
+                    _ => "?",
+                };
+                logic.state_changes.push(format!("{} = {} {} ({})", target, target, op, value));
+            }
+            ast::Stmt::If(s) => {
+                analyze_body(code, &s.body, caught, logic);
+                analyze_body(code, &s.orelse, caught, logic);
+            }
+            ast::Stmt::For(s) => {
+                analyze_body(code, &s.body, caught, logic);
+                analyze_body(code, &s.orelse, caught, logic);
+            }
+            ast::Stmt::AsyncFor(s) => {
+                analyze_body(code, &s.body, caught, logic);
+                analyze_body(code, &s.orelse, caught, logic);
+            }
+            ast::Stmt::While(s) => {
+                analyze_body(code, &s.body, caught, logic);
+                analyze_body(code, &s.orelse, caught, logic);
+            }
+            ast::Stmt::With(s) => analyze_body(code, &s.body, caught, logic),
+            ast::Stmt::AsyncWith(s) => analyze_body(code, &s.body, caught, logic),
+            ast::Stmt::Try(s) => {
+                analyze_body(code, &s.body, caught, logic);
+                for ast::ExceptHandler::ExceptHandler(handler) in &s.handlers {
+                    let bound = handler.name.as_ref().zip(handler.type_.as_deref());
+                    match bound {
+                        Some((name, caught_type)) => {
+                            // `except (X, Y) as name:` is taken to catch X
+                            let caught_type = match caught_type {
+                                ast::Expr::Tuple(tuple) => tuple.elts.first().unwrap_or(caught_type),
+                                _ => caught_type,
+                            };
+                            let mut caught = caught.clone();
+                            caught.insert(name.to_string(), source_text(code, caught_type));
+                            analyze_body(code, &handler.body, &caught, logic);
+                        }
+                        None => analyze_body(code, &handler.body, caught, logic),
+                    }
+                }
+                analyze_body(code, &s.orelse, caught, logic);
+                analyze_body(code, &s.finalbody, caught, logic);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Exception class an expression of a `raise` names: `X` for both `X` and `X(...)`
+fn exception_name(code: &str, exc: &ast::Expr) -> String {
+    match exc {
+        ast::Expr::Call(call) => source_text(code, call.func.as_ref()),
+        _ => source_text(code, exc),
+    }
+}
+
+/// What a returned expression says about the result: `sorted(...)` is sorted and a
+/// `[::-1]` slice reversed
+fn return_condition(code: &str, value: &ast::Expr) -> &'static str {
+    match value {
+        ast::Expr::Call(call) if matches!(call.func.as_ref(), ast::Expr::Name(name) if name.id.as_str() == "sorted") => {
+            "returns_sorted_result"
+        }
+        ast::Expr::Subscript(subscript) if source_text(code, subscript.slice.as_ref()).replace(' ', "") == "::-1" => {
+            "returns_reversed_result"
+        }
+        _ => "returns_value",
+    }
+}
+
+/// Python type of a literal expression, or None when only evaluation would tell
+fn literal_type(value: &ast::Expr) -> Option<&'static str> {
+    match value {
+        ast::Expr::Constant(constant) => match &constant.value {
+            ast::Constant::Str(_) => Some("str"),
+            ast::Constant::Bytes(_) => Some("bytes"),
+            ast::Constant::Bool(_) => Some("bool"),
+            ast::Constant::Int(_) => Some("int"),
+            ast::Constant::Float(_) => Some("float"),
+            ast::Constant::Complex { .. } => Some("complex"),
+            ast::Constant::None => Some("None"),
+            ast::Constant::Tuple(_) => Some("tuple"),
+            ast::Constant::Ellipsis => None,
+        },
+        ast::Expr::UnaryOp(op) if matches!(op.op, ast::UnaryOp::USub | ast::UnaryOp::UAdd) => {
+            literal_type(&op.operand).filter(|ty| matches!(*ty, "int" | "float" | "complex"))
+        }
+        ast::Expr::JoinedStr(_) => Some("str"),
+        ast::Expr::List(_) | ast::Expr::ListComp(_) => Some("list"),
+        ast::Expr::Dict(_) | ast::Expr::DictComp(_) => Some("dict"),
+        ast::Expr::Set(_) | ast::Expr::SetComp(_) => Some("set"),
+        ast::Expr::Tuple(_) => Some("tuple"),
+        _ => None,
+    }
+}
+
+/// Literal types a return annotation admits, following the numeric tower (`float`
+/// admits `int`, `int` admits `bool`). Unions and `Optional` are unfolded. None when
+/// the annotation is `Any` or mentions a type this does not know, which admits anything.
+fn admitted_types(annotation: &str) -> Option<Vec<&'static str>> {
+    let annotation: String = annotation.chars().filter(|c| !c.is_whitespace()).collect();
+    let annotation = annotation.replace("typing.", "");
+
+    let mut admitted = Vec::new();
+    for part in split_top_level(&annotation, '|') {
+        if let Some(inner) = part.strip_prefix("Optional[").and_then(|rest| rest.strip_suffix(']')) {
+            admitted.extend(admitted_types(inner)?);
+            admitted.push("None");
+            continue;
+        }
+        if let Some(inner) = part.strip_prefix("Union[").and_then(|rest| rest.strip_suffix(']')) {
+            for member in split_top_level(inner, ',') {
+                admitted.extend(admitted_types(member)?);
+            }
+            continue;
+        }
+
+        let base = part.split('[').next().unwrap_or(part);
+        admitted.extend_from_slice(match base {
+            "int" => &["int", "bool"],
+            _ if FIXED_WIDTH_RE.is_match(base) => &["int", "bool"],
+            "float" => &["float", "int", "bool"],
+            "complex" => &["complex", "float", "int", "bool"],
+            "bool" => &["bool"],
+            "str" => &["str"],
+            "bytes" => &["bytes"],
+            "None" => &["None"],
+            "list" | "List" => &["list"],
+            "dict" | "Dict" => &["dict"],
+            "set" | "Set" => &["set"],
+            "tuple" | "Tuple" => &["tuple"],
+            _ => return None,
+        });
+    }
+    Some(admitted)
+}
+
+/// Split on `separator` outside of square brackets
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ if c == separator && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Source of a node with line breaks and indentation collapsed to single spaces
+fn source_text(code: &str, node: &impl Ranged) -> String {
+    code[node.range()].split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether execution can run past the end of `body`: some path through it neither
+/// returns, raises nor loops forever. Context managers are assumed not to swallow
+/// exceptions.
+fn completes_normally(body: &[ast::Stmt]) -> bool {
+    body.iter().all(|stmt| match stmt {
+        ast::Stmt::Return(_) | ast::Stmt::Raise(_) => false,
+        ast::Stmt::Assert(assert) => !is_constant(&assert.test, false),
+        ast::Stmt::If(s) => completes_normally(&s.body) || completes_normally(&s.orelse),
+        ast::Stmt::While(s) => !is_constant(&s.test, true) || breaks(&s.body),
+        ast::Stmt::With(s) => completes_normally(&s.body),
+        ast::Stmt::AsyncWith(s) => completes_normally(&s.body),
+        ast::Stmt::Try(s) => {
+            let handled = s.handlers.iter().any(|ast::ExceptHandler::ExceptHandler(handler)| completes_normally(&handler.body));
+            completes_normally(&s.finalbody) && ((completes_normally(&s.body) && completes_normally(&s.orelse)) || handled)
+        }
+        ast::Stmt::Match(s) => {
+            let exhaustive = s.cases.iter().any(|case| {
+                case.guard.is_none() && matches!(&case.pattern, ast::Pattern::MatchAs(wildcard) if wildcard.pattern.is_none())
+            });
+            !exhaustive || s.cases.iter().any(|case| completes_normally(&case.body))
+        }
+        _ => true,
+    })
+}
+
+/// Whether a `break` in `body` leaves the loop `body` belongs to
+fn breaks(body: &[ast::Stmt]) -> bool {
+    body.iter().any(|stmt| match stmt {
+        ast::Stmt::Break(_) => true,
+        ast::Stmt::If(s) => breaks(&s.body) || breaks(&s.orelse),
+        ast::Stmt::With(s) => breaks(&s.body),
+        ast::Stmt::AsyncWith(s) => breaks(&s.body),
+        ast::Stmt::Try(s) => {
+            breaks(&s.body) || breaks(&s.orelse) || breaks(&s.finalbody)
+                || s.handlers.iter().any(|ast::ExceptHandler::ExceptHandler(handler)| breaks(&handler.body))
+        }
+        ast::Stmt::Match(s) => s.cases.iter().any(|case| breaks(&case.body)),
+        _ => false,
+    })
+}
+
+/// Whether `expr` is a literal with the given truth value, as in `while True:`
+fn is_constant(expr: &ast::Expr, truthy: bool) -> bool {
+    match expr {
+        ast::Expr::Constant(constant) => match &constant.value {
+            ast::Constant::Bool(value) => *value == truthy,
+            ast::Constant::Int(value) => (value.to_string() != "0") == truthy,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Whether a body is only a placeholder (`...` or `pass`, after an optional
+/// docstring), as in protocols, abstract methods and overloads
+fn is_stub(body: &[ast::Stmt]) -> bool {
+    let placeholder = |stmt: &ast::Stmt| match stmt {
+        ast::Stmt::Pass(_) => true,
+        ast::Stmt::Expr(expr) => matches!(
+            expr.value.as_ref(),
+            ast::Expr::Constant(ast::ExprConstant { value: ast::Constant::Ellipsis | ast::Constant::Str(_), .. })
+        ),
+        _ => false,
+    };
+    !body.is_empty() && body.iter().all(placeholder)
+}
+
+/// Names of all parameters of a function, `*args` and `**kwargs` included
+fn argument_names(args: &ast::Arguments) -> Vec<String> {
+    args.posonlyargs
+        .iter()
+        .chain(&args.args)
+        .chain(&args.kwonlyargs)
+        .map(|arg| arg.def.arg.to_string())
+        .chain(args.vararg.iter().chain(&args.kwarg).map(|arg| arg.arg.to_string()))
+        .collect()
+}
+
+/// Walks a function body for what bounds its running time from below (loops over the
+/// parameters and how deep they nest, calls to `sorted` and calls back into the function
+/// itself) and for in-place mutations of the argument objects. Nested function and class
+/// definitions are skipped; they do not run as part of the body.
+struct BodyScan<'a> {
+    code: &'a str,
+    function_name: &'a str,
+    params: &'a [String],
+    /// Parameters whose names still refer to the argument objects, i.e. have not been
+    /// rebound to a local value earlier in the body
+    arguments: Vec<String>,
+    iterates_input: bool,
+    loop_depth: usize,
+    input_loop_depth: usize,
+    calls_sorted: bool,
+    is_recursive: bool,
+    mutations: Vec<String>,
+    bare_return: bool,
+    is_generator: bool,
+}
+
+/// Methods of the built-in containers that modify the object they are called on
+const MUTATING_METHODS: &[&str] = &[
+    "append", "extend", "insert", "remove", "pop", "clear", "sort", "reverse",
+    "update", "add", "discard", "setdefault", "popitem",
+];
+
+impl<'a> BodyScan<'a> {
+    fn new(code: &'a str, function_name: &'a str, params: &'a [String]) -> Self {
+        BodyScan {
+            code,
+            function_name,
+            params,
+            arguments: params.to_vec(),
+            iterates_input: false,
+            loop_depth: 0,
+            input_loop_depth: 0,
+            calls_sorted: false,
+            is_recursive: false,
+            mutations: Vec::new(),
+            bare_return: false,
+            is_generator: false,
+        }
+    }
+
+    /// Whether `expr` is an argument object or reached through one: `items`,
+    /// `items[0]`, `config.options`
+    fn is_argument_object(&self, expr: &ast::Expr) -> bool {
+        match expr {
+            ast::Expr::Name(name) => self.arguments.iter().any(|arg| arg == name.id.as_str()),
+            ast::Expr::Attribute(attribute) => self.is_argument_object(&attribute.value),
+            ast::Expr::Subscript(subscript) => self.is_argument_object(&subscript.value),
+            _ => false,
+        }
+    }
+
+    /// Record a store to or deletion of `target` if it writes into an argument object,
+    /// and forget parameters whose names it rebinds
+    fn record_target(&mut self, target: &ast::Expr) {
+        match target {
+            ast::Expr::Name(name) => self.arguments.retain(|arg| arg != name.id.as_str()),
+            ast::Expr::Attribute(ast::ExprAttribute { value, .. }) | ast::Expr::Subscript(ast::ExprSubscript { value, .. })
+                if self.is_argument_object(value) =>
+            {
+                self.mutations.push(source_text(self.code, target));
+            }
+            ast::Expr::Tuple(ast::ExprTuple { elts, .. }) | ast::Expr::List(ast::ExprList { elts, .. }) => {
+                for elt in elts {
+                    self.record_target(elt);
+                }
+            }
+            ast::Expr::Starred(starred) => self.record_target(&starred.value),
+            _ => {}
+        }
+    }
+
+    fn mentions_input(&self, expr: &ast::Expr) -> bool {
+        let mut mentions = Mentions { names: self.params, found: false };
+        mentions.visit_expr(expr.clone());
+        mentions.found
+    }
+
+    /// Count a loop whose iteration depends on a parameter toward the loop depth
+    fn enter_loop(&mut self, over_input: bool) -> bool {
+        if over_input {
+            self.loop_depth += 1;
+            self.input_loop_depth = self.input_loop_depth.max(self.loop_depth);
+            self.iterates_input = true;
+        }
+        over_input
+    }
+
+    fn leave_loop(&mut self, counted: bool) {
+        if counted {
+            self.loop_depth -= 1;
+        }
+    }
+
+    /// Visit the `for` clauses of a comprehension, each nested in the ones before it,
+    /// and then its elements inside all of them
+    fn visit_generators(&mut self, generators: Vec<ast::Comprehension>, elements: Vec<ast::Expr>) {
+        let mut counted = 0;
+        for generator in generators {
+            if self.enter_loop(self.mentions_input(&generator.iter)) {
+                counted += 1;
+            }
+            self.visit_expr(generator.iter);
+            for condition in generator.ifs {
+                self.visit_expr(condition);
+            }
+        }
+        for element in elements {
+            self.visit_expr(element);
+        }
+        self.loop_depth -= counted;
+    }
+}
+
+/// Whether an expression reads any of the given names
+struct Mentions<'a> {
+    names: &'a [String],
+    found: bool,
+}
+
+impl Visitor for Mentions<'_> {
+    fn visit_expr_name(&mut self, node: ast::ExprName) {
+        self.found |= self.names.iter().any(|name| name == node.id.as_str());
+    }
+}
+
+impl Visitor for BodyScan<'_> {
+    fn visit_stmt_function_def(&mut self, _node: ast::StmtFunctionDef) {}
+
+    fn visit_stmt_return(&mut self, node: ast::StmtReturn) {
+        self.bare_return |= node.value.is_none();
+        self.generic_visit_stmt_return(node)
+    }
+
+    fn visit_expr_yield(&mut self, node: ast::ExprYield) {
+        self.is_generator = true;
+        self.generic_visit_expr_yield(node)
+    }
+
+    fn visit_expr_yield_from(&mut self, node: ast::ExprYieldFrom) {
+        self.is_generator = true;
+        self.generic_visit_expr_yield_from(node)
+    }
+
+    fn visit_stmt_assign(&mut self, node: ast::StmtAssign) {
+        self.visit_expr(*node.value);
+        for target in &node.targets {
+            self.record_target(target);
+        }
+    }
+
+    fn visit_stmt_aug_assign(&mut self, node: ast::StmtAugAssign) {
+        self.visit_expr(*node.value);
+        self.record_target(&node.target);
+    }
+
+    fn visit_stmt_ann_assign(&mut self, node: ast::StmtAnnAssign) {
+        if let Some(value) = node.value {
+            self.visit_expr(*value);
+            self.record_target(&node.target);
+        }
+    }
+
+    fn visit_stmt_delete(&mut self, node: ast::StmtDelete) {
+        for target in &node.targets {
+            if !matches!(target, ast::Expr::Name(_)) {
+                self.record_target(target);
+            }
+        }
+    }
+
+    fn visit_stmt_async_function_def(&mut self, _node: ast::StmtAsyncFunctionDef) {}
+
+    fn visit_stmt_class_def(&mut self, _node: ast::StmtClassDef) {}
+
+    fn visit_stmt_for(&mut self, node: ast::StmtFor) {
+        let counted = self.enter_loop(self.mentions_input(&node.iter));
+        self.generic_visit_stmt_for(node);
+        self.leave_loop(counted);
+    }
+
+    fn visit_stmt_async_for(&mut self, node: ast::StmtAsyncFor) {
+        let counted = self.enter_loop(self.mentions_input(&node.iter));
+        self.generic_visit_stmt_async_for(node);
+        self.leave_loop(counted);
+    }
+
+    fn visit_stmt_while(&mut self, node: ast::StmtWhile) {
+        let counted = self.enter_loop(self.mentions_input(&node.test));
+        self.generic_visit_stmt_while(node);
+        self.leave_loop(counted);
+    }
+
+    fn visit_expr_list_comp(&mut self, node: ast::ExprListComp) {
+        self.visit_generators(node.generators, vec![*node.elt]);
+    }
+
+    fn visit_expr_set_comp(&mut self, node: ast::ExprSetComp) {
+        self.visit_generators(node.generators, vec![*node.elt]);
+    }
+
+    fn visit_expr_dict_comp(&mut self, node: ast::ExprDictComp) {
+        self.visit_generators(node.generators, vec![*node.key, *node.value]);
+    }
+
+    fn visit_expr_generator_exp(&mut self, node: ast::ExprGeneratorExp) {
+        self.visit_generators(node.generators, vec![*node.elt]);
+    }
+
+    fn visit_expr_call(&mut self, node: ast::ExprCall) {
+        match node.func.as_ref() {
+            ast::Expr::Name(name) => {
+                self.calls_sorted |= name.id.as_str() == "sorted";
+                self.is_recursive |= name.id.as_str() == self.function_name;
+            }
+            ast::Expr::Attribute(method)
+                if MUTATING_METHODS.contains(&method.attr.as_str()) && self.is_argument_object(&method.value) =>
+            {
+                self.mutations.push(source_text(self.code, &node));
+            }
+            _ => {}
+        }
+        self.generic_visit_expr_call(node)
+    }
+}
+
+/// Contract of a module function as seen by its callers
+struct Callee {
+    /// Parameters that positional arguments bind, in order
+    parameters: Vec<String>,
+    preconditions: Vec<(Term, Comparison, Term)>,
+    postconditions: Vec<(Term, Comparison, Term)>,
+}
+
+impl Callee {
+    /// The arithmetic argument of `call` for each parameter it binds, or None when the
+    /// call unpacks arguments or passes more positional ones than there are parameters
+    fn bind(&self, code: &str, call: &ast::ExprCall) -> Option<HashMap<String, Term>> {
+        if call.args.len() > self.parameters.len()
+            || call.args.iter().any(|arg| matches!(arg, ast::Expr::Starred(_)))
+            || call.keywords.iter().any(|keyword| keyword.arg.is_none())
+        {
+            return None;
+        }
+
+        let positional = self.parameters.iter().map(String::as_str).zip(&call.args);
+        let keywords = call.keywords.iter().filter_map(|keyword| Some((keyword.arg.as_ref()?.as_str(), &keyword.value)));
+        Some(positional
+            .chain(keywords)
+            .filter_map(|(parameter, value)| {
+                Some((parameter.to_string(), arithmetic::parse_term(&source_text(code, value)).ok()?))
+            })
+            .collect())
+    }
+}
+
+/// Something a caller knows at a call site, with the text reports show for it
+#[derive(Debug, Clone)]
+struct Fact {
+    text: String,
+    body: StatementBody,
+}
+
+impl Fact {
+    fn comparison(lhs: Term, op: Comparison, rhs: Term) -> Self {
+        Fact {
+            text: format!("{} {} {}", lhs, op.symbol(), rhs),
+            body: StatementBody::Comparison { lhs, op, rhs },
+        }
+    }
+
+    /// The arithmetic reading of a Python condition such as `x > 0 and not y == 1`
+    fn condition(code: &str, expr: &ast::Expr) -> Option<Self> {
+        let body = condition_body(code, expr)?;
+        Some(Fact { text: source_text(code, expr), body })
+    }
+
+    /// Whether the variable `name` occurs in the fact
+    fn mentions(&self, name: &str) -> bool {
+        fn mentions(body: &StatementBody, name: &str) -> bool {
+            match body {
+                StatementBody::Atom(predicate) => predicate.args.iter().any(|arg| arg == name),
+                StatementBody::Comparison { lhs, rhs, .. } => lhs.variables().into_iter().chain(rhs.variables()).any(|variable| variable == name),
+                StatementBody::Not(inner) => mentions(inner, name),
+                StatementBody::Implies(premise, conclusion) => mentions(premise, name) || mentions(conclusion, name),
+                StatementBody::And(parts) | StatementBody::Or(parts) | StatementBody::ExactlyOne(parts) => parts.iter().any(|part| mentions(part, name)),
+            }
+        }
+        mentions(&self.body, name)
+    }
+
+    fn negated(&self) -> Self {
+        Fact {
+            text: format!("not ({})", self.text),
+            body: StatementBody::Not(Box::new(self.body.clone())),
+        }
+    }
+
+    fn statement(&self, id: String) -> Statement {
+        Statement {
+            id,
+            text: self.text.clone(),
+            predicates: vec![],
+            body: Some(self.body.clone()),
+            quantifier: None,
+            weight: None,
+        }
+    }
+}
+
+fn condition_body(code: &str, expr: &ast::Expr) -> Option<StatementBody> {
+    match expr {
+        ast::Expr::Compare(compare) if compare.ops.len() == 1 => {
+            let (lhs, op, rhs) = arithmetic::parse_comparison(&source_text(code, compare)).ok()?;
+            Some(StatementBody::Comparison { lhs, op, rhs })
+        }
+        ast::Expr::BoolOp(bool_op) => {
+            let parts = bool_op.values.iter().map(|value| condition_body(code, value)).collect::<Option<Vec<_>>>()?;
+            Some(match bool_op.op {
+                ast::BoolOp::And => StatementBody::And(parts),
+                ast::BoolOp::Or => StatementBody::Or(parts),
+            })
+        }
+        ast::Expr::UnaryOp(unary) if matches!(unary.op, ast::UnaryOp::Not) => {
+            Some(StatementBody::Not(Box::new(condition_body(code, &unary.operand)?)))
+        }
+        _ => None,
+    }
+}
+
+/// The identifiers in `text`: runs of letters, digits and underscores
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_')).filter(|word| !word.is_empty())
+}
+
+/// `text` with every identifier equal to `word` replaced by `replacement`
+fn replace_word(text: &str, word: &str, replacement: &str) -> String {
+    let mut replaced = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| c.is_alphanumeric() || c == '_') {
+        replaced.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        replaced.push_str(if &rest[..end] == word { replacement } else { &rest[..end] });
+        rest = &rest[end..];
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+/// `term` with parameters replaced by their arguments, or None when it mentions an
+/// unbound parameter
+fn substitute(term: &Term, bindings: &HashMap<String, Term>) -> Option<Term> {
+    let binary = |a: &Term, b: &Term| Some((Box::new(substitute(a, bindings)?), Box::new(substitute(b, bindings)?)));
+    Some(match term {
+        Term::Const(_) | Term::Ratio(_, _) => term.clone(),
+        Term::Var(name) => bindings.get(name)?.clone(),
+        Term::Neg(inner) => Term::Neg(Box::new(substitute(inner, bindings)?)),
+        Term::Add(a, b) => binary(a, b).map(|(a, b)| Term::Add(a, b))?,
+        Term::Sub(a, b) => binary(a, b).map(|(a, b)| Term::Sub(a, b))?,
+        Term::Mul(a, b) => binary(a, b).map(|(a, b)| Term::Mul(a, b))?,
+    })
+}
+
+/// The definition a dedented function source parses to
+fn parse_definition(source: &str) -> Option<ast::Stmt> {
+    ast::Suite::parse(source, "<function>").ok()?.into_iter().next()
+}
+
+/// Parameters of a definition that positional arguments bind, without `self`/`cls`
+fn positional_parameters(def: &ast::Stmt) -> Vec<String> {
+    let arguments = match def {
+        ast::Stmt::FunctionDef(def) => &def.args,
+        ast::Stmt::AsyncFunctionDef(def) => &def.args,
+        _ => return Vec::new(),
+    };
+    arguments.posonlyargs
+        .iter()
+        .chain(&arguments.args)
+        .map(|arg| arg.def.arg.to_string())
+        .filter(|name| name != "self" && name != "cls")
+        .collect()
+}
+
+/// The function `expr` calls by name, with the call
+fn called_function(expr: &ast::Expr) -> Option<(&str, &ast::ExprCall)> {
+    let ast::Expr::Call(call) = expr else { return None };
+    let ast::Expr::Name(name) = call.func.as_ref() else { return None };
+    Some((name.id.as_str(), call))
+}
+
+/// A call in a function body with the conditions known to hold where it is made
+struct CallSite {
+    call: ast::ExprCall,
+    conditions: Vec<Fact>,
+}
+
+/// Walks a function body for its calls and the names it assigns. Nested function and
+/// class definitions are skipped; they do not run as part of the body.
+struct CallScan<'a> {
+    code: &'a str,
+    calls: Vec<CallSite>,
+    /// Every binding of a plain name, with the assigned value for `=` and `x: T = ...`
+    bindings: Vec<(String, Option<ast::Expr>)>,
+}
+
+impl<'a> CallScan<'a> {
+    fn new(code: &'a str) -> Self {
+        CallScan { code, calls: Vec::new(), bindings: Vec::new() }
+    }
+
+    fn scan(&mut self, body: &[ast::Stmt], conditions: &[Fact]) {
+        let mut conditions = conditions.to_vec();
+        for stmt in body {
+            match stmt {
+                ast::Stmt::If(s) => {
+                    self.calls_in(&s.test, &conditions);
+                    let guard = Fact::condition(self.code, &s.test);
+                    let branch = |fact: Option<Fact>| conditions.iter().cloned().chain(fact).collect::<Vec<_>>();
+                    self.scan(&s.body, &branch(guard.clone()));
+                    self.scan(&s.orelse, &branch(guard.as_ref().map(Fact::negated)));
+                    // Past an `if` one branch of which always leaves, the other was taken
+                    if let Some(guard) = guard {
+                        if !completes_normally(&s.body) {
+                            conditions.push(guard.negated());
+                        } else if !s.orelse.is_empty() && !completes_normally(&s.orelse) {
+                            conditions.push(guard);
+                        }
+                    }
+                }
+                ast::Stmt::While(s) => {
+                    self.calls_in(&s.test, &conditions);
+                    let guard = Fact::condition(self.code, &s.test);
+                    self.scan(&s.body, &conditions.iter().cloned().chain(guard).collect::<Vec<_>>());
+                    self.scan(&s.orelse, &conditions);
+                }
+                ast::Stmt::For(s) => {
+                    self.calls_in(&s.iter, &conditions);
+                    self.bind_targets(&s.target, None);
+                    self.scan(&s.body, &conditions);
+                    self.scan(&s.orelse, &conditions);
+                }
+                ast::Stmt::AsyncFor(s) => {
+                    self.calls_in(&s.iter, &conditions);
+                    self.bind_targets(&s.target, None);
+                    self.scan(&s.body, &conditions);
+                    self.scan(&s.orelse, &conditions);
+                }
+                ast::Stmt::With(s) => {
+                    for item in &s.items {
+                        self.calls_in(&item.context_expr, &conditions);
+                    }
+                    self.scan(&s.body, &conditions);
+                }
+                ast::Stmt::AsyncWith(s) => {
+                    for item in &s.items {
+                        self.calls_in(&item.context_expr, &conditions);
+                    }
+                    self.scan(&s.body, &conditions);
+                }
+                ast::Stmt::Try(s) => {
+                    self.scan(&s.body, &conditions);
+                    for ast::ExceptHandler::ExceptHandler(handler) in &s.handlers {
+                        self.scan(&handler.body, &conditions);
+                    }
+                    self.scan(&s.orelse, &conditions);
+                    self.scan(&s.finalbody, &conditions);
+                }
+                ast::Stmt::Match(s) => {
+                    self.calls_in(&s.subject, &conditions);
+                    for case in &s.cases {
+                        self.scan(&case.body, &conditions);
+                    }
+                }
+                ast::Stmt::FunctionDef(_) | ast::Stmt::AsyncFunctionDef(_) | ast::Stmt::ClassDef(_) => {}
+                ast::Stmt::Assert(s) => {
+                    self.calls_in(&s.test, &conditions);
+                    conditions.extend(Fact::condition(self.code, &s.test));
+                }
+                ast::Stmt::Assign(s) => {
+                    self.calls_in(&s.value, &conditions);
+                    for target in &s.targets {
+                        self.bind_targets(target, Some(&s.value));
+                    }
+                }
+                ast::Stmt::AnnAssign(s) => {
+                    if let Some(value) = &s.value {
+                        self.calls_in(value, &conditions);
+                    }
+                    self.bind_targets(&s.target, s.value.as_deref());
+                }
+                ast::Stmt::AugAssign(s) => {
+                    self.calls_in(&s.value, &conditions);
+                    self.bind_targets(&s.target, None);
+                }
+                _ => {
+                    let mut collector = CallCollector::default();
+                    collector.visit_stmt(stmt.clone());
+                    self.record(collector, &conditions);
+                }
+            }
+        }
+    }
+
+    fn calls_in(&mut self, expr: &ast::Expr, conditions: &[Fact]) {
+        let mut collector = CallCollector::default();
+        collector.visit_expr(expr.clone());
+        self.record(collector, conditions);
+    }
+
+    fn record(&mut self, collector: CallCollector, conditions: &[Fact]) {
+        self.calls.extend(collector.calls.into_iter().map(|call| CallSite { call, conditions: conditions.to_vec() }));
+    }
+
+    /// Record the names bound by an assignment target; only a plain name is bound to
+    /// the whole value
+    fn bind_targets(&mut self, target: &ast::Expr, value: Option<&ast::Expr>) {
+        match target {
+            ast::Expr::Name(name) => self.bindings.push((name.id.to_string(), value.cloned())),
+            ast::Expr::Tuple(tuple) => tuple.elts.iter().for_each(|elt| self.bind_targets(elt, None)),
+            ast::Expr::List(list) => list.elts.iter().for_each(|elt| self.bind_targets(elt, None)),
+            ast::Expr::Starred(starred) => self.bind_targets(&starred.value, None),
+            _ => {}
+        }
+    }
+
+    /// Names bound exactly once in the body, by a plain assignment, with their value;
+    /// a parameter is already bound on entry
+    fn single_assignments(&self, parameters: &[String]) -> Vec<(&str, &ast::Expr)> {
+        let reassigned = self.reassigned(parameters);
+        self.bindings
+            .iter()
+            .filter(|(name, _)| !reassigned.contains(&name.as_str()))
+            .filter_map(|(name, value)| Some((name.as_str(), value.as_ref()?)))
+            .collect()
+    }
+
+    /// Names taking more than one value in the body: those bound twice, and parameters
+    /// bound at all
+    fn reassigned(&self, parameters: &[String]) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for (name, _) in &self.bindings {
+            let bound_before = parameters.contains(name) || self.bindings.iter().filter(|(other, _)| other == name).count() > 1;
+            if bound_before && !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+}
+
+/// Every call in a module with the function it is made in, named like
+/// [`ModuleFunction::qualified_name`] (`<module>` for top-level code), and the class
+/// whose methods `self` and `cls` refer to there
+#[derive(Default)]
+struct ScopedCalls {
+    calls: Vec<ScopedCall>,
+    /// Enclosing definitions, innermost last, with whether each is a class
+    scope: Vec<(String, bool)>,
+}
+
+struct ScopedCall {
+    caller: String,
+    class: Option<String>,
+    call: ast::ExprCall,
+}
+
+impl ScopedCalls {
+    fn enter(&mut self, name: &str, is_class: bool) {
+        self.scope.push((name.to_string(), is_class));
+    }
+
+    fn qualified(&self, scope: &[(String, bool)]) -> String {
+        scope.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(".")
+    }
+}
+
+impl Visitor for ScopedCalls {
+    fn visit_stmt_function_def(&mut self, node: ast::StmtFunctionDef) {
+        self.enter(node.name.as_str(), false);
+        self.generic_visit_stmt_function_def(node);
+        self.scope.pop();
+    }
+
+    fn visit_stmt_async_function_def(&mut self, node: ast::StmtAsyncFunctionDef) {
+        self.enter(node.name.as_str(), false);
+        self.generic_visit_stmt_async_function_def(node);
+        self.scope.pop();
+    }
+
+    fn visit_stmt_class_def(&mut self, node: ast::StmtClassDef) {
+        self.enter(node.name.as_str(), true);
+        self.generic_visit_stmt_class_def(node);
+        self.scope.pop();
+    }
+
+    fn visit_expr_call(&mut self, node: ast::ExprCall) {
+        let caller = match self.scope.iter().rposition(|(_, is_class)| !is_class) {
+            Some(function) => self.qualified(&self.scope[..=function]),
+            None => "<module>".to_string(),
+        };
+        // `self` names an instance of the class the method is defined in
+        let class = self.scope
+            .iter()
+            .rposition(|(_, is_class)| *is_class)
+            .filter(|&class| class + 1 < self.scope.len())
+            .map(|class| self.qualified(&self.scope[..=class]));
+        self.calls.push(ScopedCall { caller, class, call: node.clone() });
+        self.generic_visit_expr_call(node)
+    }
+}
+
+/// Collects the calls in an expression or simple statement, except those inside lambdas
+#[derive(Default)]
+struct CallCollector {
+    calls: Vec<ast::ExprCall>,
+}
+
+impl Visitor for CallCollector {
+    fn visit_expr_lambda(&mut self, _node: ast::ExprLambda) {}
+
+    fn visit_expr_call(&mut self, node: ast::ExprCall) {
+        self.calls.push(node.clone());
+        self.generic_visit_expr_call(node)
+    }
+}
+
+/// Value range of a fixed-width integer type name such as `uint8` or `np.int16`.
+/// `uint64` is not supported since its upper bound does not fit the arithmetic terms,
+/// and `int64` stops at `-(2^63 - 1)` since only that magnitude parses as a literal.
+fn integer_range(ty: &str) -> Option<(i64, i64)> {
+    let ty = ty.rsplit('.').next().unwrap_or(ty);
+    let (signed, bits) = match ty.strip_prefix("uint") {
+        Some(bits) => (false, bits),
+        None => (true, ty.strip_prefix("int")?),
+    };
+    match (signed, bits.parse::<u32>().ok()?) {
+        (true, 64) => Some((-i64::MAX, i64::MAX)),
+        (true, bits @ (8 | 16 | 32)) => Some((i64::MIN >> (64 - bits), i64::MAX >> (64 - bits))),
+        (false, bits @ (8 | 16 | 32)) => Some((0, (1i64 << bits) - 1)),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "z3"))]
+mod tests {
+    use super::*;
     use z3::Config;
 
+    #[test]
+    fn test_sign_contract_against_return() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let negate = r#"
+def negate(x):
+    """Returns a positive number. x must be non-negative."""
+    return -x
+"#;
+        let contract = ContractExtractor.extract_contract_from_text(negate).unwrap();
+        assert_eq!(contract.preconditions, vec!["x >= 0"]);
+        assert_eq!(contract.postconditions, vec!["result > 0"]);
+
+        let result = checker.verify_function(negate).unwrap();
+        assert!(!result.is_coherent);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::ContractImplementationMismatch));
+
+        let successor = negate.replace("return -x", "return x + 1");
+        let result = checker.verify_function(&successor).unwrap();
+        assert!(result.is_coherent);
+    }
+
+    #[test]
+    fn test_requires_ensures_annotations() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let double = r#"
+def double(x, y):
+    """Doubles x.
+
+    @requires x >= 0
+      @requires   y  <  x
+    @ensures result >= 2*x"""
+    return x + x
+"#;
+        let contract = ContractExtractor.extract_contract_from_text(double).unwrap();
+        assert_eq!(contract.preconditions, vec!["x >= 0", "y < x"]);
+        assert_eq!(contract.postconditions, vec!["result >= 2*x"]);
+        assert!(checker.verify_function(double).unwrap().is_coherent);
+
+        let violated = double.replace("return x + x", "return y - 1");
+        let result = checker.verify_function(&violated).unwrap();
+        assert!(!result.is_coherent);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::ContractImplementationMismatch));
+
+        let malformed = double.replace("@ensures result >= 2*x", "@ensures the result is large");
+        assert!(checker.verify_function(&malformed).is_err());
+    }
+
+    #[test]
+    fn test_return_annotation_against_literal_returns() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let label = r#"
+def label(count: int, name, *rest: str) -> str:
+    """Describes the count."""
+    if count == 0:
+        return "none"
+    return f"{count} {name}"
+"#;
+        let contract = ContractExtractor.extract_contract_from_text(label).unwrap();
+        assert_eq!(contract.input_types, vec!["int", "Any", "str"]);
+        assert_eq!(contract.output_type.as_deref(), Some("str"));
+        let result = checker.verify_function(label).unwrap();
+        assert!(result.is_coherent);
+        assert!(result.violations.is_empty());
+
+        let mismatched = label.replace("-> str", "-> int");
+        let result = checker.verify_function(&mismatched).unwrap();
+        assert!(!result.is_coherent);
+        assert_eq!(result.violations.len(), 2);
+        assert!(result.violations.iter().all(|v| matches!(v.violation_type, ViolationType::TypeIncoherence)));
+
+        // Optional admits None, float admits int; unannotated and Any never conflict
+        let optional = "def f(x) -> Optional[float]:\n    if x:\n        return None\n    return 1\n";
+        assert!(checker.verify_function(optional).unwrap().is_coherent);
+        let unannotated = "def f(x):\n    return \"text\"\n";
+        assert!(checker.verify_function(unannotated).unwrap().violations.is_empty());
+        let any = "def f(x) -> Any:\n    return \"text\"\n";
+        assert!(checker.verify_function(any).unwrap().violations.is_empty());
+    }
+
+    #[test]
+    fn test_violation_positions() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let reversed = r#"
+def sort_list(items):
+    """Returns a sorted list in ascending order."""
+    if not items:
+        return []
+    return items[::-1]
+"#;
+        let result = checker.verify_function(reversed).unwrap();
+        assert!(!result.is_coherent);
+        let return_line = reversed.lines().position(|line| line.contains("return items[::-1]")).unwrap() + 1;
+        assert_eq!((result.violations[0].line, result.violations[0].column), (return_line, 5));
+
+        // A contract that cannot be met points at the docstring
+        let constant = "def sort_fast(items):\n    \"\"\"Sorts in O(1) time.\"\"\"\n    return sorted(items)\n";
+        let result = checker.verify_function(constant).unwrap();
+        assert!(matches!(result.violations[0].violation_type, ViolationType::LogicalImpossibility));
+        assert_eq!((result.violations[0].line, result.violations[0].column), (2, 5));
+
+        // Positions in a module are relative to the file
+        let module = format!("import os\n\nclass Sorter:\n    {}", reversed.trim_start().replace('\n', "\n    "));
+        let results = checker.verify_module(&module).unwrap();
+        let violation = &results[0].violations[0];
+        assert_eq!((violation.line, violation.column), (return_line + 2, 9));
+        assert_eq!(module.lines().nth(violation.line - 1).unwrap().trim(), "return items[::-1]");
+    }
+
+    #[test]
+    fn test_purity_contract_against_mutations() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let pure = r#"
+def reversed_copy(items):
+    """Returns the items in reverse order. Does not modify the input list."""
+    result = list(items)
+    result.reverse()
+    return result
+"#;
+        let result = checker.verify_function(pure).unwrap();
+        assert!(result.is_coherent);
+        assert!(result.violations.is_empty());
+
+        let mutating = pure.replace("result = list(items)\n    result.reverse()", "items.reverse()\n    result = items");
+        let result = checker.verify_function(&mutating).unwrap();
+        assert!(!result.is_coherent);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::StateContradiction));
+        assert!(result.violations[0].description.contains("items.reverse()"));
+
+        // Rebinding the parameter name makes later changes local; stores into the
+        // argument object and deletions from it are mutations
+        let rebound = pure.replace("result = list(items)\n    result.reverse()", "items = items[:]\n    items.sort()\n    result = items");
+        assert!(checker.verify_function(&rebound).unwrap().is_coherent);
+        for body in ["items[0] = None", "del items[0]", "items.meta.count += 1"] {
+            let mutating = pure.replace("result = list(items)", &format!("{}\n    result = list(items)", body));
+            assert!(!checker.verify_function(&mutating).unwrap().is_coherent, "{}", body);
+        }
+
+        // Without the promise, mutating the argument is allowed
+        let undocumented = mutating.replace(" Does not modify the input list.", "");
+        assert!(checker.verify_function(&undocumented).unwrap().is_coherent);
+    }
+
+    #[test]
+    fn test_missing_return_against_annotation() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let missing_else = r#"
+def first_truthy(x: int) -> int:
+    """Returns x when it is truthy."""
+    if x:
+        return x
+"#;
+        let result = checker.verify_function(missing_else).unwrap();
+        assert!(!result.is_coherent);
+        assert_eq!(result.violations.len(), 1);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::ContractImplementationMismatch));
+
+        let both_arms = missing_else.replace("        return x\n", "        return x\n    else:\n        return 0\n");
+        assert!(checker.verify_function(&both_arms).unwrap().is_coherent);
+        let optional = missing_else.replace("-> int", "-> Optional[int]");
+        assert!(checker.verify_function(&optional).unwrap().is_coherent);
+        let bare = missing_else.replace("return x\n", "return\n    return x\n");
+        assert!(!checker.verify_function(&bare).unwrap().is_coherent);
+
+        // Paths that always raise or loop forever never return None
+        let raising = missing_else.replace("        return x\n", "        return x\n    raise ValueError(x)\n");
+        assert!(checker.verify_function(&raising).unwrap().is_coherent);
+        let looping = "def f(x) -> int:\n    while True:\n        if x:\n            return x\n";
+        assert!(checker.verify_function(looping).unwrap().is_coherent);
+        let stub = "def f(x) -> int:\n    ...\n";
+        assert!(checker.verify_function(stub).unwrap().is_coherent);
+    }
+
+    #[test]
+    fn test_registered_translation_rule() {
+        struct UniqueVersusDuplicated;
+
+        impl TranslationRule for UniqueVersusDuplicated {
+            fn apply(&self, contract: &FunctionContract, implementation: &ImplementationLogic) -> Vec<Statement> {
+                let unique = contract.docstring.as_deref().is_some_and(|doc| doc.contains("unique"));
+                let duplicated = implementation.return_expressions.iter().any(|expr| {
+                    expr.split_once(" + ").is_some_and(|(lhs, rhs)| lhs == rhs)
+                });
+                if !unique || !duplicated {
+                    return Vec::new();
+                }
+                vec![
+                    rules::atom_statement("unique_contract", "Contract: items are unique", "unique", &["result"], false),
+                    rules::atom_statement("duplicated_return", "Implementation repeats the items", "unique", &["result"], true),
+                ]
+            }
+        }
+
+        let doubled = r#"
+def distinct(items):
+    """Returns the unique items."""
+    return items + items
+"#;
+        let contract = ContractExtractor.extract_contract_from_text(doubled).unwrap();
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+        let implementation = checker.analyze_implementation_from_text(doubled).unwrap();
+
+        let mut translator = PredicateTranslator::default();
+        let builtin = translator.translate_to_predicates(&contract, &implementation).unwrap();
+        translator.register(UniqueVersusDuplicated);
+        let statements = translator.translate_to_predicates(&contract, &implementation).unwrap();
+        let added: Vec<&str> = statements[builtin.len()..].iter().map(|statement| statement.id.as_str()).collect();
+        assert_eq!(added, vec!["unique_contract", "duplicated_return"]);
+
+        assert!(checker.verify_function(doubled).unwrap().is_coherent);
+        checker.register_rule(UniqueVersusDuplicated);
+        let result = checker.verify_function(doubled).unwrap();
+        assert!(!result.is_coherent);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::ContractImplementationMismatch));
+        let deduplicated = doubled.replace("items + items", "list(set(items))");
+        assert!(checker.verify_function(&deduplicated).unwrap().is_coherent);
+
+        // The rule, not the statement IDs, decides how the conflict is reported
+        struct TypedUniqueVersusDuplicated;
+
+        impl TranslationRule for TypedUniqueVersusDuplicated {
+            fn apply(&self, contract: &FunctionContract, implementation: &ImplementationLogic) -> Vec<Statement> {
+                let mut statements = UniqueVersusDuplicated.apply(contract, implementation);
+                for statement in &mut statements {
+                    statement.id = format!("impossible_{}", statement.id);
+                }
+                statements
+            }
+
+            fn violation_type(&self) -> ViolationType {
+                ViolationType::TypeIncoherence
+            }
+        }
+
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+        checker.register_rule(TypedUniqueVersusDuplicated);
+        let result = checker.verify_function(doubled).unwrap();
+        assert!(!result.is_coherent);
+        assert_eq!(result.violations[0].violation_type, ViolationType::TypeIncoherence);
+    }
+
     #[test]
     fn test_simple_function_verification() {
         let cfg = Config::new();
@@ -351,4 +2894,694 @@ def sort_list(items):
         assert!(!result.is_coherent);
         assert!(!result.violations.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_return_analysis_uses_syntax_tree() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let checker = CodeCoherenceChecker::new(&ctx);
+
+        // A return spanning lines, after a comment that mentions `return` and `sorted(`
+        let logic = checker.analyze_implementation_from_text(r#"
+def sort_list(items):
+    """Returns a sorted list in ascending order."""
+    # return sorted(items) was too slow
+    def helper(x):
+        return sorted(x)
+    return (
+        items
+    )[::-1]
+"#).unwrap();
+        assert_eq!(logic.return_conditions, vec!["returns_reversed_result"]);
+        assert_eq!(logic.return_expressions, vec!["( items )[::-1]"]);
+
+        let logic = checker.analyze_implementation_from_text(r#"
+def describe(items):
+    return "call sorted(items) first"
+"#).unwrap();
+        assert_eq!(logic.return_conditions, vec!["returns_value"]);
+    }
+
+    #[test]
+    fn test_exception_chain_mismatch() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let python_code = r#"
+def load_config(path):
+    """Load the configuration file.
+
+    Raises ConfigError caused by an IOError.
+    """
+    try:
+        data = parse(path)
+    except ValueError as err:
+        raise ConfigError("bad config") from err
+    return data
+"#;
+
+        let result = checker.verify_function(python_code).unwrap();
+        assert!(!result.is_coherent);
+
+        let matching = python_code.replace("except ValueError", "except IOError");
+        let result = checker.verify_function(&matching).unwrap();
+        assert!(result.is_coherent);
+
+        // A raise split over lines still carries its cause
+        let multiline = matching.replace(
+            r#"raise ConfigError("bad config") from err"#,
+            "raise ConfigError(\n            \"bad config\",\n        ) from err",
+        );
+        let result = checker.verify_function(&multiline).unwrap();
+        assert!(result.is_coherent);
+
+        // `err` is no longer the caught exception
+        let rebound = multiline.replace("except IOError as err", "except IOError as cause");
+        let result = checker.verify_function(&rebound).unwrap();
+        assert!(!result.is_coherent);
+    }
+
+    #[test]
+    fn test_internal_call_to_deprecated_function() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let python_code = r#"
+def old_total(items):
+    """Deprecated, use `total()` instead."""
+    return sum(items)
+
+def total(items):
+    """Returns the sum of the items."""
+    return sum(items)
+
+def report(items):
+    """Returns a summary line."""
+    return "total: %d" % old_total(items)
+"#;
+
+        let results = checker.verify_module(python_code).unwrap();
+        let report = results.iter().find(|result| result.location == "report").unwrap();
+        let warnings: Vec<&CoherenceViolation> = report.violations
+            .iter()
+            .filter(|v| matches!(v.violation_type, ViolationType::DeprecatedUsage))
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, Severity::Warning);
+        assert_eq!(warnings[0].location, "report");
+        assert_eq!(warnings[0].line, 12);
+        assert_eq!(warnings[0].description, "Calls deprecated function old_total(); use total instead");
+        assert!(report.is_coherent);
+
+        // Only a docstring saying so, a directive or a decorator marks a function
+        // deprecated, and only a quoted or called name is a replacement
+        let python_code = r#"
+def new_api():
+    """Replacement for the deprecated old_api(); use this one."""
+    return 1
+
+def fetch():
+    """Fetches data.
+
+    .. deprecated:: 2.0
+       Use load_data() instead.
+    """
+    return 1
+
+@warnings.deprecated("going away")
+def legacy():
+    return 1
+
+def caller():
+    return new_api() + fetch() + legacy()
+"#;
+        let violations = checker.find_deprecated_calls(python_code).unwrap();
+        let descriptions: Vec<&str> = violations.iter().map(|v| v.description.as_str()).collect();
+        assert_eq!(descriptions, vec![
+            "Calls deprecated function fetch(); use load_data instead",
+            "Calls deprecated function legacy()",
+        ]);
+        assert!(violations.iter().all(|v| v.location == "caller"));
+
+        // Methods resolve through their class; strings and comments hold no calls
+        let python_code = r#"
+class Legacy:
+    def fetch(self):
+        """Deprecated, use `load()` instead."""
+        return 1
+
+    def run(self):
+        """Mentions fetch() in passing."""
+        # fetch() is on its way out
+        return self.fetch()
+
+class Fresh:
+    def fetch(self):
+        """Returns one."""
+        return 1
+
+    def run(self):
+        """Returns one."""
+        return self.fetch()
+"#;
+        let results = checker.verify_module(python_code).unwrap();
+        let warnings: Vec<(&str, &CoherenceViolation)> = results
+            .iter()
+            .flat_map(|result| result.violations.iter().map(move |violation| (result.location.as_str(), violation)))
+            .filter(|(_, v)| matches!(v.violation_type, ViolationType::DeprecatedUsage))
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, "Legacy.run");
+        assert_eq!(warnings[0].1.location, "Legacy.run");
+        assert_eq!((warnings[0].1.line, warnings[0].1.column), (10, 16));
+    }
+
+    #[test]
+    fn test_method_breaks_class_invariant() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let python_code = r#"
+class Account:
+    """A bank account.
+
+    Invariant: balance >= 0
+    """
+
+    def deposit(self, amount):
+        """Adds money. amount must be non-negative."""
+        self.balance += amount
+
+    def overdraw(self):
+        """Puts the account in the red."""
+        self.balance = self.balance - self.balance - 1
+
+    @staticmethod
+    def opening_balance(balance):
+        """Returns a non-negative number."""
+        return balance
+"#;
+
+        let results = checker.verify_module(python_code).unwrap();
+        let locations: Vec<&str> = results.iter().map(|result| result.location.as_str()).collect();
+        assert_eq!(locations, vec!["Account.deposit", "Account.overdraw", "Account.opening_balance"]);
+        assert!(results[0].is_coherent);
+        assert!(!results[1].is_coherent);
+        assert!(matches!(results[1].violations[0].violation_type, ViolationType::StateContradiction));
+        assert_eq!(results[1].violations[0].location, "Account.overdraw");
+        assert_eq!(
+            results[1].violations[0].formal_contradiction,
+            "balance >= 0 ∧ balance' = balance - balance - 1 ⊢ ¬(balance' >= 0)",
+        );
+        assert!(results[2].is_coherent);
+    }
+
+    #[test]
+    fn test_module_functions_verified_separately() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let python_code = r#"
+def sort_list(items):
+    """Returns a sorted list in ascending order."""
+    def key(item):
+        return item
+    return sorted(items, key=key)
+
+def sort_desc(items):
+    """Returns a sorted list in ascending order."""
+    return items[::-1]
+"#;
+
+        let results = checker.verify_module(python_code).unwrap();
+        let locations: Vec<&str> = results.iter().map(|result| result.location.as_str()).collect();
+        assert_eq!(locations, vec!["sort_list", "sort_list.key", "sort_desc"]);
+        assert!(results[0].is_coherent && results[1].is_coherent);
+        assert!(!results[2].is_coherent);
+        assert_eq!(results[2].violations[0].location, "sort_desc");
+    }
+
+    #[test]
+    fn test_fixed_width_return_range() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let python_code = r#"
+def shift(x: uint8):
+    """Returns a uint8, 0-255."""
+    return x + 256
+"#;
+        let result = checker.verify_function(python_code).unwrap();
+        assert!(!result.is_coherent);
+        assert!(result.violations.iter().any(|v| matches!(v.violation_type, ViolationType::LogicalImpossibility)));
+
+        let result = checker.verify_function(&python_code.replace("x + 256", "x + 1")).unwrap();
+        assert!(result.is_coherent);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].severity, Severity::Warning);
+
+        let result = checker.verify_function(&python_code.replace("x + 256", "x // 2")).unwrap();
+        assert!(result.violations.is_empty());
+    }
+
+    #[test]
+    fn test_undecided_return_range_is_not_an_error() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::with_backend(coherence_verifier::CoherenceVerifier::with_timeout(&ctx, std::time::Duration::from_millis(50)));
+
+        // Fitting int8 means factoring 1000000007 * 1000000009: far too slow for the budget
+        let python_code = r#"
+def near(x: uint32, y: uint32):
+    """Returns an int8."""
+    return (x + 2)*(y + 2) - 1000000016000000063
+"#;
+        let result = checker.verify_function(python_code).unwrap();
+        assert!(result.is_coherent, "{:?}", result.violations);
+        assert!(result.violations.iter().all(|v| v.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn test_constant_time_contract_against_body() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let constant = r#"
+def first(items):
+    """Returns the first item in O(1)."""
+    return items[0]
+"#;
+        let result = checker.verify_function(constant).unwrap();
+        assert!(result.is_coherent);
+        assert!(result.violations.is_empty());
+
+        let looping = r#"
+def contains(items, target):
+    """Checks membership in constant time."""
+    for item in items:
+        if item == target:
+            return True
+    return False
+"#;
+        let result = checker.verify_function(looping).unwrap();
+        assert!(!result.is_coherent);
+        assert_eq!(result.violations.len(), 1);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::LogicalImpossibility));
+
+        // Iteration hidden in a comprehension or a recursive call counts too
+        let comprehension = "def squares(xs):\n    \"\"\"O(1) squares.\"\"\"\n    return [x * x for x in xs]\n";
+        assert!(!checker.verify_function(comprehension).unwrap().is_coherent);
+        let recursive = "def length(xs):\n    \"\"\"O(1) length.\"\"\"\n    return 0 if not xs else 1 + length(xs[1:])\n";
+        assert!(!checker.verify_function(recursive).unwrap().is_coherent);
+    }
+
+    #[test]
+    fn test_documented_complexity_against_loop_nesting() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let nested = r#"
+def has_duplicates(items):
+    """Checks for duplicates in O(n) time."""
+    for i in range(len(items)):
+        for j in range(i + 1, len(items)):
+            if items[i] == items[j]:
+                return True
+    return False
+"#;
+        let result = checker.verify_function(nested).unwrap();
+        let warnings: Vec<&CoherenceViolation> = result.violations
+            .iter()
+            .filter(|v| matches!(v.violation_type, ViolationType::ComplexityMismatch))
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, Severity::Warning);
+        assert!(result.is_coherent);
+
+        let quadratic = nested.replace("O(n)", "O(n^2)");
+        assert!(checker.verify_function(&quadratic).unwrap().violations.is_empty());
+
+        let linear = r#"
+def total(items):
+    """Sums the items in O(n)."""
+    result = 0
+    for item in items:
+        result += item
+    return result
+"#;
+        assert!(checker.verify_function(linear).unwrap().violations.is_empty());
+
+        // Only reads of the parameter count, not an attribute that shares its name
+        let attribute = r#"
+def tally(items):
+    """Counts in O(n)."""
+    count = 0
+    for item in items:
+        for flag in SETTINGS.items:
+            count += 1
+    return count
+"#;
+        assert!(checker.verify_function(attribute).unwrap().violations.is_empty());
+
+        let comprehension = r#"
+def pairs(items):
+    """Pairs the items in O(n)."""
+    return [(a, b) for a in items for b in items]
+"#;
+        let result = checker.verify_function(comprehension).unwrap();
+        assert!(result.violations.iter().any(|v| matches!(v.violation_type, ViolationType::ComplexityMismatch)));
+    }
+
+    #[test]
+    fn test_rust_doc_contracts() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = RustCoherenceChecker::new(&ctx);
+
+        let reversed = r#"
+/// Ranks the scores.
+///
+/// # Postconditions
+/// - the result is sorted in ascending order
+pub fn rank(mut scores: Vec<i32>) -> Vec<i32> {
+    scores.sort();
+    scores.reverse();
+    scores
+}
+"#;
+        let result = checker.verify_function(reversed).unwrap();
+        assert!(!result.is_coherent);
+        let mismatch = result.violations
+            .iter()
+            .find(|v| matches!(v.violation_type, ViolationType::ContractImplementationMismatch))
+            .unwrap();
+        assert_eq!((mismatch.line, mismatch.column), (9, 5));
+
+        let sorted = reversed.replace("    scores.reverse();\n", "");
+        assert!(checker.verify_function(&sorted).unwrap().is_coherent);
+
+        let negated = r#"
+impl Account {
+    /// # Preconditions
+    /// - `x >= 0`
+    ///
+    /// # Postconditions
+    /// - `result > 0`
+    fn negate(x: i64) -> i64 {
+        -x
+    }
+}
+"#;
+        let results = checker.verify_file(negated).unwrap();
+        assert_eq!(results[0].location, "Account.negate");
+        assert!(!results[0].is_coherent);
+    }
+
+    #[test]
+    fn test_jsdoc_contracts() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = JsCoherenceChecker::new(&ctx);
+
+        let reversed = r#"
+/**
+ * Ranks the scores.
+ * @param {number[]} scores
+ * @returns {number[]} the scores sorted in ascending order
+ */
+export const rank = (scores) => [...scores].sort((a, b) => a - b).reverse();
+"#;
+        let result = checker.verify_function(reversed).unwrap();
+        assert!(!result.is_coherent);
+        assert!(result.violations.iter().any(|v| matches!(v.violation_type, ViolationType::ContractImplementationMismatch)));
+        let sorted = reversed.replace(".reverse()", "");
+        assert!(checker.verify_function(&sorted).unwrap().is_coherent);
+
+        // Falling off the end returns undefined, which the documented number is not
+        let undefined = r#"
+class Stats {
+    /**
+     * @returns {number} the largest value
+     */
+    max(values: number[]) {
+        if (values.length > 0) {
+            return Math.max(...values);
+        }
+    }
+}
+"#;
+        let results = checker.verify_file(undefined).unwrap();
+        assert_eq!(results[0].location, "Stats.max");
+        assert!(!results[0].is_coherent);
+        assert!(results[0].violations[0].description.contains("undefined"));
+        assert_eq!((results[0].violations[0].line, results[0].violations[0].column), (6, 5));
+
+        let total = undefined.replace("        }\n    }", "        }\n        return 0;\n    }");
+        assert!(checker.verify_file(&total).unwrap()[0].is_coherent);
+    }
+
+    #[test]
+    fn test_watch_reverifies_on_change() {
+        let ctx = Context::new(&Config::new());
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+        let path = std::env::temp_dir().join(format!("code-checker-watch-{}.py", std::process::id()));
+        let coherent = "def add(a, b):\n    \"\"\"Returns the sum of two numbers.\"\"\"\n    return a + b\n";
+        let incoherent = "def negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n";
+        std::fs::write(&path, coherent).unwrap();
+
+        // Each run edits the file and signals the change, as the file watcher would; a
+        // burst of signals counts as one change, and dropping the sender ends the watch
+        let (sender, changes) = std::sync::mpsc::channel();
+        let mut sender = Some(sender);
+        let mut runs = Vec::new();
+        watch::run(&mut checker, &path, &changes, std::time::Duration::from_millis(10), |results| {
+            runs.push(results.map(|results| results.iter().all(|result| result.is_coherent)).map_err(|e| format!("{:#}", e)));
+            match runs.len() {
+                1 => std::fs::write(&path, incoherent)?,
+                2 => std::fs::write(&path, "def broken(:\n")?,
+                _ => {
+                    sender = None;
+                    return Ok(());
+                }
+            }
+            let sender = sender.as_ref().unwrap();
+            sender.send(())?;
+            sender.send(())?;
+            Ok(())
+        })
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0], Ok(true));
+        assert_eq!(runs[1], Ok(false));
+        // A syntax error is reported and watching goes on
+        assert!(runs[2].as_ref().unwrap_err().contains("Cannot parse Python code"), "{:?}", runs[2]);
+    }
+
+    #[test]
+    fn test_unsatisfiable_preconditions() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let clamp = r#"
+def clamp(x):
+    """Clamps x.
+
+    @requires x >= 0
+    @requires x < 0
+    """
+    return x
+"#;
+        let result = checker.verify_function(clamp).unwrap();
+        assert!(!result.is_coherent);
+        assert_eq!(result.violations.len(), 1);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::LogicalImpossibility));
+        assert!(result.violations[0].description.starts_with("Unsatisfiable precondition"));
+        assert!(result.violations[0].description.contains("x >= 0 and x < 0"));
+        assert_eq!(result.violations[0].line, 3);
+
+        let satisfiable = clamp.replace("@requires x < 0", "@requires x < 10");
+        assert!(checker.verify_function(&satisfiable).unwrap().is_coherent);
+    }
+
+    #[test]
+    fn test_caller_violates_callee_precondition() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let python_code = r#"
+def isqrt(n):
+    """Integer square root.
+
+    @requires n >= 0
+    @ensures result >= 0
+    """
+    return n
+
+def shrink(x):
+    """Shrinks x.
+
+    @requires x >= 0
+    """
+    return isqrt(x - 1)
+
+def guarded(x):
+    """Shrinks x when it is positive.
+
+    @requires x >= 0
+    """
+    if x == 0:
+        return 0
+    root = isqrt(x - 1)
+    return isqrt(root) + len(str(x))
+"#;
+
+        let results = checker.verify_module(python_code).unwrap();
+        let locations: Vec<&str> = results.iter().map(|result| result.location.as_str()).collect();
+        assert_eq!(locations, vec!["isqrt", "shrink", "guarded"]);
+        assert!(results[0].is_coherent);
+        assert!(results[2].is_coherent, "{:?}", results[2].violations);
+
+        assert!(!results[1].is_coherent);
+        let violation = &results[1].violations[0];
+        assert!(matches!(violation.violation_type, ViolationType::ContractImplementationMismatch));
+        assert_eq!(violation.description, "Calls isqrt(x - 1) which may break its precondition x - 1 >= 0");
+        assert_eq!((violation.line, violation.column), (15, 12));
+        assert_eq!(violation.formal_contradiction, "x >= 0 ⊬ x - 1 >= 0");
+
+        // Calls to functions defined elsewhere are not checked
+        let external = python_code.replace("def isqrt(n)", "def unused(n)");
+        assert!(checker.check_call_contracts(&external).unwrap().is_empty());
+
+        // A reassigned parameter no longer satisfies its precondition, and a guard on it
+        // no longer holds past the reassignment
+        let reassigned = r#"
+def isqrt(n):
+    """Integer square root.
+
+    @requires n >= 0
+    """
+    return n
+
+def shrink(x):
+    """Shrinks x.
+
+    @requires x >= 0
+    """
+    x = x - 1
+    return isqrt(x)
+
+def lower(y):
+    if y > 0:
+        y = y - 5
+        return isqrt(y)
+    return 0
+"#;
+        let violations = checker.check_call_contracts(reassigned).unwrap();
+        let locations: Vec<&str> = violations.iter().map(|violation| violation.location.as_str()).collect();
+        assert_eq!(locations, vec!["shrink", "lower"]);
+        assert_eq!(violations[0].formal_contradiction, "⊤ ⊬ x >= 0");
+        assert_eq!(violations[1].formal_contradiction, "⊤ ⊬ y >= 0");
+    }
+
+    #[test]
+    fn test_self_contradictory_docstring() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let rank = r#"
+def rank(items):
+    """Returns a sorted list in descending order but always ascending."""
+    return sorted(items)
+"#;
+        // Orders read from keywords only conflict as a warning
+        let result = checker.verify_function(rank).unwrap();
+        assert!(result.is_coherent, "{:?}", result.violations);
+        assert_eq!(result.violations.len(), 1);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::LogicalImpossibility));
+        assert_eq!(result.violations[0].severity, Severity::Warning);
+        assert_eq!(
+            result.violations[0].description,
+            "Contradictory docstring: no result satisfies result_ascending_order and result_descending_order",
+        );
+
+        // Alternatives offered in prose are valid documentation
+        let order = r#"
+def order(items, reverse=False):
+    """Returns the items sorted in ascending order, or descending when reverse is set."""
+    return sorted(items, reverse=reverse)
+"#;
+        let result = checker.verify_function(order).unwrap();
+        assert!(result.is_coherent, "{:?}", result.violations);
+        assert!(result.violations.iter().all(|v| v.severity == Severity::Warning));
+
+        let arithmetic = r#"
+def score(x):
+    """Returns a positive number.
+
+    @ensures result < 0
+    """
+    return x
+"#;
+        let result = checker.verify_function(arithmetic).unwrap();
+        assert!(!result.is_coherent);
+        assert!(matches!(result.violations[0].violation_type, ViolationType::LogicalImpossibility));
+        assert_eq!(result.violations[0].severity, Severity::Error);
+        assert_eq!(result.violations[0].formal_contradiction, "result > 0 ∧ result < 0 ⊢ ⊥");
+
+        // No extractable postconditions: nothing to contradict
+        let plain = rank.replace("Returns a sorted list in descending order but always ascending.", "Ranks the items.");
+        assert!(checker.verify_function(&plain).unwrap().is_coherent);
+    }
+
+    #[test]
+    fn test_axioms_join_the_check() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut checker = CodeCoherenceChecker::new(&ctx);
+
+        let rank = r#"
+def rank(items):
+    """Returns the items sorted in descending order."""
+    return sorted(items)
+"#;
+        assert!(checker.verify_function(rank).unwrap().is_coherent);
+
+        // sorted() returns ascending order, which only the axiom says
+        let atom = |name: &str, arg: &str| StatementBody::Atom(Predicate {
+            name: name.to_string(),
+            args: vec![arg.to_string()],
+            negated: false,
+        });
+        let ascending = Statement::builder("sorted_is_ascending")
+            .text("sorted() never returns descending order")
+            .body(StatementBody::Implies(
+                Box::new(atom("returns_sorted_result", "implementation")),
+                Box::new(StatementBody::Not(Box::new(atom("result_descending_order", "output")))),
+            ))
+            .build();
+        checker.set_axioms(vec![ascending.clone()]).unwrap();
+        let result = checker.verify_function(rank).unwrap();
+        assert!(!result.is_coherent);
+        assert!(result.violations[0].formal_contradiction.contains("axiom_sorted_is_ascending"));
+
+        // Contradictory axioms are rejected, and the earlier ones stay in force
+        let mut negated = ascending.clone();
+        negated.id = "not_ascending".to_string();
+        negated.body = negated.body.map(|body| StatementBody::Not(Box::new(body)));
+        let error = checker.set_axioms(vec![ascending, negated]).unwrap_err();
+        assert!(error.to_string().starts_with("Axioms are contradictory"), "{}", error);
+        assert!(!checker.verify_function(rank).unwrap().is_coherent);
+    }
+}
@@ -1,405 +0,0 @@
-/*!
-Code Coherence Checker - Extends formal verification to code analysis
-
-This module provides mathematical guarantees of code logical consistency by:
-1. Parsing Python AST into logical predicates
-2. Extracting formal contracts from docstrings and type hints  
-3. Verifying implementation matches contracts using Z3 theorem prover
-4. Detecting logical impossibilities and contradictions in code
-
-Core principle: Code is logically consistent if and only if it can be formally verified.
-*/
-
-use coherence_verifier::{CoherenceVerifier, Statement, Predicate, VerificationResult};
-// Simplified approach - focus on core logic verification
-use serde::{Deserialize, Serialize};
-use z3::Context;
-use anyhow::{Result, anyhow};
-
-/// Main code coherence checking engine
-pub struct CodeCoherenceChecker<'ctx> {
-    verifier: CoherenceVerifier<'ctx>,
-    contract_extractor: ContractExtractor,
-    predicate_translator: PredicateTranslator,
-    context: &'ctx Context,
-}
-
-/// Represents a function contract extracted from docstring and type hints
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FunctionContract {
-    pub name: String,
-    pub preconditions: Vec<String>,
-    pub postconditions: Vec<String>,
-    pub input_types: Vec<String>,
-    pub output_type: Option<String>,
-    pub docstring: Option<String>,
-}
-
-/// Represents logical predicates extracted from code implementation
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ImplementationLogic {
-    pub function_name: String,
-    pub logical_assertions: Vec<String>,
-    pub state_changes: Vec<String>,
-    pub return_conditions: Vec<String>,
-}
-
-/// Extracts formal contracts from Python function signatures and docstrings
-pub struct ContractExtractor;
-
-/// Translates code semantics into logical predicates for Z3 verification
-pub struct PredicateTranslator;
-
-/// Result of code coherence verification
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CodeVerificationResult {
-    pub is_coherent: bool,
-    pub confidence: f64,
-    pub violations: Vec<CoherenceViolation>,
-    pub formal_proof: Option<String>,
-}
-
-/// Specific coherence violation detected in code
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CoherenceViolation {
-    pub violation_type: ViolationType,
-    pub description: String,
-    pub location: String,
-    pub formal_contradiction: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum ViolationType {
-    ContractImplementationMismatch,
-    LogicalImpossibility,
-    TypeIncoherence,
-    StateContradiction,
-}
-
-impl<'ctx> CodeCoherenceChecker<'ctx> {
-    pub fn new(context: &'ctx Context) -> Self {
-        Self {
-            verifier: CoherenceVerifier::new(context),
-            contract_extractor: ContractExtractor,
-            predicate_translator: PredicateTranslator,
-            context,
-        }
-    }
-
-    /// Verify coherence of a Python function
-    pub fn verify_function(&mut self, python_code: &str) -> Result<CodeVerificationResult> {
-        // Simplified approach: Extract contracts from comments and basic pattern matching
-        let contract = self.contract_extractor.extract_contract_from_text(python_code)?;
-        let implementation = self.analyze_implementation_from_text(python_code)?;
-        
-        // Translate to logical predicates
-        let predicates = self.predicate_translator.translate_to_predicates(&contract, &implementation)?;
-        
-        // Verify with Z3
-        let verification_result = self.verifier.verify_statements(&predicates)?;
-        
-        // Convert to code verification result
-        self.convert_to_code_result(verification_result, &contract, &implementation)
-    }
-
-    /// Verify coherence of entire Python module
-    pub fn verify_module(&mut self, python_code: &str) -> Result<Vec<CodeVerificationResult>> {
-        // For now, treat the entire module as one function
-        let result = self.verify_function(python_code)?;
-        Ok(vec![result])
-    }
-
-    fn analyze_implementation_from_text(&self, code: &str) -> Result<ImplementationLogic> {
-        // Extract function name from code
-        let function_name = if let Some(def_line) = code.lines().find(|line| line.trim().starts_with("def ")) {
-            def_line.split_whitespace()
-                .nth(1)
-                .and_then(|name| name.split('(').next())
-                .unwrap_or("unknown_function")
-                .to_string()
-        } else {
-            "unknown_function".to_string()
-        };
-
-        let mut logic = ImplementationLogic {
-            function_name,
-            logical_assertions: Vec::new(),
-            state_changes: Vec::new(),
-            return_conditions: Vec::new(),
-        };
-
-        // Simple pattern matching for common constructs
-        for line in code.lines() {
-            let line = line.trim();
-            
-            if line.starts_with("return ") {
-                if line.contains("sorted(") {
-                    logic.return_conditions.push("returns_sorted_result".to_string());
-                } else if line.contains("[::-1]") {
-                    logic.return_conditions.push("returns_reversed_result".to_string());
-                } else {
-                    logic.return_conditions.push("returns_value".to_string());
-                }
-            }
-            
-            if line.starts_with("assert ") {
-                logic.logical_assertions.push("has_assertion".to_string());
-            }
-        }
-
-        Ok(logic)
-    }
-
-    fn analyze_statement(&self, stmt: &ast::Stmt, logic: &mut ImplementationLogic) -> Result<()> {
-        match stmt {
-            ast::Stmt::Return { value: Some(expr), .. } => {
-                // Analyze return expression for logical conditions
-                let condition = self.analyze_return_expression(expr)?;
-                logic.return_conditions.push(condition);
-            }
-            ast::Stmt::Assert { test, .. } => {
-                // Extract assertion as logical predicate
-                let assertion = self.analyze_assertion(test)?;
-                logic.logical_assertions.push(assertion);
-            }
-            ast::Stmt::Assign { targets, value, .. } => {
-                // Track state changes
-                let state_change = self.analyze_assignment(targets, value)?;
-                logic.state_changes.push(state_change);
-            }
-            _ => {
-                // Handle other statement types as needed
-            }
-        }
-        Ok(())
-    }
-
-    fn analyze_return_expression(&self, expr: &ast::Expr) -> Result<String> {
-        // Simplified analysis - extract logical meaning of return expression
-        match expr {
-            ast::Expr::Call { func, .. } => {
-                if let ast::Expr::Name { id, .. } = func.as_ref() {
-                    Ok(format!("returns_result_of_{}", id))
-                } else {
-                    Ok("returns_complex_expression".to_string())
-                }
-            }
-            ast::Expr::Name { id, .. } => {
-                Ok(format!("returns_variable_{}", id))
-            }
-            _ => Ok("returns_expression".to_string()),
-        }
-    }
-
-    fn analyze_assertion(&self, expr: &ast::Expr) -> Result<String> {
-        // Extract logical meaning from assertion
-        match expr {
-            ast::Expr::Compare { left, ops, comparators } => {
-                // Handle comparisons like x >= 0, len(list) > 0, etc.
-                Ok("comparison_assertion".to_string())
-            }
-            _ => Ok("general_assertion".to_string()),
-        }
-    }
-
-    fn analyze_assignment(&self, targets: &[ast::Expr], value: &ast::Expr) -> Result<String> {
-        // Track variable assignments and state changes
-        Ok("variable_assignment".to_string())
-    }
-
-    fn extract_function_code(&self, full_code: &str, function_name: &str) -> Result<String> {
-        // Extract individual function from module code
-        // This is a simplified implementation
-        Ok(full_code.to_string())
-    }
-
-    fn convert_to_code_result(
-        &self,
-        verification_result: VerificationResult,
-        contract: &FunctionContract,
-        implementation: &ImplementationLogic,
-    ) -> Result<CodeVerificationResult> {
-        let violations = if !verification_result.is_consistent {
-            vec![CoherenceViolation {
-                violation_type: ViolationType::ContractImplementationMismatch,
-                description: "Implementation does not satisfy contract".to_string(),
-                location: contract.name.clone(),
-                formal_contradiction: format!("{:?}", verification_result.contradictions),
-            }]
-        } else {
-            Vec::new()
-        };
-
-        Ok(CodeVerificationResult {
-            is_coherent: verification_result.is_consistent,
-            confidence: verification_result.confidence,
-            violations,
-            formal_proof: Some(format!("Z3 verification: {}", verification_result.is_consistent)),
-        })
-    }
-}
-
-impl ContractExtractor {
-    pub fn extract_contract(&self, function: &ast::Stmt) -> Result<FunctionContract> {
-        if let ast::Stmt::FunctionDef { name, args, returns, body, .. } = function {
-            let mut contract = FunctionContract {
-                name: name.clone(),
-                preconditions: Vec::new(),
-                postconditions: Vec::new(),
-                input_types: Vec::new(),
-                output_type: None,
-                docstring: None,
-            };
-
-            // Extract type hints
-            for arg in &args.args {
-                if let Some(annotation) = &arg.node.annotation {
-                    contract.input_types.push(self.extract_type_annotation(annotation)?);
-                }
-            }
-
-            if let Some(return_annotation) = returns {
-                contract.output_type = Some(self.extract_type_annotation(return_annotation)?);
-            }
-
-            // Extract docstring from first statement if it's a string
-            if let Some(first_stmt) = body.first() {
-                if let ast::Stmt::Expr { value } = first_stmt {
-                    if let ast::Expr::Constant { value: ast::Constant::Str(docstring), .. } = value.as_ref() {
-                        contract.docstring = Some(docstring.clone());
-                        self.parse_docstring_contracts(&mut contract, docstring)?;
-                    }
-                }
-            }
-
-            Ok(contract)
-        } else {
-            Err(anyhow!("Not a function definition"))
-        }
-    }
-
-    // Simplified contract extraction from text patterns
-
-    fn parse_docstring_contracts(&self, contract: &mut FunctionContract, docstring: &str) -> Result<()> {
-        // Parse docstring for formal contracts
-        // Look for patterns like "Returns:", "Args:", "Raises:", etc.
-        
-        if docstring.to_lowercase().contains("sorted") {
-            contract.postconditions.push("result_is_sorted".to_string());
-        }
-        
-        if docstring.to_lowercase().contains("ascending") {
-            contract.postconditions.push("result_ascending_order".to_string());
-        }
-        
-        if docstring.to_lowercase().contains("non-negative") || docstring.to_lowercase().contains("positive") {
-            contract.preconditions.push("input_non_negative".to_string());
-        }
-
-        Ok(())
-    }
-}
-
-impl PredicateTranslator {
-    pub fn translate_to_predicates(
-        &self,
-        contract: &FunctionContract,
-        implementation: &ImplementationLogic,
-    ) -> Result<Vec<Statement>> {
-        let mut statements = Vec::new();
-        let mut statement_id = 0;
-
-        // Translate contract preconditions
-        for precondition in &contract.preconditions {
-            statements.push(Statement {
-                id: format!("precond_{}", statement_id),
-                text: format!("Precondition: {}", precondition),
-                predicates: vec![Predicate {
-                    name: precondition.clone(),
-                    args: vec!["input".to_string()],
-                    negated: false,
-                }],
-            });
-            statement_id += 1;
-        }
-
-        // Translate contract postconditions
-        for postcondition in &contract.postconditions {
-            statements.push(Statement {
-                id: format!("postcond_{}", statement_id),
-                text: format!("Postcondition: {}", postcondition),
-                predicates: vec![Predicate {
-                    name: postcondition.clone(),
-                    args: vec!["output".to_string()],
-                    negated: false,
-                }],
-            });
-            statement_id += 1;
-        }
-
-        // Translate implementation logic
-        for assertion in &implementation.logical_assertions {
-            statements.push(Statement {
-                id: format!("impl_assert_{}", statement_id),
-                text: format!("Implementation assertion: {}", assertion),
-                predicates: vec![Predicate {
-                    name: assertion.clone(),
-                    args: vec!["state".to_string()],
-                    negated: false,
-                }],
-            });
-            statement_id += 1;
-        }
-
-        // Add consistency requirements
-        if contract.postconditions.contains(&"result_is_sorted".to_string()) {
-            // If contract says result is sorted, implementation must guarantee this
-            statements.push(Statement {
-                id: format!("consistency_{}", statement_id),
-                text: "Implementation must produce sorted result".to_string(),
-                predicates: vec![Predicate {
-                    name: "implementation_produces_sorted_result".to_string(),
-                    args: vec!["implementation".to_string()],
-                    negated: false,
-                }],
-            });
-        }
-
-        Ok(statements)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_simple_function_verification() {
-        let mut checker = CodeCoherenceChecker::new().unwrap();
-        
-        let python_code = r#"
-def sort_list(items):
-    """Returns a sorted list in ascending order."""
-    return sorted(items)
-"#;
-
-        let result = checker.verify_function(python_code).unwrap();
-        assert!(result.is_coherent);
-    }
-
-    #[test]
-    fn test_contradictory_function() {
-        let mut checker = CodeCoherenceChecker::new().unwrap();
-        
-        let python_code = r#"
-def sort_list(items):
-    """Returns a sorted list in ascending order."""
-    return items.reverse()  # This contradicts the contract
-"#;
-
-        let result = checker.verify_function(python_code).unwrap();
-        // Should detect contradiction between contract and implementation
-        assert!(!result.is_coherent);
-        assert!(!result.violations.is_empty());
-    }
-}
\ No newline at end of file
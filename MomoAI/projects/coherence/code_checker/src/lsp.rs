@@ -0,0 +1,232 @@
+/*!
+Language server
+
+The subset of the Language Server Protocol an editor needs to show coherence
+violations as diagnostics: `initialize`, `shutdown`/`exit` and full-text sync of
+`textDocument/didOpen`, `didChange` and `didClose`. Each open document is verified
+as a module and its violations published with `textDocument/publishDiagnostics`.
+
+Edits arrive on every keystroke, so a changed document is only re-verified once no
+change has come in for the debounce interval. While a document does not parse, its
+last diagnostics are left in place. Messages that cannot be decoded are answered with
+a parse error and do not end the session.
+*/
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context as _, Result};
+use serde_json::{json, Value};
+
+use crate::{CodeCoherenceChecker, Severity};
+
+/// JSON-RPC error code for requests the server does not implement
+const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for a message that could not be framed or decoded
+const PARSE_ERROR: i64 = -32700;
+
+/// Serve LSP messages from `input` until `exit` or end of input, writing responses
+/// and diagnostics to `output`
+pub fn serve(
+    checker: &mut CodeCoherenceChecker,
+    input: impl Read + Send + 'static,
+    output: impl Write,
+    debounce: Duration,
+) -> Result<()> {
+    // Z3 contexts stay on this thread; a reader thread only decodes messages, passing
+    // on the ones it cannot decode so they are answered without ending the session
+    let (sender, messages) = mpsc::channel();
+    thread::spawn(move || {
+        let mut input = BufReader::new(input);
+        while let Ok(Some(message)) = read_message(&mut input) {
+            if sender.send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut server = Server {
+        checker,
+        output,
+        documents: HashMap::new(),
+        pending: HashMap::new(),
+        debounce,
+    };
+    loop {
+        let message = match server.pending.values().min() {
+            Some(&due) => messages.recv_timeout(due.saturating_duration_since(Instant::now())),
+            None => messages.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match message {
+            Ok(Ok(message)) => {
+                if !server.handle(&message)? {
+                    return Ok(());
+                }
+            }
+            Ok(Err(error)) => {
+                let error = json!({ "code": PARSE_ERROR, "message": format!("{:#}", error) });
+                write_message(&mut server.output, &json!({ "jsonrpc": "2.0", "id": null, "error": error }))?;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        server.publish_due()?;
+    }
+}
+
+struct Server<'a, 'ctx, W> {
+    checker: &'a mut CodeCoherenceChecker<'ctx>,
+    output: W,
+    /// Text of each open document by URI
+    documents: HashMap<String, String>,
+    /// When each changed document is due for re-verification
+    pending: HashMap<String, Instant>,
+    debounce: Duration,
+}
+
+impl<W: Write> Server<'_, '_, W> {
+    /// Handle one message; `false` once the client asked the server to exit
+    fn handle(&mut self, message: &Value) -> Result<bool> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+
+        match method {
+            "initialize" => self.respond(message, json!({
+                "capabilities": { "textDocumentSync": 1 },
+                "serverInfo": { "name": "code_checker", "version": env!("CARGO_PKG_VERSION") },
+            }))?,
+            "shutdown" => self.respond(message, Value::Null)?,
+            "exit" => return Ok(false),
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default().to_string();
+                self.documents.insert(uri.clone(), text);
+                self.publish(&uri)?;
+            }
+            "textDocument/didChange" => {
+                // Full sync: the last change carries the whole document
+                if let Some(text) = params["contentChanges"].as_array().and_then(|changes| changes.last()) {
+                    let text = text["text"].as_str().unwrap_or_default().to_string();
+                    self.documents.insert(uri.clone(), text);
+                    self.pending.insert(uri, Instant::now() + self.debounce);
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                self.pending.remove(&uri);
+                self.notify_diagnostics(&uri, Vec::new())?;
+            }
+            _ if message.get("id").is_some() => {
+                let error = json!({ "code": METHOD_NOT_FOUND, "message": format!("Unsupported method '{}'", method) });
+                write_message(&mut self.output, &json!({ "jsonrpc": "2.0", "id": message["id"], "error": error }))?;
+            }
+            // Other notifications need no answer
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Re-verify the changed documents whose debounce interval has passed
+    fn publish_due(&mut self) -> Result<()> {
+        let now = Instant::now();
+        let due: Vec<String> = self.pending
+            .iter()
+            .filter(|(_, &due)| due <= now)
+            .map(|(uri, _)| uri.clone())
+            .collect();
+        for uri in due {
+            self.pending.remove(&uri);
+            self.publish(&uri)?;
+        }
+        Ok(())
+    }
+
+    /// Verify a document and publish its diagnostics, unless it does not parse
+    fn publish(&mut self, uri: &str) -> Result<()> {
+        let Some(text) = self.documents.get(uri) else {
+            return Ok(());
+        };
+        let Ok(results) = self.checker.verify_module(text) else {
+            return Ok(());
+        };
+
+        let lines: Vec<&str> = text.lines().collect();
+        let diagnostics = results
+            .iter()
+            .flat_map(|result| &result.violations)
+            .map(|violation| {
+                // LSP positions are 0-based UTF-16 offsets; underline the rest of the line
+                let line = violation.line.saturating_sub(1);
+                let text = lines.get(line).copied().unwrap_or_default();
+                let start: usize = text.chars().take(violation.column.saturating_sub(1)).map(char::len_utf16).sum();
+                let end = text.encode_utf16().count().max(start);
+                json!({
+                    "range": {
+                        "start": { "line": line, "character": start },
+                        "end": { "line": line, "character": end },
+                    },
+                    "severity": match violation.severity {
+                        Severity::Error => 1,
+                        Severity::Warning => 2,
+                    },
+                    "code": violation.violation_type.rule_id(),
+                    "source": "code_checker",
+                    "message": format!("{}: {}", violation.location, violation.description),
+                })
+            })
+            .collect();
+        self.notify_diagnostics(uri, diagnostics)
+    }
+
+    fn notify_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> Result<()> {
+        write_message(&mut self.output, &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }))
+    }
+
+    fn respond(&mut self, request: &Value, result: Value) -> Result<()> {
+        write_message(&mut self.output, &json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+}
+
+/// Read one `Content-Length`-framed message; `None` at end of input. A message whose
+/// header or body cannot be decoded is an inner error, after which reading goes on
+/// with the next header.
+fn read_message(input: &mut impl BufRead) -> std::io::Result<Option<Result<Value>>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = Some(value.trim().parse::<usize>().context("Invalid Content-Length"));
+            }
+        }
+    }
+
+    let length = match length.context("Message without Content-Length") {
+        Ok(Ok(length)) => length,
+        Ok(Err(error)) | Err(error) => return Ok(Some(Err(error))),
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body).context("Invalid JSON in message body")))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
@@ -3,47 +3,121 @@ Code Coherence Checker CLI - Mathematical verification of code logical consisten
 
 Usage:
   code_checker verify-function --code "def func(): ..." 
-  code_checker verify-file --path "script.py"
+  code_checker verify-function --code - < function.py
+  code_checker verify-file --path "script.py" --axioms axioms.json
+  code_checker verify-dir --path src/
+  code_checker check changed.py other.py
   code_checker interactive
+  code_checker lsp
+  code_checker watch --path "script.py"
   code_checker test
 
 Provides 100% mathematical certainty of code coherence through formal verification.
 */
 
-use code_coherence_checker::{CodeCoherenceChecker, CodeVerificationResult};
+use code_coherence_checker::{lsp, watch, CodeCoherenceChecker};
+use coherence_verifier::repl::LineReader;
+use coherence_verifier::reporter::{exit_code, ColorChoice, Format, HumanReporter, ReportEntry, Reporter, Style};
+use coherence_verifier::{Statement, VerificationStatus};
 use z3::Config;
-use clap::{Parser, Subcommand};
-use z3;
+use clap::{CommandFactory, Parser, Subcommand};
 use std::fs;
-use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::io::{self, Read, Write};
+use std::time::Duration;
 use anyhow::Result;
 
 #[derive(Parser)]
 #[command(name = "code_checker")]
 #[command(about = "Mathematical verification of code logical consistency")]
+#[command(version = version())]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Always exit with code 0 once the report is written, even if code is incoherent
+    #[arg(long, global = true)]
+    no_fail: bool,
+    /// Print only a final summary line such as `3/5 coherent, 2 violations`, or with
+    /// `--format json` only a summary object
+    #[arg(long, global = true)]
+    quiet: bool,
+    /// Output format: `human`/`text` (default), `json`, `sarif`, `junit`, `markdown` or `quiet`
+    #[arg(long, global = true, default_value = "human")]
+    format: Format,
+    /// Emoji in human output: `auto` (default; off when piped or `NO_COLOR` is set),
+    /// `always` or `never`
+    #[arg(long, global = true, default_value = "auto")]
+    color: ColorChoice,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Verify coherence of a Python function from command line
     VerifyFunction {
-        /// Python function code to verify
+        /// Python function code to verify, or `-` to read it from stdin
         #[arg(short, long)]
         code: String,
+        /// JSON file with an array of statements asserted alongside the function's
+        #[arg(long)]
+        axioms: Option<String>,
     },
     /// Verify coherence of a Python file
     VerifyFile {
         /// Path to Python file
         #[arg(short, long)]
         path: String,
+        /// JSON file with an array of statements asserted alongside each function's
+        #[arg(long)]
+        axioms: Option<String>,
+    },
+    /// Verify every Python file under a directory
+    VerifyDir {
+        /// Directory to walk
+        #[arg(short, long)]
+        path: String,
+        /// Files to verify, relative to the directory
+        #[arg(long, default_value = "**/*.py")]
+        glob: String,
+        /// Stop at the first incoherent file
+        #[arg(long)]
+        fail_fast: bool,
+    },
+    /// Verify the Python files among the given paths, as a pre-commit hook; other files
+    /// are ignored
+    Check {
+        /// Changed files, as passed by the hook
+        files: Vec<String>,
     },
     /// Interactive coherence checking session
     Interactive,
+    /// Language server on stdin/stdout publishing violations as diagnostics
+    Lsp {
+        /// Quiet period after an edit before the document is re-verified
+        #[arg(long, default_value = "300")]
+        debounce_ms: u64,
+    },
+    /// Re-verify a Python file whenever it changes
+    Watch {
+        /// Path to Python file
+        #[arg(short, long)]
+        path: String,
+        /// Quiet period after a change before the file is re-verified
+        #[arg(long, default_value = "200")]
+        debounce_ms: u64,
+    },
     /// Run built-in test suite
     Test,
+    /// Print a shell completion script for bash, zsh, fish, elvish or powershell
+    #[command(hide = true)]
+    Completions {
+        shell: clap_complete::Shell,
+    },
+}
+
+/// Crate version followed by the version of the linked Z3 library, for `--version`
+fn version() -> &'static str {
+    static VERSION: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    VERSION.get_or_init(|| format!("{} (Z3 {})", env!("CARGO_PKG_VERSION"), coherence_verifier::z3_version()))
 }
 
 fn main() -> Result<()> {
@@ -52,88 +126,254 @@ fn main() -> Result<()> {
     let ctx = z3::Context::new(&cfg);
     let mut checker = CodeCoherenceChecker::new(&ctx);
 
-    match cli.command {
-        Commands::VerifyFunction { code } => {
-            verify_function_command(&mut checker, &code)?;
+    let style = cli.color.style();
+    let mut reporter = if cli.quiet {
+        cli.format.summary_reporter("coherent", "violations")
+    } else {
+        cli.format.reporter("code_checker", env!("CARGO_PKG_VERSION"), style)
+    };
+
+    let statuses = match cli.command {
+        Commands::VerifyFunction { code, axioms } => {
+            let code = if code == "-" { read_stdin()? } else { code };
+            load_axioms(&mut checker, axioms.as_deref())?;
+            vec![verify_function_command(&mut checker, reporter.as_mut(), &code)?]
+        }
+        Commands::VerifyFile { path, axioms } => {
+            load_axioms(&mut checker, axioms.as_deref())?;
+            verify_file_command(&mut checker, reporter.as_mut(), &path)?
+        }
+        Commands::VerifyDir { path, glob, fail_fast } => {
+            verify_dir_command(&mut checker, reporter.as_mut(), &path, &glob, fail_fast, style)?
         }
-        Commands::VerifyFile { path } => {
-            verify_file_command(&mut checker, &path)?;
+        Commands::Check { files } => {
+            check_command(&mut checker, reporter.as_mut(), &files, style)?
+        }
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "code_checker", &mut io::stdout());
+            return Ok(());
         }
         Commands::Interactive => {
-            interactive_mode(&mut checker)?;
+            interactive_mode(&mut checker, style)?;
+            return Ok(());
         }
-        Commands::Test => {
-            run_test_suite(&mut checker)?;
+        Commands::Lsp { debounce_ms } => {
+            lsp::serve(&mut checker, io::stdin(), io::stdout(), Duration::from_millis(debounce_ms))?;
+            return Ok(());
         }
-    }
+        Commands::Watch { path, debounce_ms } => {
+            watch::watch(&mut checker, Path::new(&path), Duration::from_millis(debounce_ms), io::stdout(), style)?;
+            return Ok(());
+        }
+        Commands::Test if cli.format == Format::Human && !cli.quiet => {
+            run_test_suite(&mut checker, style)?;
+            return Ok(());
+        }
+        Commands::Test => report_test_suite(&mut checker, reporter.as_mut())?,
+    };
+
+    let mut stdout = io::stdout();
+    reporter.finish(&mut stdout)?;
+    stdout.flush()?;
 
+    // Fail CI builds when any function is incoherent
+    let code = exit_code(statuses);
+    if code != 0 && !cli.no_fail {
+        std::process::exit(code);
+    }
     Ok(())
 }
 
-fn verify_function_command(checker: &mut CodeCoherenceChecker, code: &str) -> Result<()> {
-    println!("🔍 Analyzing function for logical coherence...\n");
+/// Read the whole of stdin as Python source, rejecting empty input
+fn read_stdin() -> Result<String> {
+    let mut code = String::new();
+    io::stdin().read_to_string(&mut code)?;
+    if code.trim().is_empty() {
+        anyhow::bail!("No Python code on stdin");
+    }
+    Ok(code)
+}
+
+/// Assert the statements of the JSON file at `path`, if any, for every function
+fn load_axioms(checker: &mut CodeCoherenceChecker, path: Option<&str>) -> Result<()> {
+    let Some(path) = path else { return Ok(()) };
+    let axioms: Vec<Statement> = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| anyhow::anyhow!("{} is not a JSON array of statements: {}", path, e))?;
+    checker.set_axioms(axioms).map_err(|e| anyhow::anyhow!("{}: {}", path, e))
+}
+
+fn verify_function_command(checker: &mut CodeCoherenceChecker, reporter: &mut dyn Reporter, code: &str) -> Result<VerificationStatus> {
+    let mut stdout = io::stdout();
+    reporter.section(&mut stdout, "🔍 Analyzing function for logical coherence...", &[])?;
     
     let result = checker.verify_function(code)?;
-    display_verification_result(&result);
+    let entry = result.to_report_entry("function");
+    reporter.entry(&mut stdout, &entry)?;
     
-    Ok(())
+    Ok(entry.status)
 }
 
-fn verify_file_command(checker: &mut CodeCoherenceChecker, path: &str) -> Result<()> {
-    println!("🔍 Analyzing file: {}\n", path);
+fn verify_file_command(checker: &mut CodeCoherenceChecker, reporter: &mut dyn Reporter, path: &str) -> Result<Vec<VerificationStatus>> {
+    let mut stdout = io::stdout();
+    reporter.section(&mut stdout, &format!("🔍 Analyzing file: {}", path), &[])?;
     
     let code = fs::read_to_string(path)?;
     let results = checker.verify_module(&code)?;
     
-    for (i, result) in results.iter().enumerate() {
-        println!("Function {}:", i + 1);
-        display_verification_result(result);
-        println!();
+    let mut statuses = Vec::new();
+    for result in &results {
+        reporter.section(&mut stdout, &format!("Function {}:", result.location), &[])?;
+        let entry = result.to_file_report_entry(path);
+        reporter.entry(&mut stdout, &entry)?;
+        statuses.push(entry.status);
     }
     
-    Ok(())
+    Ok(statuses)
 }
 
-fn interactive_mode(checker: &mut CodeCoherenceChecker) -> Result<()> {
-    println!("🚀 Code Coherence Checker - Interactive Mode");
+/// Verify each file matching `pattern` under `dir`, in path order
+fn verify_dir_command(
+    checker: &mut CodeCoherenceChecker,
+    reporter: &mut dyn Reporter,
+    dir: &str,
+    pattern: &str,
+    fail_fast: bool,
+    style: Style,
+) -> Result<Vec<VerificationStatus>> {
+    let full_pattern = Path::new(dir).join(pattern);
+    let mut paths = Vec::new();
+    for entry in glob::glob(&full_pattern.to_string_lossy())? {
+        match entry {
+            Ok(path) if path.is_file() => paths.push(path),
+            Ok(_) => {}
+            Err(e) => eprintln!("{}", style.paint(&format!("⚠️  Skipping {}: {}", e.path().display(), e.error()))),
+        }
+    }
+    paths.sort();
+
+    verify_paths(checker, reporter, &paths, fail_fast, style)
+}
+
+/// Verify the Python files among `files`, for a pre-commit hook passing the changed
+/// files; other files are ignored
+fn check_command(
+    checker: &mut CodeCoherenceChecker,
+    reporter: &mut dyn Reporter,
+    files: &[String],
+    style: Style,
+) -> Result<Vec<VerificationStatus>> {
+    let paths: Vec<PathBuf> = files
+        .iter()
+        .map(PathBuf::from)
+        .filter(|path| path.extension().is_some_and(|extension| extension == "py" || extension == "pyi"))
+        .collect();
+    verify_paths(checker, reporter, &paths, false, style)
+}
+
+/// Verify each file in order, grouping the results by file, then summarize. Files
+/// that cannot be read as UTF-8 text or parsed are reported on stderr and skipped.
+fn verify_paths(
+    checker: &mut CodeCoherenceChecker,
+    reporter: &mut dyn Reporter,
+    paths: &[PathBuf],
+    fail_fast: bool,
+    style: Style,
+) -> Result<Vec<VerificationStatus>> {
+    let mut stdout = io::stdout();
+    let mut statuses = Vec::new();
+    let (mut coherent, mut incoherent, mut skipped) = (0, 0, 0);
+    for path in paths {
+        let name = path.display().to_string();
+        let code = match fs::read_to_string(path) {
+            Ok(code) => code,
+            Err(e) => {
+                eprintln!("{}", style.paint(&format!("⚠️  Skipping {}: {:#}", name, e)));
+                skipped += 1;
+                continue;
+            }
+        };
+
+        let results = match checker.verify_module(&code) {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("{}", style.paint(&format!("⚠️  Skipping {}: {:#}", name, e)));
+                skipped += 1;
+                continue;
+            }
+        };
+
+        reporter.section(&mut stdout, &format!("📄 {}", name), &[])?;
+        let mut file_statuses = Vec::new();
+        for result in &results {
+            let entry = result.to_file_report_entry(&name);
+            reporter.entry(&mut stdout, &entry)?;
+            file_statuses.push(entry.status);
+        }
+
+        let failed = exit_code(file_statuses.iter().copied()) != 0;
+        statuses.extend(file_statuses);
+        if failed {
+            incoherent += 1;
+            if fail_fast {
+                break;
+            }
+        } else {
+            coherent += 1;
+        }
+    }
+
+    let summary = vec![
+        format!("Files: {}", paths.len()),
+        format!("✅ Coherent: {}", coherent),
+        format!("❌ Incoherent: {}", incoherent),
+        format!("⚠️  Skipped: {}", skipped),
+    ];
+    reporter.section(&mut stdout, "📊 Summary", &summary)?;
+
+    Ok(statuses)
+}
+
+fn interactive_mode(checker: &mut CodeCoherenceChecker, style: Style) -> Result<()> {
+    println!("{}", style.paint("🚀 Code Coherence Checker - Interactive Mode"));
     println!("Enter Python functions to verify logical coherence.");
     println!("Type 'exit' to quit, 'help' for commands.\n");
 
+    let mut lines = LineReader::open(".code_checker_history")?;
     loop {
-        print!("coherence> ");
-        io::stdout().flush()?;
-        
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        // Ctrl-D ends the session like `exit`
+        let Some(input) = lines.read_line("coherence> ")? else {
+            println!("{}", style.paint("👋 Goodbye!"));
+            break;
+        };
         let input = input.trim();
         
         match input {
             "exit" => {
-                println!("👋 Goodbye!");
+                println!("{}", style.paint("👋 Goodbye!"));
                 break;
             }
             "help" => {
-                print_help();
+                print_help(style);
             }
             "test" => {
-                run_test_suite(checker)?;
+                run_test_suite(checker, style)?;
             }
             "" => continue,
             _ => {
                 if input.starts_with("def ") {
                     // Single line function
                     match checker.verify_function(input) {
-                        Ok(result) => display_verification_result(&result),
-                        Err(e) => println!("❌ Error: {}", e),
+                        Ok(result) => HumanReporter::new(style).entry(&mut io::stdout(), &result.to_report_entry("function"))?,
+                        Err(e) => println!("{}", style.paint(&format!("❌ Error: {:#}", e))),
                     }
                 } else {
                     // Multi-line input mode
-                    println!("📝 Multi-line mode. Enter your function (end with empty line):");
-                    let code = read_multiline_input()?;
+                    println!("{}", style.paint("📝 Multi-line mode. Enter your function (end with empty line):"));
+                    let code = read_multiline_input(&mut lines)?;
                     
                     match checker.verify_function(&code) {
-                        Ok(result) => display_verification_result(&result),
-                        Err(e) => println!("❌ Error: {}", e),
+                        Ok(result) => HumanReporter::new(style).entry(&mut io::stdout(), &result.to_report_entry("function"))?,
+                        Err(e) => println!("{}", style.paint(&format!("❌ Error: {:#}", e))),
                     }
                 }
             }
@@ -144,47 +384,24 @@ fn interactive_mode(checker: &mut CodeCoherenceChecker) -> Result<()> {
     Ok(())
 }
 
-fn read_multiline_input() -> Result<String> {
+fn read_multiline_input(reader: &mut LineReader) -> Result<String> {
     let mut lines = Vec::new();
     
     loop {
-        let mut line = String::new();
-        io::stdin().read_line(&mut line)?;
+        let line = reader.read_line("... ")?.unwrap_or_default();
         
         if line.trim().is_empty() {
             break;
         }
         
-        lines.push(line);
+        lines.push(line + "\n");
     }
     
     Ok(lines.join(""))
 }
 
-fn display_verification_result(result: &CodeVerificationResult) {
-    if result.is_coherent {
-        println!("✅ COHERENT: Function is logically consistent");
-        println!("   Confidence: {:.1}%", result.confidence);
-        if let Some(proof) = &result.formal_proof {
-            println!("   Formal proof: {}", proof);
-        }
-    } else {
-        println!("❌ INCOHERENT: Logical contradictions detected");
-        println!("   Confidence: {:.1}%", result.confidence);
-        
-        if !result.violations.is_empty() {
-            println!("🚨 Violations:");
-            for violation in &result.violations {
-                println!("   • {}: {}", violation.violation_type_str(), violation.description);
-                println!("     Location: {}", violation.location);
-                println!("     Formal contradiction: {}", violation.formal_contradiction);
-            }
-        }
-    }
-}
-
-fn print_help() {
-    println!("📚 Available commands:");
+fn print_help(style: Style) {
+    println!("{}", style.paint("📚 Available commands:"));
     println!("  def function_name(): ...  - Verify a single-line function");
     println!("  <multiline>              - Enter multiline function (end with empty line)");
     println!("  test                     - Run built-in test suite");
@@ -192,10 +409,9 @@ fn print_help() {
     println!("  exit                     - Quit interactive mode");
 }
 
-fn run_test_suite(checker: &mut CodeCoherenceChecker) -> Result<()> {
-    println!("🧪 Running Code Coherence Test Suite\n");
-    
-    let tests = vec![
+/// The built-in test suite: small functions with the verdict each should get
+fn built_in_tests() -> Vec<TestCase> {
+    vec![
         TestCase {
             name: "Simple coherent function",
             code: r#"
@@ -243,8 +459,13 @@ def sort_in_constant_time(items):
 "#,
             expected_coherent: false,
         },
-    ];
-    
+    ]
+}
+
+fn run_test_suite(checker: &mut CodeCoherenceChecker, style: Style) -> Result<()> {
+    println!("{}", style.paint("🧪 Running Code Coherence Test Suite\n"));
+
+    let tests = built_in_tests();
     let mut passed = 0;
     let mut failed = 0;
     
@@ -255,53 +476,62 @@ def sort_in_constant_time(items):
         match checker.verify_function(test.code) {
             Ok(result) => {
                 if result.is_coherent == test.expected_coherent {
-                    println!("✅ PASS");
+                    println!("{}", style.paint("✅ PASS"));
                     passed += 1;
                 } else {
-                    println!("❌ FAIL");
+                    println!("{}", style.paint("❌ FAIL"));
                     println!("   Expected: {}, Got: {}", test.expected_coherent, result.is_coherent);
                     failed += 1;
                 }
             }
             Err(e) => {
-                println!("❌ ERROR: {}", e);
+                println!("{}", style.paint(&format!("❌ ERROR: {:#}", e)));
                 failed += 1;
             }
         }
     }
     
-    println!("\n📊 Test Results:");
+    println!("{}", style.paint("\n📊 Test Results:"));
     println!("   Passed: {}", passed);
     println!("   Failed: {}", failed);
     println!("   Total:  {}", passed + failed);
     
     if failed == 0 {
-        println!("🎉 All tests passed! Code coherence checker is working correctly.");
+        println!("{}", style.paint("🎉 All tests passed! Code coherence checker is working correctly."));
     } else {
-        println!("⚠️  Some tests failed. Check implementation for issues.");
+        println!("{}", style.paint("⚠️  Some tests failed. Check implementation for issues."));
     }
     
     Ok(())
 }
 
+/// Report each built-in test case as an entry that passes when the function got its
+/// expected verdict, for formats such as JUnit that CI systems read
+fn report_test_suite(checker: &mut CodeCoherenceChecker, reporter: &mut dyn Reporter) -> Result<Vec<VerificationStatus>> {
+    let mut stdout = io::stdout();
+    let mut statuses = Vec::new();
+    for test in built_in_tests() {
+        let expected = coherence_status(test.expected_coherent);
+        let entry = match checker.verify_function(test.code) {
+            Ok(result) => ReportEntry::test_case(test.name, expected, coherence_status(result.is_coherent)),
+            Err(e) => {
+                let mut entry = ReportEntry::test_case(test.name, expected, VerificationStatus::Unknown);
+                entry.summary = format!("Verification failed: {:#}", e);
+                entry
+            }
+        };
+        reporter.entry(&mut stdout, &entry)?;
+        statuses.push(entry.status);
+    }
+    Ok(statuses)
+}
+
+fn coherence_status(coherent: bool) -> VerificationStatus {
+    if coherent { VerificationStatus::Consistent } else { VerificationStatus::Inconsistent }
+}
+
 struct TestCase {
     name: &'static str,
     code: &'static str,
     expected_coherent: bool,
 }
-
-// Extension trait for better display
-trait ViolationTypeDisplay {
-    fn violation_type_str(&self) -> &'static str;
-}
-
-impl ViolationTypeDisplay for code_coherence_checker::CoherenceViolation {
-    fn violation_type_str(&self) -> &'static str {
-        match self.violation_type {
-            code_coherence_checker::ViolationType::ContractImplementationMismatch => "Contract Mismatch",
-            code_coherence_checker::ViolationType::LogicalImpossibility => "Logical Impossibility",
-            code_coherence_checker::ViolationType::TypeIncoherence => "Type Incoherence",
-            code_coherence_checker::ViolationType::StateContradiction => "State Contradiction",
-        }
-    }
-}
\ No newline at end of file
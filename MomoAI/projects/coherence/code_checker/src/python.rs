@@ -0,0 +1,111 @@
+/*!
+Python bindings
+
+Built with the `python` feature into an extension module named `coherence`:
+
+```python
+import coherence
+
+coherence.verify_statements([{"id": "s1", "text": "...", "predicates": [...]}])
+coherence.verify_function("def f(x):\n    ...")
+
+verifier = coherence.CoherenceVerifier()
+verifier.push_statements(base)
+verifier.check_pushed()
+```
+
+Statements go in and results come out as the dicts of their JSON form, the same
+shapes the CLIs read and print.
+*/
+
+use coherence_verifier::{CoherenceVerifier, Statement};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use z3::{Config, Context};
+
+use crate::CodeCoherenceChecker;
+
+/// Convert Python objects to a Rust value through their JSON form
+fn from_python<T: DeserializeOwned>(py: Python<'_>, value: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = py.import("json")?.call_method1("dumps", (value,))?.extract()?;
+    serde_json::from_str(&json).map_err(|error| PyValueError::new_err(error.to_string()))
+}
+
+/// Convert a Rust value to Python dicts and lists through its JSON form
+fn to_python(py: Python<'_>, value: &impl Serialize) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(|error| PyRuntimeError::new_err(error.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn runtime_error(error: anyhow::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("{:#}", error))
+}
+
+/// Check a list of statement dicts for consistency on a fresh Z3 context
+#[pyfunction]
+fn verify_statements(py: Python<'_>, statements: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let statements: Vec<Statement> = from_python(py, statements)?;
+    let context = Context::new(&Config::new());
+    let result = CoherenceVerifier::new(&context).verify_statements(&statements).map_err(runtime_error)?;
+    to_python(py, &result)
+}
+
+/// Check a Python function's implementation against its docstring contract
+#[pyfunction]
+fn verify_function(py: Python<'_>, code: &str) -> PyResult<PyObject> {
+    let context = Context::new(&Config::new());
+    let result = CodeCoherenceChecker::new(&context).verify_function(code).map_err(runtime_error)?;
+    to_python(py, &result)
+}
+
+/// A Z3 solver kept alive across calls, for incremental checks with
+/// `push_statements` / `pop`. Z3 contexts are not thread-safe, so an instance may
+/// only be used from the thread that created it.
+#[pyclass(name = "CoherenceVerifier", unsendable)]
+struct PyCoherenceVerifier {
+    // Declared before `_context` so it is dropped first
+    verifier: CoherenceVerifier<'static>,
+    _context: Box<Context>,
+}
+
+#[pymethods]
+impl PyCoherenceVerifier {
+    #[new]
+    fn new() -> Self {
+        let context = Box::new(Context::new(&Config::new()));
+        // SAFETY: the context is heap-allocated, never moved out of its box and only
+        // dropped after the verifier borrowing it (field order above)
+        let borrowed: &'static Context = unsafe { &*(context.as_ref() as *const Context) };
+        Self { verifier: CoherenceVerifier::new(borrowed), _context: context }
+    }
+
+    fn verify_statements(&mut self, py: Python<'_>, statements: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let statements: Vec<Statement> = from_python(py, statements)?;
+        let result = self.verifier.verify_statements(&statements).map_err(runtime_error)?;
+        to_python(py, &result)
+    }
+
+    fn push_statements(&mut self, py: Python<'_>, statements: &Bound<'_, PyAny>) -> PyResult<()> {
+        let statements: Vec<Statement> = from_python(py, statements)?;
+        self.verifier.push_statements(&statements).map_err(runtime_error)
+    }
+
+    fn pop(&mut self) -> PyResult<()> {
+        self.verifier.pop().map_err(runtime_error)
+    }
+
+    fn check_pushed(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        let result = self.verifier.check_pushed().map_err(runtime_error)?;
+        to_python(py, &result)
+    }
+}
+
+#[pymodule]
+fn coherence(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(verify_statements, module)?)?;
+    module.add_function(wrap_pyfunction!(verify_function, module)?)?;
+    module.add_class::<PyCoherenceVerifier>()?;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+/*!
+Translation rules
+
+A rule looks at a function's contract and the analysis of its implementation and
+contributes Z3 statements. Rules that detect a conflict contribute a pair of statements
+that contradict each other, so the conflict is proven by the verifier like any other.
+`PredicateTranslator` runs the built-in rules below and then any rules registered with
+`PredicateTranslator::register`, in registration order.
+
+Statement IDs must be unique across the rules that fire for one function. A conflict is
+reported with the violation type of the rule whose statements are contradicted.
+*/
+
+use crate::{is_constant_time, FunctionContract, ImplementationLogic, ViolationType};
+use coherence_verifier::{Predicate, Statement};
+
+/// Maps part of a contract and implementation to statements for Z3
+pub trait TranslationRule {
+    fn apply(&self, contract: &FunctionContract, implementation: &ImplementationLogic) -> Vec<Statement>;
+
+    /// How a contradiction of this rule's statements is reported
+    fn violation_type(&self) -> ViolationType {
+        ViolationType::ContractImplementationMismatch
+    }
+}
+
+/// A statement that `name(args)` holds, or with `negated` that it does not
+pub fn atom_statement(id: &str, text: &str, name: &str, args: &[&str], negated: bool) -> Statement {
+    Statement {
+        id: id.to_string(),
+        text: text.to_string(),
+        predicates: vec![Predicate {
+            name: name.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            negated,
+        }],
+        body: None,
+        quantifier: None,
+        weight: None,
+    }
+}
+
+/// A sorted-result contract against a body returning a reversed sequence
+pub struct SortedVersusReversed;
+
+impl TranslationRule for SortedVersusReversed {
+    fn apply(&self, contract: &FunctionContract, implementation: &ImplementationLogic) -> Vec<Statement> {
+        if !contract.postconditions.iter().any(|condition| condition == "result_is_sorted")
+            || !implementation.return_conditions.iter().any(|condition| condition == "returns_reversed_result")
+        {
+            return Vec::new();
+        }
+        vec![
+            atom_statement("sorted_contract", "Contract: result is sorted", "result_is_sorted", &["output"], false),
+            atom_statement("reversed_return", "Implementation returns reversed", "result_is_sorted", &["output"], true),
+        ]
+    }
+}
+
+/// A constant-time contract cannot be met by a body whose work grows with the input
+pub struct ConstantTime;
+
+impl TranslationRule for ConstantTime {
+    fn apply(&self, contract: &FunctionContract, implementation: &ImplementationLogic) -> Vec<Statement> {
+        let Some(complexity) = contract.complexity.as_deref().filter(|complexity| is_constant_time(complexity)) else {
+            return Vec::new();
+        };
+
+        let mut growing = Vec::new();
+        if implementation.iterates_input {
+            growing.push("iterates over the input");
+        }
+        if implementation.calls_sorted {
+            growing.push("calls sorted");
+        }
+        if implementation.is_recursive {
+            growing.push("recurses");
+        }
+        if growing.is_empty() {
+            return Vec::new();
+        }
+
+        vec![
+            atom_statement("complexity_contract", &format!("Contract: runs in {}", complexity), "constant_time", &["implementation"], false),
+            atom_statement(
+                "impossible_complexity",
+                &format!("Implementation {}", growing.join(" and ")),
+                "constant_time",
+                &["implementation"],
+                true,
+            ),
+        ]
+    }
+
+    fn violation_type(&self) -> ViolationType {
+        ViolationType::LogicalImpossibility
+    }
+}
+
+/// A promise not to modify the arguments against mutations of their objects
+pub struct Purity;
+
+impl TranslationRule for Purity {
+    fn apply(&self, contract: &FunctionContract, implementation: &ImplementationLogic) -> Vec<Statement> {
+        if !contract.pure || implementation.parameter_mutations.is_empty() {
+            return Vec::new();
+        }
+        vec![
+            atom_statement("pure_contract", "Contract: does not modify its arguments", "modifies_input", &["implementation"], true),
+            atom_statement(
+                "mutation_arguments",
+                &format!("Implementation mutates {}", implementation.parameter_mutations.join(", ")),
+                "modifies_input",
+                &["implementation"],
+                false,
+            ),
+        ]
+    }
+
+    fn violation_type(&self) -> ViolationType {
+        ViolationType::StateContradiction
+    }
+}
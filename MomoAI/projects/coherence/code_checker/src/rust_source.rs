@@ -0,0 +1,561 @@
+/*!
+Rust source backend
+
+`RustCoherenceChecker` checks Rust functions against contracts in their doc comments,
+the way `CodeCoherenceChecker` checks Python functions against their docstrings. Doc
+comments are read like docstrings (`sorted`, `O(n)`, "Returns a positive number",
+`@requires`/`@ensures`), and `# Preconditions` / `# Postconditions` sections list
+arithmetic conditions, one per bullet:
+
+```text
+/// Scores from best to worst.
+///
+/// # Preconditions
+/// - `limit >= 1`
+///
+/// # Postconditions
+/// - the result is sorted
+/// - `result >= 0`
+```
+
+The body is analysed with `syn` into the same `ImplementationLogic` as Python code,
+so contracts go through the same `PredicateTranslator` rules and Z3 checks. Tail
+expressions count as returns. A returned variable whose last ordering call was
+`.reverse()`, or a returned `.rev()` chain, is a reversed result.
+*/
+
+use anyhow::Result;
+use proc_macro2::{LineColumn, Span};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Attribute, Block, Expr, FnArg, ImplItem, Item, Pat, ReturnType, Signature, Stmt, Type};
+#[cfg(feature = "z3")]
+use z3::Context;
+
+use crate::{
+    CodeCoherenceChecker, CodeVerificationResult, ContractExtractor, FunctionContract, ImplementationLogic,
+    SourcePosition, TranslationRule,
+};
+use coherence_verifier::arithmetic;
+use coherence_verifier::backend::Backend;
+
+/// Coherence checking of Rust functions against their doc-comment contracts
+pub struct RustCoherenceChecker<'ctx> {
+    checker: CodeCoherenceChecker<'ctx>,
+}
+
+impl<'ctx> RustCoherenceChecker<'ctx> {
+    #[cfg(feature = "z3")]
+    pub fn new(context: &'ctx Context) -> Self {
+        Self { checker: CodeCoherenceChecker::new(context) }
+    }
+
+    /// A checker deciding contracts with `backend`, see [`CodeCoherenceChecker::with_backend`]
+    pub fn with_backend(backend: impl Backend + 'ctx) -> Self {
+        Self { checker: CodeCoherenceChecker::with_backend(backend) }
+    }
+
+    /// Add a contract-to-Z3 translation rule to those checked for every function
+    pub fn register_rule(&mut self, rule: impl TranslationRule + 'static) {
+        self.checker.register_rule(rule);
+    }
+
+    /// Verify coherence of the first function in a Rust snippet
+    pub fn verify_function(&mut self, rust_code: &str) -> Result<CodeVerificationResult> {
+        let file = parse(rust_code)?;
+        let mut functions = Vec::new();
+        collect_functions(&file.items, "", &mut functions);
+        let function = functions.first().ok_or_else(|| anyhow::anyhow!("No function found in Rust code"))?;
+        self.verify(rust_code, function)
+    }
+
+    /// Verify each function of a Rust file separately, in source order: free functions,
+    /// methods of `impl` blocks (`Type.method`) and functions of inline modules
+    /// (`module.function`)
+    pub fn verify_file(&mut self, rust_code: &str) -> Result<Vec<CodeVerificationResult>> {
+        let file = parse(rust_code)?;
+        let mut functions = Vec::new();
+        collect_functions(&file.items, "", &mut functions);
+        functions.iter().map(|function| self.verify(rust_code, function)).collect()
+    }
+
+    fn verify(&mut self, code: &str, function: &RustFunction) -> Result<CodeVerificationResult> {
+        let contract = extract_contract(code, function)?;
+        let implementation = analyze_implementation(code, function);
+        self.checker.verify_contract(&contract, &implementation)
+    }
+}
+
+fn parse(code: &str) -> Result<syn::File> {
+    syn::parse_file(code).map_err(|e| anyhow::anyhow!("Cannot parse Rust code: {}", e))
+}
+
+/// A function found in a Rust file, with its name qualified by `impl` type or module
+struct RustFunction<'a> {
+    qualified_name: String,
+    attrs: &'a [Attribute],
+    sig: &'a Signature,
+    block: &'a Block,
+}
+
+fn collect_functions<'a>(items: &'a [Item], prefix: &str, functions: &mut Vec<RustFunction<'a>>) {
+    for item in items {
+        match item {
+            Item::Fn(function) => functions.push(RustFunction {
+                qualified_name: format!("{}{}", prefix, function.sig.ident),
+                attrs: &function.attrs,
+                sig: &function.sig,
+                block: &function.block,
+            }),
+            Item::Impl(implementation) => {
+                let type_name = match implementation.self_ty.as_ref() {
+                    Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+                    _ => None,
+                }
+                .unwrap_or_else(|| "impl".to_string());
+                for item in &implementation.items {
+                    if let ImplItem::Fn(method) = item {
+                        functions.push(RustFunction {
+                            qualified_name: format!("{}{}.{}", prefix, type_name, method.sig.ident),
+                            attrs: &method.attrs,
+                            sig: &method.sig,
+                            block: &method.block,
+                        });
+                    }
+                }
+            }
+            Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    collect_functions(items, &format!("{}{}.", prefix, module.ident), functions);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Contract from the signature and doc comment
+fn extract_contract(code: &str, function: &RustFunction) -> Result<FunctionContract> {
+    let mut contract = FunctionContract {
+        name: function.qualified_name.clone(),
+        preconditions: Vec::new(),
+        postconditions: Vec::new(),
+        input_types: Vec::new(),
+        output_type: None,
+        docstring: None,
+        raises: Vec::new(),
+        complexity: None,
+        parameter_types: Vec::new(),
+        invariants: Vec::new(),
+        pure: false,
+        position: position(function.sig.fn_token.span.start()),
+        docstring_position: None,
+    };
+
+    let mut parameters = Vec::new();
+    for input in &function.sig.inputs {
+        match input {
+            FnArg::Receiver(_) => contract.input_types.push("Self".to_string()),
+            FnArg::Typed(typed) => {
+                let ty = integer_type(&span_text(code, typed.ty.span()));
+                contract.input_types.push(ty.clone());
+                if let Pat::Ident(pat) = typed.pat.as_ref() {
+                    parameters.push(pat.ident.to_string());
+                    contract.parameter_types.push((pat.ident.to_string(), ty));
+                }
+            }
+        }
+    }
+    if let ReturnType::Type(_, ty) = &function.sig.output {
+        contract.output_type = Some(integer_type(&span_text(code, ty.span())));
+    }
+
+    let docs: Vec<&Attribute> = function.attrs.iter().filter(|attr| attr.path().is_ident("doc")).collect();
+    let lines: Vec<String> = docs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value: Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(line), .. }),
+                ..
+            }) => Some(line.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').unwrap_or(&line).trim_end().to_string())
+        .collect();
+    if lines.is_empty() {
+        return Ok(contract);
+    }
+
+    let docstring = lines.join("\n");
+    contract.docstring_position = docs.first().map(|attr| position(attr.span().start()));
+    ContractExtractor.parse_docstring_contracts(&mut contract, &docstring, &parameters)?;
+    for (section, condition) in section_conditions(&lines) {
+        let conditions = match section {
+            Section::Preconditions => &mut contract.preconditions,
+            Section::Postconditions => &mut contract.postconditions,
+        };
+        if !conditions.contains(&condition) {
+            conditions.push(condition);
+        }
+    }
+    contract.docstring = Some(docstring);
+
+    Ok(contract)
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Preconditions,
+    Postconditions,
+}
+
+/// Arithmetic conditions listed under `# Preconditions` / `# Postconditions`, one per
+/// line or bullet, with code backticks removed. Other items are only read for the
+/// docstring phrases every line is checked for.
+fn section_conditions(lines: &[String]) -> Vec<(Section, String)> {
+    let mut section = None;
+    let mut conditions = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if let Some(heading) = line.strip_prefix('#') {
+            section = match heading.trim_start_matches('#').trim().to_lowercase().as_str() {
+                "preconditions" | "requires" => Some(Section::Preconditions),
+                "postconditions" | "ensures" => Some(Section::Postconditions),
+                _ => None,
+            };
+            continue;
+        }
+        let Some(section) = section else { continue };
+        let item = line.trim_start_matches(['-', '*']).replace('`', "");
+        let condition = item.split_whitespace().collect::<Vec<_>>().join(" ");
+        if arithmetic::parse_comparison(&condition).is_ok() {
+            conditions.push((section, condition));
+        }
+    }
+    conditions
+}
+
+/// Rust integer types under the fixed-width names the range checks know (`u8` is
+/// `uint8`); other types are kept as written
+fn integer_type(ty: &str) -> String {
+    let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+    match ty.as_str() {
+        "u8" | "u16" | "u32" | "u64" => format!("uint{}", &ty[1..]),
+        "i8" | "i16" | "i32" | "i64" => format!("int{}", &ty[1..]),
+        _ => ty,
+    }
+}
+
+fn analyze_implementation(code: &str, function: &RustFunction) -> ImplementationLogic {
+    let mut parameters = Vec::new();
+    let mut mutable = Vec::new();
+    for input in &function.sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => {
+                parameters.push("self".to_string());
+                if receiver.reference.is_some() && receiver.mutability.is_some() {
+                    mutable.push("self".to_string());
+                }
+            }
+            FnArg::Typed(typed) => {
+                let Pat::Ident(pat) = typed.pat.as_ref() else { continue };
+                parameters.push(pat.ident.to_string());
+                if matches!(typed.ty.as_ref(), Type::Reference(reference) if reference.mutability.is_some()) {
+                    mutable.push(pat.ident.to_string());
+                }
+            }
+        }
+    }
+
+    let mut scan = BodyScan {
+        code,
+        name: function.sig.ident.to_string(),
+        parameters,
+        mutable,
+        returns: Vec::new(),
+        orderings: Vec::new(),
+        loop_depth: 0,
+        input_loop_depth: 0,
+        iterates_input: false,
+        calls_sorted: false,
+        is_recursive: false,
+        mutations: Vec::new(),
+        closure_depth: 0,
+    };
+    for stmt in &function.block.stmts {
+        scan.visit_stmt(stmt);
+    }
+    // The tail expression is the value of the body
+    if let Some(Stmt::Expr(tail, None)) = function.block.stmts.last() {
+        if !matches!(tail, Expr::Return(_)) {
+            scan.returns.push(tail);
+        }
+    }
+
+    let mut logic = ImplementationLogic {
+        function_name: function.qualified_name.clone(),
+        logical_assertions: Vec::new(),
+        state_changes: Vec::new(),
+        return_conditions: Vec::new(),
+        raised_exceptions: Vec::new(),
+        return_expressions: Vec::new(),
+        return_types: Vec::new(),
+        return_positions: Vec::new(),
+        input_loop_depth: scan.input_loop_depth,
+        iterates_input: scan.iterates_input,
+        calls_sorted: scan.calls_sorted,
+        is_recursive: scan.is_recursive,
+        parameter_mutations: scan.mutations.clone(),
+        returns_none_implicitly: false,
+    };
+    for expr in &scan.returns {
+        logic.return_conditions.push(scan.return_condition(expr).to_string());
+        logic.return_expressions.push(span_text(code, expr.span()));
+        logic.return_types.push(None);
+        logic.return_positions.push(position(expr.span().start()));
+    }
+    logic
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Ordering {
+    Sorted,
+    Reversed,
+}
+
+/// Facts about a function body gathered in one pass. Closures and nested functions
+/// have returns of their own, which are not the function's.
+struct BodyScan<'a, 'ast> {
+    code: &'a str,
+    name: String,
+    parameters: Vec<String>,
+    /// Parameters the caller can observe changes to: `&mut` references and `&mut self`
+    mutable: Vec<String>,
+    returns: Vec<&'ast Expr>,
+    /// In-place `sort*` / `reverse` calls by receiver variable, in source order
+    orderings: Vec<(String, Ordering, LineColumn)>,
+    loop_depth: usize,
+    input_loop_depth: usize,
+    iterates_input: bool,
+    calls_sorted: bool,
+    is_recursive: bool,
+    mutations: Vec<String>,
+    closure_depth: usize,
+}
+
+impl BodyScan<'_, '_> {
+    fn is_parameter(&self, name: &str) -> bool {
+        self.parameters.iter().any(|parameter| parameter == name)
+    }
+
+    fn mentions_parameter(&self, expr: &Expr) -> bool {
+        let mut mentions = Mentions { parameters: &self.parameters, found: false };
+        mentions.visit_expr(expr);
+        mentions.found
+    }
+
+    /// Count a loop whose iteration depends on a parameter toward the loop depth
+    fn enter_loop(&mut self, over_input: bool) -> bool {
+        if over_input {
+            self.loop_depth += 1;
+            self.input_loop_depth = self.input_loop_depth.max(self.loop_depth);
+            self.iterates_input = true;
+        }
+        over_input
+    }
+
+    fn leave_loop(&mut self, counted: bool) {
+        if counted {
+            self.loop_depth -= 1;
+        }
+    }
+
+    fn record_mutation(&mut self, target: &Expr, text: Span) {
+        if root_variable(target).is_some_and(|root| self.mutable.contains(&root)) {
+            self.mutations.push(span_text(self.code, text));
+        }
+    }
+
+    /// What a returned expression says about the result, like `return_condition` for
+    /// Python: a `.rev()` chain, or a variable last reversed or sorted in place
+    fn return_condition(&self, expr: &Expr) -> &'static str {
+        if method_chain(expr).any(|method| method == "rev") {
+            return "returns_reversed_result";
+        }
+        let Some(variable) = variable(expr) else { return "returns_value" };
+        let returned_at = expr.span().start();
+        let last = self.orderings
+            .iter()
+            .rev()
+            .find(|(name, _, at)| *name == variable && (at.line, at.column) < (returned_at.line, returned_at.column));
+        match last {
+            Some((_, Ordering::Reversed, _)) => "returns_reversed_result",
+            Some((_, Ordering::Sorted, _)) => "returns_sorted_result",
+            None => "returns_value",
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for BodyScan<'_, 'ast> {
+    fn visit_expr_return(&mut self, node: &'ast syn::ExprReturn) {
+        if self.closure_depth == 0 {
+            if let Some(expr) = &node.expr {
+                self.returns.push(expr);
+            }
+        }
+        visit::visit_expr_return(self, node);
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast syn::ExprClosure) {
+        self.closure_depth += 1;
+        visit::visit_expr_closure(self, node);
+        self.closure_depth -= 1;
+    }
+
+    fn visit_item_fn(&mut self, _: &'ast syn::ItemFn) {}
+
+    fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+        let counted = self.enter_loop(self.mentions_parameter(&node.expr));
+        visit::visit_expr_for_loop(self, node);
+        self.leave_loop(counted);
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+        let counted = self.enter_loop(self.mentions_parameter(&node.cond));
+        visit::visit_expr_while(self, node);
+        self.leave_loop(counted);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        let method = node.method.to_string();
+        let root = root_variable(&node.receiver);
+
+        if method.starts_with("sort") {
+            self.calls_sorted = true;
+        }
+        if let Some(root) = &root {
+            let ordering = match method.as_str() {
+                "reverse" => Some(Ordering::Reversed),
+                _ if method.starts_with("sort") => Some(Ordering::Sorted),
+                _ => None,
+            };
+            if let Some(ordering) = ordering {
+                self.orderings.push((root.clone(), ordering, node.span().start()));
+            }
+            if self.is_parameter(root) && ["iter", "into_iter", "iter_mut", "chars", "bytes", "windows", "chunks"].contains(&method.as_str()) {
+                self.iterates_input = true;
+            }
+            if root == "self" && method == self.name {
+                self.is_recursive = true;
+            }
+        }
+        if MUTATING_METHODS.contains(&method.as_str()) || method.starts_with("sort") {
+            self.record_mutation(&node.receiver, node.span());
+        }
+        visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = node.func.as_ref() {
+            if path.path.segments.last().is_some_and(|segment| segment.ident == self.name) {
+                self.is_recursive = true;
+            }
+        }
+        visit::visit_expr_call(self, node);
+    }
+
+    fn visit_expr_assign(&mut self, node: &'ast syn::ExprAssign) {
+        // Rebinding a parameter changes nothing the caller sees; writing through it does
+        if !matches!(node.left.as_ref(), Expr::Path(_)) {
+            self.record_mutation(&node.left, node.span());
+        }
+        visit::visit_expr_assign(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        let compound = matches!(
+            node.op,
+            syn::BinOp::AddAssign(_) | syn::BinOp::SubAssign(_) | syn::BinOp::MulAssign(_) | syn::BinOp::DivAssign(_)
+                | syn::BinOp::RemAssign(_) | syn::BinOp::BitAndAssign(_) | syn::BinOp::BitOrAssign(_)
+                | syn::BinOp::BitXorAssign(_) | syn::BinOp::ShlAssign(_) | syn::BinOp::ShrAssign(_)
+        );
+        if compound && !matches!(node.left.as_ref(), Expr::Path(_)) {
+            self.record_mutation(&node.left, node.span());
+        }
+        visit::visit_expr_binary(self, node);
+    }
+}
+
+/// Methods of standard collections and strings that change their receiver
+const MUTATING_METHODS: &[&str] = &[
+    "push", "push_str", "pop", "insert", "remove", "clear", "truncate", "extend", "append", "retain", "dedup",
+    "drain", "swap", "reverse", "fill", "resize", "split_off",
+];
+
+/// Whether an expression mentions one of the parameters
+struct Mentions<'a> {
+    parameters: &'a [String],
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for Mentions<'_> {
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        if node.path.get_ident().is_some_and(|ident| self.parameters.iter().any(|parameter| ident == parameter)) {
+            self.found = true;
+        }
+    }
+}
+
+/// The variable an expression reads or writes through: `items` for `items[0]`,
+/// `self` for `self.scores.push(x)`, `v` for `*v`
+fn root_variable(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        Expr::MethodCall(call) => root_variable(&call.receiver),
+        Expr::Field(field) => root_variable(&field.base),
+        Expr::Index(index) => root_variable(&index.expr),
+        Expr::Reference(reference) => root_variable(&reference.expr),
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Deref(_), expr, .. }) => root_variable(expr),
+        Expr::Paren(paren) => root_variable(&paren.expr),
+        _ => None,
+    }
+}
+
+/// A plain variable, possibly parenthesised or borrowed
+fn variable(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        Expr::Reference(reference) => variable(&reference.expr),
+        Expr::Paren(paren) => variable(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Names of the methods called along a `a.b().c()` chain, outermost first
+fn method_chain(expr: &Expr) -> impl Iterator<Item = String> + '_ {
+    std::iter::successors(Some(expr), |expr| match expr {
+        Expr::MethodCall(call) => Some(call.receiver.as_ref()),
+        _ => None,
+    })
+    .filter_map(|expr| match expr {
+        Expr::MethodCall(call) => Some(call.method.to_string()),
+        _ => None,
+    })
+}
+
+/// 1-based line and column of a span position (columns are 0-based in spans)
+fn position(at: LineColumn) -> SourcePosition {
+    SourcePosition { line: at.line, column: at.column + 1 }
+}
+
+/// Source text covered by a span
+fn span_text(code: &str, span: Span) -> String {
+    let offset = |at: LineColumn| {
+        let line_start: usize = code.split_inclusive('\n').take(at.line - 1).map(str::len).sum();
+        let column: usize = code[line_start..].chars().take(at.column).map(char::len_utf8).sum();
+        line_start + column
+    };
+    let (start, end) = (offset(span.start()), offset(span.end()));
+    code.get(start..end).unwrap_or_default().to_string()
+}
+
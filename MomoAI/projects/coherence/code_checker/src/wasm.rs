@@ -0,0 +1,35 @@
+/*!
+WebAssembly exports
+
+Built with the `wasm` feature as a `cdylib` for wasm32 (see the README for the commands).
+The module also carries the verifier's exports, `verify_statements_json` and `backend`
+from [`coherence_verifier::wasm`]. Inputs and outputs are JSON strings; failures come
+back as `{"error": "..."}`.
+
+Z3 does not build for `wasm32-unknown-unknown`, so browser builds decide contracts
+with the propositional SAT backend. Checks on the syntax tree alone, such as returned
+types and implicit `None`, run as natively; contracts with arithmetic are left
+undecided, so they are not reported as broken.
+*/
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::CodeCoherenceChecker;
+
+/// Verify the Python function in `code` against its docstring contract; returns the
+/// `CodeVerificationResult` as JSON
+#[wasm_bindgen]
+pub fn verify_function(code: &str) -> String {
+    #[cfg(feature = "z3")]
+    let context = z3::Context::new(&z3::Config::new());
+    #[cfg(feature = "z3")]
+    let mut checker = CodeCoherenceChecker::new(&context);
+    #[cfg(not(feature = "z3"))]
+    let mut checker = CodeCoherenceChecker::with_backend(coherence_verifier::propositional::PropositionalVerifier::new());
+
+    match checker.verify_function(code).and_then(|result| Ok(serde_json::to_string(&result)?)) {
+        Ok(json) => json,
+        Err(error) => json!({ "error": format!("{:#}", error) }).to_string(),
+    }
+}
@@ -0,0 +1,99 @@
+/*!
+Watch mode
+
+Re-verifies a Python file each time it is saved, clearing the screen and printing the
+fresh result. Editors often write a file several times per save (or replace it with a
+renamed temporary), so a change is only acted on once no further change has come in
+for the debounce interval. One checker, and so one Z3 context, serves every run. A
+file that does not parse, or cannot be read mid-save, is reported and watching goes on.
+*/
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use coherence_verifier::reporter::{HumanReporter, Reporter, Style};
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::{CodeCoherenceChecker, CodeVerificationResult};
+
+/// Clear the terminal and move the cursor home
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Watch the file at `path` until the process is interrupted, printing each run's
+/// result to `out` in the human format
+pub fn watch(
+    checker: &mut CodeCoherenceChecker,
+    path: &Path,
+    debounce: Duration,
+    mut out: impl Write,
+    style: Style,
+) -> Result<()> {
+    let (sender, changes) = mpsc::channel();
+    let file_name = path.file_name().map(ToOwned::to_owned);
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        let touches_file = event.paths.iter().any(|changed| changed.file_name() == file_name.as_deref());
+        if touches_file && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+            let _ = sender.send(());
+        }
+    })?;
+    // Watch the directory rather than the file, which an atomic save replaces
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Cannot watch {}", dir.display()))?;
+
+    let name = path.display().to_string();
+    let mut reporter = HumanReporter::new(style);
+    run(checker, path, &changes, debounce, |results| {
+        if style.is_decorated() {
+            write!(out, "{}", CLEAR_SCREEN)?;
+        } else {
+            writeln!(out, "{}", "-".repeat(40))?;
+        }
+        match results {
+            Ok(results) => {
+                reporter.section(&mut out, &format!("👀 Watching {}", name), &[])?;
+                for result in &results {
+                    reporter.section(&mut out, &format!("Function {}:", result.location), &[])?;
+                    reporter.entry(&mut out, &result.to_file_report_entry(&name))?;
+                }
+            }
+            Err(error) => writeln!(out, "{}", style.paint(&format!("❌ {}: {:#}", name, error)))?,
+        }
+        out.flush()?;
+        Ok(())
+    })
+}
+
+/// Verify the file at `path` as a module, then again after each burst of signals on
+/// `changes` has been quiet for `debounce`, handing every outcome to `on_verified`.
+/// Returns once `changes` disconnects.
+pub fn run(
+    checker: &mut CodeCoherenceChecker,
+    path: &Path,
+    changes: &Receiver<()>,
+    debounce: Duration,
+    mut on_verified: impl FnMut(Result<Vec<CodeVerificationResult>>) -> Result<()>,
+) -> Result<()> {
+    loop {
+        let results = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read {}", path.display()))
+            .and_then(|code| checker.verify_module(&code));
+        on_verified(results)?;
+
+        if changes.recv().is_err() {
+            return Ok(());
+        }
+        // Wait for the burst of changes to end
+        while changes.recv_timeout(debounce).is_ok() {}
+    }
+}
@@ -0,0 +1,288 @@
+#![cfg(feature = "z3")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn code_checker(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_code_checker"))
+        .args(args)
+        .output()
+        .expect("failed to run code_checker")
+}
+
+#[test]
+fn exit_code_reflects_verdict() {
+    let coherent = "def add(a, b):\n    \"\"\"Returns the sum of two numbers.\"\"\"\n    return a + b\n";
+    let incoherent = "def negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n";
+
+    assert_eq!(code_checker(&["verify-function", "--code", coherent]).status.code(), Some(0));
+    assert_eq!(code_checker(&["verify-function", "--code", incoherent]).status.code(), Some(1));
+    assert_eq!(code_checker(&["--no-fail", "verify-function", "--code", incoherent]).status.code(), Some(0));
+}
+
+/// Run `code_checker verify-function --code -` with `code` piped to its stdin
+fn verify_stdin(code: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_code_checker"))
+        .args(["verify-function", "--code", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run code_checker");
+    child.stdin.take().unwrap().write_all(code.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn verify_function_from_stdin() {
+    let code = "def negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n";
+    let output = verify_stdin(code);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("INCOHERENT"));
+
+    let output = verify_stdin("  \n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No Python code on stdin"));
+}
+
+#[test]
+fn verify_dir_reports_each_file() {
+    let dir = std::env::temp_dir().join(format!("code_checker-dir-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("pkg")).unwrap();
+    std::fs::write(
+        dir.join("a_add.py"),
+        "def add(a, b):\n    \"\"\"Returns the sum of two numbers.\"\"\"\n    return a + b\n",
+    ).unwrap();
+    std::fs::write(
+        dir.join("pkg/b_negate.py"),
+        "def negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n",
+    ).unwrap();
+    std::fs::write(dir.join("pkg/c_binary.py"), [0xff, 0xfe, 0x00]).unwrap();
+    std::fs::write(dir.join("pkg/d_notes.txt"), "def ignored(): pass\n").unwrap();
+    let path = dir.to_str().unwrap();
+
+    let output = code_checker(&["verify-dir", "--path", path]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("a_add.py") && stdout.contains("b_negate.py"), "{}", stdout);
+    assert!(!stdout.contains("d_notes.txt"));
+    assert!(stdout.contains("Coherent: 1") && stdout.contains("Incoherent: 1") && stdout.contains("Skipped: 1"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("c_binary.py"));
+
+    // A narrower glob only sees the coherent file
+    let output = code_checker(&["verify-dir", "--path", path, "--glob", "*.py"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    std::fs::write(dir.join("pkg/e_negate.py"), "def negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n").unwrap();
+    let output = code_checker(&["verify-dir", "--path", path, "--fail-fast"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("e_negate.py"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn check_changed_files() {
+    let dir = std::env::temp_dir().join(format!("code_checker-check-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let add = dir.join("add.py");
+    std::fs::write(&add, "def add(a, b):\n    \"\"\"Returns the sum of two numbers.\"\"\"\n    return a + b\n").unwrap();
+    let negate = dir.join("negate.py");
+    std::fs::write(&negate, "def negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n").unwrap();
+    let notes = dir.join("notes.md");
+    std::fs::write(&notes, "def ignored(): pass\n").unwrap();
+    let (add, negate, notes) = (add.to_str().unwrap(), negate.to_str().unwrap(), notes.to_str().unwrap());
+
+    let output = code_checker(&["check", add, notes, negate]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("add.py") && stdout.contains("negate.py"), "{}", stdout);
+    assert!(!stdout.contains("notes.md"));
+    assert!(stdout.contains("Coherent: 1") && stdout.contains("Incoherent: 1"), "{}", stdout);
+
+    let output = code_checker(&["check", add, notes]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn sarif_results_point_at_the_violation() {
+    let path = std::env::temp_dir().join(format!("code_checker-sarif-{}.py", std::process::id()));
+    std::fs::write(
+        &path,
+        "import math\n\n\ndef negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n",
+    ).unwrap();
+    let file = path.to_str().unwrap();
+
+    let output = code_checker(&["--format", "sarif", "verify-file", "--path", file]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "code_checker");
+    assert_eq!(run["tool"]["driver"]["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "contract-mismatch");
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "contract-mismatch");
+    assert_eq!(result["level"], "error");
+    assert!(result["message"]["text"].as_str().is_some_and(|text| !text.is_empty()));
+    let location = &result["locations"][0];
+    assert_eq!(location["physicalLocation"]["artifactLocation"]["uri"], file);
+    assert_eq!(location["physicalLocation"]["region"]["startLine"], 6);
+    assert_eq!(location["physicalLocation"]["region"]["startColumn"], 5);
+    assert_eq!(location["logicalLocations"][0]["name"], "negate");
+}
+
+/// Frame a JSON-RPC message the way LSP clients send it
+fn lsp_message(message: serde_json::Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+/// Split a server's output into the messages of its `Content-Length` frames
+fn lsp_messages(mut output: &str) -> Vec<serde_json::Value> {
+    let mut messages = Vec::new();
+    while let Some((header, rest)) = output.split_once("\r\n\r\n") {
+        let length: usize = header.trim_start_matches("Content-Length: ").parse().unwrap();
+        messages.push(serde_json::from_str(&rest[..length]).unwrap());
+        output = &rest[length..];
+    }
+    messages
+}
+
+#[test]
+fn lsp_publishes_diagnostics_on_open() {
+    let code = "def sort_list(items):\n    \"\"\"Returns a sorted list in ascending order.\"\"\"\n    return items[::-1]\n";
+    let uri = "file:///tmp/sort.py";
+    let input = [
+        lsp_message(serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}})),
+        lsp_message(serde_json::json!({"jsonrpc": "2.0", "method": "initialized", "params": {}})),
+        lsp_message(serde_json::json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+            "textDocument": {"uri": uri, "languageId": "python", "version": 1, "text": code},
+        }})),
+        lsp_message(serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"})),
+        lsp_message(serde_json::json!({"jsonrpc": "2.0", "method": "exit"})),
+    ].concat();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_code_checker"))
+        .arg("lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run code_checker");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    let messages = lsp_messages(&String::from_utf8(output.stdout).unwrap());
+    assert_eq!(messages[0]["id"], 1);
+    assert_eq!(messages[0]["result"]["capabilities"]["textDocumentSync"], 1);
+
+    let published = messages.iter()
+        .find(|message| message["method"] == "textDocument/publishDiagnostics")
+        .expect("no diagnostics published");
+    assert_eq!(published["params"]["uri"], uri);
+    let diagnostic = &published["params"]["diagnostics"][0];
+    assert_eq!(diagnostic["code"], "contract-mismatch");
+    assert_eq!(diagnostic["severity"], 1);
+    assert_eq!(diagnostic["range"]["start"]["line"], 2);
+    assert_eq!(diagnostic["range"]["start"]["character"], 4);
+}
+
+#[test]
+fn lsp_answers_malformed_messages_and_keeps_serving() {
+    // The 😀 is one character but two UTF-16 code units
+    let code = "def sort_list(items):\n    \"\"\"Returns a sorted list in ascending order.\"\"\"\n    x = \"😀\"; return items[::-1]\n";
+    let uri = "file:///tmp/sort.py";
+    let input = [
+        "Content-Length: lots\r\n\r\n".to_string(),
+        "Content-Length: 6\r\n\r\n{oops}".to_string(),
+        lsp_message(serde_json::json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+            "textDocument": {"uri": uri, "languageId": "python", "version": 1, "text": code},
+        }})),
+        lsp_message(serde_json::json!({"jsonrpc": "2.0", "method": "exit"})),
+    ].concat();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_code_checker"))
+        .arg("lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run code_checker");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    let messages = lsp_messages(&String::from_utf8(output.stdout).unwrap());
+    assert_eq!(messages[0]["error"]["code"], -32700);
+    assert_eq!(messages[1]["error"]["code"], -32700);
+    assert_eq!(messages[0]["id"], serde_json::Value::Null);
+
+    let published = messages.iter()
+        .find(|message| message["method"] == "textDocument/publishDiagnostics")
+        .expect("no diagnostics published after malformed messages");
+    let diagnostic = &published["params"]["diagnostics"][0];
+    assert_eq!(diagnostic["range"]["start"]["line"], 2);
+    assert_eq!(diagnostic["range"]["start"]["character"], 14);
+}
+
+#[test]
+fn test_suite_as_junit() {
+    let output = code_checker(&["test", "--format", "junit"]);
+    assert_eq!(output.status.code(), Some(0));
+    let xml = String::from_utf8_lossy(&output.stdout);
+    assert!(xml.starts_with("<?xml"), "{}", xml);
+    assert_eq!(xml.matches("<testcase ").count(), 5, "{}", xml);
+    assert_eq!(xml.matches("<failure").count(), 0, "{}", xml);
+}
+
+#[test]
+fn quiet_prints_only_a_summary() {
+    let dir = std::env::temp_dir().join(format!("code_checker-quiet-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("add.py"), "def add(a, b):\n    \"\"\"Returns the sum of two numbers.\"\"\"\n    return a + b\n").unwrap();
+    std::fs::write(dir.join("negate.py"), "def negate(x):\n    \"\"\"Returns a positive number. x must be non-negative.\"\"\"\n    return -x\n").unwrap();
+    let path = dir.to_str().unwrap();
+
+    let output = code_checker(&["--quiet", "verify-dir", "--path", path]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1/2 coherent, 1 violations\n");
+
+    let output = code_checker(&["--quiet", "--format", "json", "verify-dir", "--path", path]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "{}", stdout);
+    let summary: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(summary["checked"], 2);
+    assert_eq!(summary["violations"], 1);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn completions_for_bash() {
+    let output = code_checker(&["completions", "bash"]);
+    assert!(output.status.success());
+    let script = String::from_utf8_lossy(&output.stdout);
+    assert!(script.contains("code_checker"));
+    for subcommand in ["verify-function", "verify-file", "verify-dir", "check", "watch"] {
+        assert!(script.contains(subcommand), "{} missing from {}", subcommand, script);
+    }
+
+    assert!(!String::from_utf8_lossy(&code_checker(&["--help"]).stdout).contains("completions"));
+}
+
+#[test]
+fn version_names_z3() {
+    let output = code_checker(&["--version"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(&format!("code_checker {} (Z3 ", env!("CARGO_PKG_VERSION"))), "{}", stdout);
+    let z3 = stdout.trim_end().split("(Z3 ").nth(1).and_then(|rest| rest.strip_suffix(')')).unwrap();
+    assert_eq!(z3.split('.').filter(|part| part.parse::<u32>().is_ok()).count(), 3, "{}", stdout);
+}
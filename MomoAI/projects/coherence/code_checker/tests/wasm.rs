@@ -0,0 +1,24 @@
+//! Headless WebAssembly tests of the JSON API:
+//! `cargo test --target wasm32-unknown-unknown --no-default-features --features wasm --test wasm`
+//! with `wasm-bindgen-test-runner` as the target runner (see README)
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use code_coherence_checker::wasm::verify_function;
+use coherence_verifier::wasm::backend;
+use serde_json::Value;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn incoherent_function_round_trips_through_json() {
+    assert_eq!(backend(), "sat");
+
+    let code = "def sort_list(items):\n    \"\"\"Returns a sorted list in ascending order.\"\"\"\n    return items[::-1]\n";
+    let result: Value = serde_json::from_str(&verify_function(code)).unwrap();
+    assert_eq!(result["location"], "sort_list");
+    assert_eq!(result["is_coherent"], false);
+    assert_eq!(result["violations"][0]["violation_type"], "ContractImplementationMismatch");
+    assert_eq!(result["formal_proof"], "SAT verification: Inconsistent");
+
+    let error: Value = serde_json::from_str(&verify_function("def f(:")).unwrap();
+    assert!(error["error"].as_str().unwrap().starts_with("Cannot parse Python code"));
+}
@@ -0,0 +1,298 @@
+/*!
+Arithmetic comparison predicates
+
+A predicate whose name is a comparison operator (`<`, `<=`, `>`, `>=`, `=`, `!=`)
+and which has exactly two arguments is read as an arithmetic constraint between
+two linear terms such as `B+5` or `2*x - y`, instead of an opaque proposition.
+*/
+
+use anyhow::{anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Comparison operator of an arithmetic predicate, serialized as its symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    /// Recognise a predicate name as a comparison operator
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "<" => Some(Comparison::Lt),
+            "<=" => Some(Comparison::Le),
+            ">" => Some(Comparison::Gt),
+            ">=" => Some(Comparison::Ge),
+            "=" | "==" => Some(Comparison::Eq),
+            "!=" => Some(Comparison::Ne),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+            Comparison::Eq => "=",
+            Comparison::Ne => "!=",
+        }
+    }
+}
+
+impl TryFrom<String> for Comparison {
+    type Error = String;
+
+    fn try_from(symbol: String) -> Result<Self, Self::Error> {
+        Comparison::from_name(&symbol).ok_or_else(|| format!("unknown comparison operator '{}'", symbol))
+    }
+}
+
+impl From<Comparison> for String {
+    fn from(op: Comparison) -> Self {
+        op.symbol().to_string()
+    }
+}
+
+/// Arithmetic term appearing on either side of a comparison, serialized as its text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Term {
+    Const(i64),
+    /// Decimal literal such as `0.7`, stored as numerator / denominator
+    Ratio(i64, i64),
+    Var(String),
+    Neg(Box<Term>),
+    Add(Box<Term>, Box<Term>),
+    Sub(Box<Term>, Box<Term>),
+    Mul(Box<Term>, Box<Term>),
+}
+
+impl Term {
+    /// Names of the variables in the term, in order of appearance
+    pub fn variables(&self) -> Vec<&str> {
+        match self {
+            Term::Const(_) | Term::Ratio(_, _) => vec![],
+            Term::Var(name) => vec![name.as_str()],
+            Term::Neg(inner) => inner.variables(),
+            Term::Add(a, b) | Term::Sub(a, b) | Term::Mul(a, b) => {
+                let mut variables = a.variables();
+                variables.extend(b.variables());
+                variables
+            }
+        }
+    }
+}
+
+impl Term {
+    /// Binding strength when printed: sums, products, then factors
+    fn precedence(&self) -> u8 {
+        match self {
+            Term::Add(_, _) | Term::Sub(_, _) => 1,
+            Term::Mul(_, _) => 2,
+            Term::Const(_) | Term::Ratio(_, _) | Term::Var(_) | Term::Neg(_) => 3,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, min_precedence: u8) -> fmt::Result {
+        if self.precedence() < min_precedence {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+/// Prints text that `parse_term` reads back as an equivalent term
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Const(n) => write!(f, "{}", n),
+            Term::Ratio(num, den) => {
+                let places = den.checked_ilog10().filter(|places| 10i64.pow(*places) == *den);
+                match places {
+                    Some(places) => {
+                        let sign = if *num < 0 { "-" } else { "" };
+                        let num = num.unsigned_abs();
+                        let den = den.unsigned_abs();
+                        write!(f, "{}{}.{:0width$}", sign, num / den, num % den, width = places as usize)
+                    }
+                    None => write!(f, "{}/{}", num, den),
+                }
+            }
+            Term::Var(name) => write!(f, "{}", name),
+            Term::Neg(inner) => {
+                write!(f, "-")?;
+                inner.fmt_operand(f, 3)
+            }
+            Term::Add(a, b) => {
+                a.fmt_operand(f, 1)?;
+                write!(f, " + ")?;
+                b.fmt_operand(f, 1)
+            }
+            Term::Sub(a, b) => {
+                a.fmt_operand(f, 1)?;
+                write!(f, " - ")?;
+                b.fmt_operand(f, 2)
+            }
+            Term::Mul(a, b) => {
+                a.fmt_operand(f, 2)?;
+                write!(f, "*")?;
+                b.fmt_operand(f, 3)
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for Term {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        parse_term(&text).map_err(|e| e.to_string())
+    }
+}
+
+impl From<Term> for String {
+    fn from(term: Term) -> Self {
+        term.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(i64),
+    Decimal(i64, i64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    LParen,
+    RParen,
+}
+
+/// Parse a term built from integer or decimal literals, variables, `+`, `-`, `*` and
+/// parentheses
+pub fn parse_term(text: &str) -> anyhow::Result<Term> {
+    let tokens = tokenize(text)?;
+    let mut pos = 0;
+    let term = parse_sum(&tokens, &mut pos)?;
+    if pos != tokens.len() {
+        bail!("Unexpected trailing input in arithmetic term '{}'", text);
+    }
+    Ok(term)
+}
+
+/// Parse a comparison between two terms such as `result > 0` or `2*x <= y + 1`
+pub fn parse_comparison(text: &str) -> anyhow::Result<(Term, Comparison, Term)> {
+    let start = text
+        .find(['<', '>', '=', '!'])
+        .ok_or_else(|| anyhow!("No comparison operator in '{}'", text))?;
+    let len = if text[start + 1..].starts_with('=') { 2 } else { 1 };
+    let op = Comparison::from_name(&text[start..start + len])
+        .ok_or_else(|| anyhow!("Unknown comparison operator in '{}'", text))?;
+    Ok((parse_term(&text[..start])?, op, parse_term(&text[start + len..])?))
+}
+
+fn tokenize(text: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut digits = String::new();
+            while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                digits.push(d);
+                chars.next();
+            }
+            if chars.peek() == Some(&'.') {
+                chars.next();
+                let mut fraction = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    fraction.push(d);
+                    chars.next();
+                }
+                let den = 10i64.checked_pow(fraction.len() as u32)
+                    .ok_or_else(|| anyhow!("Too many decimal places in arithmetic term '{}'", text))?;
+                let num = format!("{}{}", digits, fraction).parse()?;
+                tokens.push(Token::Decimal(num, den));
+            } else {
+                tokens.push(Token::Num(digits.parse()?));
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let mut ident = String::new();
+            while let Some(&d) = chars.peek().filter(|d| d.is_alphanumeric() || **d == '_' || **d == '.') {
+                ident.push(d);
+                chars.next();
+            }
+            tokens.push(Token::Ident(ident));
+        } else {
+            tokens.push(match c {
+                '+' => Token::Plus,
+                '-' => Token::Minus,
+                '*' => Token::Star,
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                _ => bail!("Unexpected character '{}' in arithmetic term '{}'", c, text),
+            });
+            chars.next();
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_sum(tokens: &[Token], pos: &mut usize) -> anyhow::Result<Term> {
+    let mut term = parse_product(tokens, pos)?;
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::Plus) => {
+                *pos += 1;
+                term = Term::Add(Box::new(term), Box::new(parse_product(tokens, pos)?));
+            }
+            Some(Token::Minus) => {
+                *pos += 1;
+                term = Term::Sub(Box::new(term), Box::new(parse_product(tokens, pos)?));
+            }
+            _ => return Ok(term),
+        }
+    }
+}
+
+fn parse_product(tokens: &[Token], pos: &mut usize) -> anyhow::Result<Term> {
+    let mut term = parse_factor(tokens, pos)?;
+    while tokens.get(*pos) == Some(&Token::Star) {
+        *pos += 1;
+        term = Term::Mul(Box::new(term), Box::new(parse_factor(tokens, pos)?));
+    }
+    Ok(term)
+}
+
+fn parse_factor(tokens: &[Token], pos: &mut usize) -> anyhow::Result<Term> {
+    let token = tokens.get(*pos).ok_or_else(|| anyhow!("Unexpected end of arithmetic term"))?;
+    *pos += 1;
+    match token {
+        Token::Num(n) => Ok(Term::Const(*n)),
+        Token::Decimal(num, den) => Ok(Term::Ratio(*num, *den)),
+        Token::Ident(name) => Ok(Term::Var(name.clone())),
+        Token::Minus => Ok(Term::Neg(Box::new(parse_factor(tokens, pos)?))),
+        Token::LParen => {
+            let term = parse_sum(tokens, pos)?;
+            if tokens.get(*pos) != Some(&Token::RParen) {
+                bail!("Missing ')' in arithmetic term");
+            }
+            *pos += 1;
+            Ok(term)
+        }
+        other => bail!("Unexpected {:?} in arithmetic term", other),
+    }
+}
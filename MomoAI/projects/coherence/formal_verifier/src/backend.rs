@@ -0,0 +1,41 @@
+/*!
+Verification backends
+
+[`CoherenceVerifier`](crate::CoherenceVerifier) decides statements with Z3 and needs the
+`z3` feature. [`PropositionalVerifier`] is a pure-Rust SAT fallback that decides
+statements without quantifiers or arithmetic and reports everything else as unknown.
+Code that only needs consistency verdicts can take either through [`Backend`].
+*/
+
+use crate::propositional::PropositionalVerifier;
+use crate::{Statement, VerificationResult};
+
+/// Decides whether a set of statements is consistent
+pub trait Backend {
+    /// Short name of the decision procedure, e.g. "z3"
+    fn name(&self) -> &'static str;
+
+    /// Verify logical consistency of a set of statements
+    fn verify_statements(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationResult>;
+}
+
+#[cfg(feature = "z3")]
+impl Backend for crate::CoherenceVerifier<'_> {
+    fn name(&self) -> &'static str {
+        "z3"
+    }
+
+    fn verify_statements(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationResult> {
+        crate::CoherenceVerifier::verify_statements(self, statements)
+    }
+}
+
+impl Backend for PropositionalVerifier {
+    fn name(&self) -> &'static str {
+        "sat"
+    }
+
+    fn verify_statements(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationResult> {
+        PropositionalVerifier::verify_statements(self, statements)
+    }
+}
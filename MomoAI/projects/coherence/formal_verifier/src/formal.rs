@@ -0,0 +1,256 @@
+/*!
+Formal statement syntax
+
+A compact notation for writing statements directly instead of through the English
+parser:
+
+```text
+~sorted(output) & reversed(output)
+forall x: human(x) -> mortal(x)
+exists x, y: parent(x, y) & ~(rich(x) | rich(y))
+```
+
+Atoms are predicates applied to comma-separated arguments, or bare propositions.
+`~` binds tightest, then `&`, then `|`; `->` binds loosest and groups to the right.
+A quantifier (`forall` / `exists`, or `∀` / `∃`) may open the statement and binds its
+variables over the whole formula. `¬`, `∧`, `∨` and `→` are accepted for `~`, `&`, `|`
+and `->`.
+*/
+
+use anyhow::{anyhow, bail};
+
+use crate::{Predicate, Quantifier, Statement, StatementBody};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    ForAll,
+    Exists,
+    Not,
+    And,
+    Or,
+    Implies,
+    LParen,
+    RParen,
+    Comma,
+    Colon,
+}
+
+/// Parse a statement written in the formal syntax. Errors give the character
+/// position of the offending token.
+pub fn parse_formal(text: &str, id: &str) -> anyhow::Result<Statement> {
+    let mut parser = Parser { tokens: tokenize(text)?, pos: 0, end: text.chars().count() };
+
+    let quantifier = parser.parse_quantifier()?;
+    let body = parser.parse_implication()?;
+    if let Some((token, position)) = parser.tokens.get(parser.pos) {
+        bail!("Unexpected {} at position {}", describe(token), position);
+    }
+
+    // A conjunction of atoms is an ordinary list of predicates
+    let (predicates, body) = match body {
+        StatementBody::Atom(predicate) => (vec![predicate], None),
+        StatementBody::And(parts) if parts.iter().all(|part| matches!(part, StatementBody::Atom(_))) => {
+            let predicates = parts
+                .into_iter()
+                .filter_map(|part| match part {
+                    StatementBody::Atom(predicate) => Some(predicate),
+                    _ => None,
+                })
+                .collect();
+            (predicates, None)
+        }
+        body => (Vec::new(), Some(body)),
+    };
+
+    Ok(Statement {
+        id: id.to_string(),
+        text: text.to_string(),
+        predicates,
+        body,
+        quantifier,
+        weight: None,
+    })
+}
+
+/// Tokens paired with the character position they start at
+fn tokenize(text: &str) -> anyhow::Result<Vec<(Token, usize)>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().enumerate().peekable();
+
+    while let Some(&(position, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut ident = String::new();
+            while let Some(&(_, d)) = chars.peek().filter(|(_, d)| d.is_alphanumeric() || *d == '_') {
+                ident.push(d);
+                chars.next();
+            }
+            let token = match ident.as_str() {
+                "forall" => Token::ForAll,
+                "exists" => Token::Exists,
+                _ => Token::Ident(ident),
+            };
+            tokens.push((token, position));
+        } else {
+            chars.next();
+            let token = match c {
+                '~' | '¬' => Token::Not,
+                '&' | '∧' => Token::And,
+                '|' | '∨' => Token::Or,
+                '→' => Token::Implies,
+                '-' if chars.next_if(|&(_, d)| d == '>').is_some() => Token::Implies,
+                '∀' => Token::ForAll,
+                '∃' => Token::Exists,
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                ',' => Token::Comma,
+                ':' => Token::Colon,
+                _ => bail!("Unexpected character '{}' at position {}", c, position),
+            };
+            tokens.push((token, position));
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Ident(name) => format!("'{}'", name),
+        Token::ForAll => "'forall'".to_string(),
+        Token::Exists => "'exists'".to_string(),
+        Token::Not => "'~'".to_string(),
+        Token::And => "'&'".to_string(),
+        Token::Or => "'|'".to_string(),
+        Token::Implies => "'->'".to_string(),
+        Token::LParen => "'('".to_string(),
+        Token::RParen => "')'".to_string(),
+        Token::Comma => "','".to_string(),
+        Token::Colon => "':'".to_string(),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Position reported for a missing token at the end of the input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn eat(&mut self, expected: &Token) -> bool {
+        let found = self.peek() == Some(expected);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: &Token) -> anyhow::Result<()> {
+        if self.eat(expected) {
+            return Ok(());
+        }
+        Err(self.error(&format!("Expected {}", describe(expected))))
+    }
+
+    /// "<what>, found <token> at position <n>" for the current token
+    fn error(&self, what: &str) -> anyhow::Error {
+        match self.tokens.get(self.pos) {
+            Some((token, position)) => anyhow!("{}, found {} at position {}", what, describe(token), position),
+            None => anyhow!("{}, found end of input at position {}", what, self.end),
+        }
+    }
+
+    fn ident(&mut self, what: &str) -> anyhow::Result<String> {
+        match self.peek() {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(self.error(&format!("Expected {}", what))),
+        }
+    }
+
+    /// `forall x, y:` / `exists x:` before the formula
+    fn parse_quantifier(&mut self) -> anyhow::Result<Option<Quantifier>> {
+        let universal = match self.peek() {
+            Some(Token::ForAll) => true,
+            Some(Token::Exists) => false,
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+
+        let mut variables = vec![self.ident("a variable")?];
+        while self.eat(&Token::Comma) {
+            variables.push(self.ident("a variable")?);
+        }
+        self.expect(&Token::Colon)?;
+
+        Ok(Some(if universal {
+            Quantifier::ForAll { variables, domain: Vec::new() }
+        } else {
+            Quantifier::Exists { variables, domain: Vec::new() }
+        }))
+    }
+
+    fn parse_implication(&mut self) -> anyhow::Result<StatementBody> {
+        let antecedent = self.parse_disjunction()?;
+        if !self.eat(&Token::Implies) {
+            return Ok(antecedent);
+        }
+        let consequent = self.parse_implication()?;
+        Ok(StatementBody::Implies(Box::new(antecedent), Box::new(consequent)))
+    }
+
+    fn parse_disjunction(&mut self) -> anyhow::Result<StatementBody> {
+        let mut parts = vec![self.parse_conjunction()?];
+        while self.eat(&Token::Or) {
+            parts.push(self.parse_conjunction()?);
+        }
+        Ok(if parts.len() == 1 { parts.pop().unwrap() } else { StatementBody::Or(parts) })
+    }
+
+    fn parse_conjunction(&mut self) -> anyhow::Result<StatementBody> {
+        let mut parts = vec![self.parse_unary()?];
+        while self.eat(&Token::And) {
+            parts.push(self.parse_unary()?);
+        }
+        Ok(if parts.len() == 1 { parts.pop().unwrap() } else { StatementBody::And(parts) })
+    }
+
+    fn parse_unary(&mut self) -> anyhow::Result<StatementBody> {
+        if self.eat(&Token::Not) {
+            // A negated atom keeps its predicate form
+            return Ok(match self.parse_unary()? {
+                StatementBody::Atom(predicate) => StatementBody::Atom(Predicate { negated: !predicate.negated, ..predicate }),
+                inner => StatementBody::Not(Box::new(inner)),
+            });
+        }
+        if self.eat(&Token::LParen) {
+            let inner = self.parse_implication()?;
+            self.expect(&Token::RParen)?;
+            return Ok(inner);
+        }
+        if matches!(self.peek(), Some(Token::ForAll | Token::Exists)) {
+            return Err(self.error("Quantifiers may only open a statement"));
+        }
+
+        let name = self.ident("a predicate")?;
+        let mut args = Vec::new();
+        if self.eat(&Token::LParen) {
+            args.push(self.ident("an argument")?);
+            while self.eat(&Token::Comma) {
+                args.push(self.ident("an argument")?);
+            }
+            self.expect(&Token::RParen)?;
+        }
+        Ok(StatementBody::Atom(Predicate { name, args, negated: false }))
+    }
+}
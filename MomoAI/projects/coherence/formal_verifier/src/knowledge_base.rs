@@ -0,0 +1,119 @@
+/*!
+Incrementally built knowledge base
+
+Statements are added one at a time to a single long-lived solver. Every addition is
+checked first, so the base is guaranteed to stay consistent: a statement that would
+make it unsatisfiable is rejected with the existing statements it conflicts with.
+*/
+
+use crate::{CoherenceVerifier, Statement};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use z3::ast::Bool;
+use z3::{Context, SatResult};
+
+/// Why a statement was rejected by the knowledge base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conflict {
+    /// Id of the rejected statement
+    pub statement: String,
+    /// Ids of the committed statements it conflicts with (from Z3's unsat core)
+    pub conflicting: Vec<String>,
+    pub reason: String,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.conflicting.is_empty() {
+            write!(f, "{}: {}", self.statement, self.reason)
+        } else {
+            write!(f, "{} conflicts with {}: {}", self.statement, self.conflicting.join(", "), self.reason)
+        }
+    }
+}
+
+impl std::error::Error for Conflict {}
+
+pub struct KnowledgeBase<'ctx> {
+    verifier: CoherenceVerifier<'ctx>,
+    statements: Vec<Statement>,
+    trackers: Vec<Bool<'ctx>>,
+    next_tracker: usize,
+}
+
+impl<'ctx> KnowledgeBase<'ctx> {
+    pub fn new(context: &'ctx Context) -> Self {
+        Self {
+            verifier: CoherenceVerifier::new(context),
+            statements: Vec::new(),
+            trackers: Vec::new(),
+            next_tracker: 0,
+        }
+    }
+
+    /// Statements committed so far, in insertion order
+    pub fn statements(&self) -> &[Statement] {
+        &self.statements
+    }
+
+    /// Check whether adding the statement would make the base inconsistent, without
+    /// changing the base either way
+    pub fn would_conflict(&mut self, statement: &Statement) -> anyhow::Result<Option<Conflict>> {
+        let (conflict, _) = self.check_candidate(statement)?;
+        Ok(conflict)
+    }
+
+    /// Add the statement if the base stays consistent. On conflict the base is left
+    /// exactly as it was and the conflict details are returned instead.
+    ///
+    /// The outer error is reserved for statements that cannot be translated to Z3.
+    pub fn try_add(&mut self, statement: Statement) -> anyhow::Result<Result<(), Conflict>> {
+        let (conflict, expr) = self.check_candidate(&statement)?;
+        if let Some(conflict) = conflict {
+            return Ok(Err(conflict));
+        }
+
+        let tracker = self.new_tracker();
+        self.verifier.solver.assert_and_track(&expr, &tracker);
+        self.trackers.push(tracker);
+        self.statements.push(statement);
+        Ok(Ok(()))
+    }
+
+    /// Assert the statement in a scratch scope and check it against the committed base
+    fn check_candidate(&mut self, statement: &Statement) -> anyhow::Result<(Option<Conflict>, Bool<'ctx>)> {
+        let expr = self.verifier.statement_to_z3(statement)?;
+        let candidate = self.new_tracker();
+
+        self.verifier.solver.push();
+        self.verifier.solver.assert_and_track(&expr, &candidate);
+        let conflict = match self.verifier.solver.check() {
+            SatResult::Sat => None,
+            SatResult::Unsat => {
+                let core = self.verifier.solver.get_unsat_core();
+                Some(Conflict {
+                    statement: statement.id.clone(),
+                    conflicting: self.trackers.iter()
+                        .zip(&self.statements)
+                        .filter(|(tracker, _)| core.contains(tracker))
+                        .map(|(_, committed)| committed.id.clone())
+                        .collect(),
+                    reason: "Z3 proved the statement is unsatisfiable together with the base".to_string(),
+                })
+            }
+            SatResult::Unknown => Some(Conflict {
+                statement: statement.id.clone(),
+                conflicting: vec![],
+                reason: "Z3 could not decide whether the statement is consistent with the base".to_string(),
+            }),
+        };
+        self.verifier.solver.pop(1);
+
+        Ok((conflict, expr))
+    }
+
+    fn new_tracker(&mut self) -> Bool<'ctx> {
+        self.next_tracker += 1;
+        Bool::new_const(self.verifier.context, format!("__kb_track_{}", self.next_tracker))
+    }
+}
@@ -5,369 +5,4286 @@ A mathematically rigorous coherence checker using Z3 theorem prover.
 This provides actual logical proofs rather than heuristic pattern matching.
 */
 
-use z3::ast::Bool;
-use z3::Config;
-use z3::{Context, Solver, SatResult};
+#[cfg(feature = "z3")]
+use z3::ast::{Ast, Bool, Dynamic, Int, Real};
+#[cfg(feature = "z3")]
+use z3::{Config, Context, FuncDecl, Optimize, Params, Solver, SatResult, Sort, StatisticsValue, Symbol};
+#[cfg(feature = "z3")]
+use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub mod arithmetic;
+pub mod backend;
+pub mod formal;
+#[cfg(feature = "z3")]
+pub mod knowledge_base;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "z3")]
+pub mod probability;
+pub mod parser;
+pub mod propositional;
+#[cfg(feature = "z3")]
+pub mod relations;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod repl;
+pub mod reporter;
+#[cfg(feature = "z3")]
+mod smtlib;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use arithmetic::{Comparison, Term};
+pub use backend::Backend;
+pub use formal::parse_formal;
+pub use parser::{ParserRule, StatementParser};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Statement {
     pub id: String,
     pub text: String,
     pub predicates: Vec<Predicate>,
+    /// Propositional structure beyond a conjunction, conjoined with `predicates`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<StatementBody>,
+    /// Binds variables in `predicates` and `body`; `None` for a ground statement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantifier: Option<Quantifier>,
+    /// Priority when statements conflict: `max_consistent_subset` prefers keeping
+    /// statements of higher total weight. `None` counts as 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Statement {
+    /// The statement's formula: its predicates ANDed together with its body
+    pub fn formula(&self) -> StatementBody {
+        let mut conjuncts: Vec<StatementBody> = self.predicates.iter().cloned().map(StatementBody::Atom).collect();
+        conjuncts.extend(self.body.clone());
+        if conjuncts.len() == 1 {
+            conjuncts.pop().unwrap()
+        } else {
+            StatementBody::And(conjuncts)
+        }
+    }
+
+    /// Whether no predicates were extracted from the statement, e.g. a sentence the
+    /// parser did not understand. Such a statement constrains nothing, so it is
+    /// consistent with anything and entailed by anything.
+    pub fn is_unparsed(&self) -> bool {
+        self.atoms().is_empty()
+    }
+
+    /// Whether both statements assert the same formula, whatever their ids and texts,
+    /// comparing predicates by their normalized form
+    pub fn same_formula(&self, other: &Statement) -> bool {
+        let predicates = |statement: &Statement| statement.predicates.iter().map(Predicate::normalized).collect::<Vec<_>>();
+        predicates(self) == predicates(other)
+            && self.body.as_ref().map(StatementBody::normalized) == other.body.as_ref().map(StatementBody::normalized)
+            && self.quantifier.as_ref().map(Quantifier::normalized) == other.quantifier.as_ref().map(Quantifier::normalized)
+    }
+
+    /// Every predicate occurring in the statement's formula, with comparisons written
+    /// as predicates named by their operator
+    pub fn atoms(&self) -> Vec<Predicate> {
+        let mut atoms = self.predicates.clone();
+        if let Some(body) = &self.body {
+            body.collect_atoms(&mut atoms);
+        }
+        atoms
+    }
+
+    /// Start building a statement with the given id, see [`StatementBuilder`]
+    pub fn builder(id: impl Into<String>) -> StatementBuilder {
+        StatementBuilder::new().id(id)
+    }
+}
+
+/// Fluent construction of a [`Statement`] without spelling out every field:
+///
+/// ```text
+/// Statement::builder("stmt1")
+///     .text("The output is sorted and not reversed")
+///     .predicate("sorted", ["output"])
+///     .not_predicate("reversed", ["output"])
+///     .build()
+/// ```
+///
+/// A builder given no id gets a fresh `auto_<n>` one on `build`, unique within the
+/// process.
+#[derive(Debug, Clone, Default)]
+pub struct StatementBuilder {
+    id: Option<String>,
+    text: String,
+    predicates: Vec<Predicate>,
+    body: Option<StatementBody>,
+    quantifier: Option<Quantifier>,
+    weight: Option<f64>,
+}
+
+impl StatementBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Add the atom `name(args)`
+    pub fn predicate<S: Into<String>>(self, name: impl Into<String>, args: impl IntoIterator<Item = S>) -> Self {
+        self.atom(name, args, false)
+    }
+
+    /// Add the negated atom `¬name(args)`
+    pub fn not_predicate<S: Into<String>>(self, name: impl Into<String>, args: impl IntoIterator<Item = S>) -> Self {
+        self.atom(name, args, true)
+    }
+
+    fn atom<S: Into<String>>(mut self, name: impl Into<String>, args: impl IntoIterator<Item = S>, negated: bool) -> Self {
+        self.predicates.push(Predicate {
+            name: name.into(),
+            args: args.into_iter().map(Into::into).collect(),
+            negated,
+        });
+        self
+    }
+
+    pub fn body(mut self, body: StatementBody) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn quantifier(mut self, quantifier: Quantifier) -> Self {
+        self.quantifier = Some(quantifier);
+        self
+    }
+
+    pub fn weight(mut self, weight: f64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn build(self) -> Statement {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Statement {
+            id: self
+                .id
+                .unwrap_or_else(|| format!("auto_{}", NEXT_ID.fetch_add(1, Ordering::Relaxed))),
+            text: self.text,
+            predicates: self.predicates,
+            body: self.body,
+            quantifier: self.quantifier,
+            weight: self.weight,
+        }
+    }
+}
+
+/// Propositional formula over predicates
+///
+/// ```json
+/// {"implies": [{"atom": {"name": "p", "args": ["x"], "negated": false}},
+///              {"atom": {"name": "q", "args": ["x"], "negated": false}}]}
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementBody {
+    /// A predicate, negated if its `negated` flag is set
+    Atom(Predicate),
+    /// Arithmetic constraint between linear terms over the configured numeric sort,
+    /// e.g. `{"comparison": {"lhs": "x", "op": ">=", "rhs": "0"}}`
+    Comparison { lhs: Term, op: Comparison, rhs: Term },
+    Not(Box<StatementBody>),
+    /// All parts hold; the empty conjunction is true
+    And(Vec<StatementBody>),
+    /// Some part holds; the empty disjunction is false
+    Or(Vec<StatementBody>),
+    Implies(Box<StatementBody>, Box<StatementBody>),
+    /// Exactly one part holds: some part holds and no two do at once. One part is
+    /// just that part; no parts is false.
+    #[serde(rename = "exactly_one")]
+    ExactlyOne(Vec<StatementBody>),
+}
+
+impl StatementBody {
+    /// The body with every predicate normalized
+    fn normalized(&self) -> StatementBody {
+        let all = |parts: &[StatementBody]| parts.iter().map(StatementBody::normalized).collect();
+        match self {
+            StatementBody::Atom(predicate) => StatementBody::Atom(predicate.normalized()),
+            StatementBody::Comparison { .. } => self.clone(),
+            StatementBody::Not(inner) => StatementBody::Not(Box::new(inner.normalized())),
+            StatementBody::And(parts) => StatementBody::And(all(parts)),
+            StatementBody::Or(parts) => StatementBody::Or(all(parts)),
+            StatementBody::Implies(premise, conclusion) => {
+                StatementBody::Implies(Box::new(premise.normalized()), Box::new(conclusion.normalized()))
+            }
+            StatementBody::ExactlyOne(parts) => StatementBody::ExactlyOne(all(parts)),
+        }
+    }
+
+    fn collect_atoms(&self, atoms: &mut Vec<Predicate>) {
+        match self {
+            StatementBody::Atom(predicate) => atoms.push(predicate.clone()),
+            StatementBody::Comparison { lhs, op, rhs } => atoms.push(Predicate {
+                name: op.symbol().to_string(),
+                args: vec![lhs.to_string(), rhs.to_string()],
+                negated: false,
+            }),
+            StatementBody::Not(inner) => inner.collect_atoms(atoms),
+            StatementBody::And(parts) | StatementBody::Or(parts) | StatementBody::ExactlyOne(parts) => {
+                for part in parts {
+                    part.collect_atoms(atoms);
+                }
+            }
+            StatementBody::Implies(antecedent, consequent) => {
+                antecedent.collect_atoms(atoms);
+                consequent.collect_atoms(atoms);
+            }
+        }
+    }
+}
+
+/// Quantifier over the object sort, binding predicate arguments named by its variables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Quantifier {
+    /// `∀ variables: domain → predicates`, e.g. "All humans are mortal" is
+    /// `∀x: human(x) → mortal(x)`
+    ForAll {
+        variables: Vec<String>,
+        /// Predicates restricting the range of the variables; empty ranges over
+        /// every object
+        #[serde(default)]
+        domain: Vec<Predicate>,
+    },
+    /// `∃ variables: domain ∧ predicates`, e.g. "Some humans are not mortal" is
+    /// `∃x: human(x) ∧ ¬mortal(x)`. Z3 sorts are never empty, but an empty domain
+    /// predicate is fine: a universal over it holds vacuously, while an existential
+    /// asserts that it has a member.
+    Exists {
+        variables: Vec<String>,
+        #[serde(default)]
+        domain: Vec<Predicate>,
+    },
+}
+
+impl Quantifier {
+    pub fn variables(&self) -> &[String] {
+        match self {
+            Quantifier::ForAll { variables, .. } | Quantifier::Exists { variables, .. } => variables,
+        }
+    }
+
+    /// Predicates restricting the range of the variables
+    pub fn domain(&self) -> &[Predicate] {
+        match self {
+            Quantifier::ForAll { domain, .. } | Quantifier::Exists { domain, .. } => domain,
+        }
+    }
+
+    /// The quantifier with its variables and domain predicates normalized
+    fn normalized(&self) -> Quantifier {
+        let variables = self.variables().iter().map(|variable| normalize_symbol(variable)).collect();
+        let domain = self.domain().iter().map(Predicate::normalized).collect();
+        match self {
+            Quantifier::ForAll { .. } => Quantifier::ForAll { variables, domain },
+            Quantifier::Exists { .. } => Quantifier::Exists { variables, domain },
+        }
+    }
+}
+
+/// An atom `name(args)`. Names and arguments are compared after normalization, see
+/// [`normalize_symbol`]: `Mortal( Socrates )` and `mortal(socrates)` are the same atom.
+/// Comparisons (`<`, `>=`, ...) are the exception: their arguments are arithmetic
+/// terms, whose variables keep their case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Predicate {
     pub name: String,
     pub args: Vec<String>,
     pub negated: bool,
 }
 
+impl Predicate {
+    /// The predicate with its name and arguments normalized; comparisons are returned
+    /// unchanged
+    pub fn normalized(&self) -> Predicate {
+        if Comparison::from_name(&self.name).is_some() {
+            return self.clone();
+        }
+        Predicate {
+            name: normalize_symbol(&self.name),
+            args: self.args.iter().map(|arg| normalize_symbol(arg)).collect(),
+            negated: self.negated,
+        }
+    }
+}
+
+/// Canonical form of a predicate name, argument or quantified variable: trimmed,
+/// lowercased, with internal runs of whitespace collapsed to one space. This matches
+/// how `parse_statement` lowercases its input, so hand-built statements compare the
+/// same way as parsed ones.
+pub fn normalize_symbol(symbol: &str) -> String {
+    symbol.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Built-in predicate over object terms, named `eq` or `distinct`, which Z3 reasons
+/// about directly instead of treating as an uninterpreted function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectRelation {
+    /// `eq(a, b, ...)`: all arguments name the same object
+    Eq,
+    /// `distinct(a, b, ...)`: the arguments name pairwise different objects
+    Distinct,
+}
+
+impl ObjectRelation {
+    /// Recognise a predicate name as a built-in object relation
+    pub fn from_name(name: &str) -> Option<Self> {
+        match normalize_symbol(name).as_str() {
+            "eq" => Some(ObjectRelation::Eq),
+            "distinct" => Some(ObjectRelation::Distinct),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationResult {
-    pub is_consistent: bool,
+    /// Consistent, inconsistent, or unknown when Z3 could not decide (which says
+    /// nothing about consistency)
+    pub status: VerificationStatus,
     pub proof: Option<String>,
     pub contradictions: Vec<Contradiction>,
+    /// Graded consistency: 1.0 for consistent statements, the fraction of statements
+    /// kept by the maximum consistent subset for inconsistent ones, 0.0 when unknown
+    pub confidence: f64,
+    /// For consistent statements: each statement id with the truth value of its
+    /// predicates under the satisfying assignment Z3 found
+    pub per_statement_witness: Option<Vec<StatementWitness>>,
+    /// Truth value of every named predicate under the model Z3 found: a satisfying
+    /// assignment for consistent statements, a counterexample for invalid reasoning
+    #[serde(default)]
+    pub model: Option<HashMap<String, bool>>,
+    /// The total deadline passed before the analysis finished, so the verdict may be
+    /// unknown or the contradictions incomplete
+    #[serde(default)]
+    pub truncated: bool,
+    /// Why Z3 returned unknown; `None` unless the verdict is unknown
+    #[serde(default)]
+    pub unknown_reason: Option<UnknownReason>,
+    /// Each statement id, in input order, with whether the statement is true or false
+    /// on its own, whatever the other statements say. `None` for ordinary statements
+    /// and those left undecided.
+    #[serde(default)]
+    pub statement_triviality: Vec<(String, Option<Triviality>)>,
+    /// Ids of the statements from which no predicates were extracted, which the
+    /// verdict says nothing about
+    #[serde(default)]
+    pub unparsed_statements: Vec<String>,
+}
+
+/// A statement whose truth does not depend on anything else
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Triviality {
+    /// True under every assignment, e.g. `P | ~P`; it constrains nothing
+    Tautology,
+    /// False under every assignment, e.g. `P & ~P`; it contradicts itself
+    Contradiction,
+}
+
+/// Why Z3 could not decide a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnknownReason {
+    /// The solver timeout or the total deadline ran out
+    Timeout,
+    /// Z3 gave up without running out of time, e.g. on nonlinear arithmetic
+    Incomplete,
+    /// A statement uses constructs the configured [`VerifyOptions::logic`] excludes,
+    /// such as arithmetic under `QF_UF`
+    OutsideLogic,
+}
+
+impl VerificationResult {
+    /// Whether the statements were proved consistent; `false` for unknown as well
+    #[deprecated(note = "use `status`, which distinguishes unknown from inconsistent")]
+    pub fn is_consistent(&self) -> bool {
+        matches!(self.status, VerificationStatus::Consistent)
+    }
+
+    /// Whether the statement with this id is a tautology or contradicts itself
+    pub fn triviality(&self, id: &str) -> Option<Triviality> {
+        self.statement_triviality.iter().find(|(statement, _)| statement == id).and_then(|(_, triviality)| *triviality)
+    }
+}
+
+/// Outcome of checking whether premises entail a conclusion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReasoningResult {
+    /// Z3 proved the premises entail the conclusion
+    pub is_valid: bool,
+    /// For invalid reasoning: truth values of the named predicates in a world where
+    /// the premises hold and the conclusion does not
+    pub counterexample: Option<HashMap<String, bool>>,
+    pub proof: Option<String>,
     pub confidence: f64,
+    /// The total deadline passed before Z3 decided
+    #[serde(default)]
+    pub truncated: bool,
+    /// Why Z3 returned unknown; `None` unless validity is undetermined
+    #[serde(default)]
+    pub unknown_reason: Option<UnknownReason>,
+    /// Ids of the premises and conclusion from which no predicates were extracted;
+    /// a conclusion that says nothing follows from anything
+    #[serde(default)]
+    pub unparsed_statements: Vec<String>,
 }
 
+/// How a new statement set relates to an old one, by what each entails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strength {
+    /// Each set entails the other
+    Equivalent,
+    /// The new set entails the old one but not the reverse: it says more
+    Stronger,
+    /// The old set entails the new one but not the reverse: it says less
+    Weaker,
+    /// Neither set entails the other
+    Incomparable,
+    /// Z3 could not decide at least one direction
+    Unknown,
+}
+
+/// Outcome of comparing two statement sets with [`CoherenceVerifier::compare`]. A
+/// direction that fails carries a counterexample: a world where its premises hold
+/// and the other set does not.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquivalenceResult {
+    pub strength: Strength,
+    /// Whether the old statements entail the new ones
+    pub old_entails_new: ReasoningResult,
+    /// Whether the new statements entail the old ones
+    pub new_entails_old: ReasoningResult,
+}
+
+/// A statement id with the truth value of each of its predicates in a model
+pub type StatementWitness = (String, Vec<(String, bool)>);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Contradiction {
     pub statement1: String,
     pub statement2: String,
+    /// Ids of every statement in the conflicting set, in input order
+    #[serde(default)]
+    pub statements: Vec<String>,
     pub reason: String,
     pub formal_proof: String,
 }
 
+/// Outcome of a satisfiability check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationStatus {
+    Consistent,
+    Inconsistent,
+    /// Z3 could not decide, e.g. on timeout
+    Unknown,
+}
+
+/// Outcome of [`CoherenceVerifier::verify_with_escalation`]: the result of the last
+/// stage tried, and which stage it was
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationResult {
+    pub result: VerificationResult,
+    /// Index into the schedule of the stage that reached a verdict; `None` when every
+    /// stage came back unknown
+    pub stage: Option<usize>,
+    /// Wall-clock time of all stages together
+    pub elapsed_ms: f64,
+}
+
+/// Outcome of [`CoherenceVerifier::find_all_muses`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MusEnumeration {
+    /// Minimal inconsistent subsets as statement IDs in input order, sorted
+    pub muses: Vec<Vec<String>>,
+    /// A check came back unknown or the total deadline passed, so more MUSes may exist
+    pub truncated: bool,
+}
+
+/// Everything learned about one verification run, bundled into a single serializable
+/// value for rich consumers (JSON output, editor integrations)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub status: VerificationStatus,
+    pub proof: Option<String>,
+    pub confidence: f64,
+    /// Conflicting statement pairs or chains; empty unless inconsistent
+    pub contradictions: Vec<Contradiction>,
+    /// Ids of the statements in the unsat core Z3 reported (not necessarily minimal)
+    pub unsat_core: Vec<String>,
+    /// Statement ids grouped into connected components of the contradiction graph
+    pub clusters: Vec<Vec<String>>,
+    /// Z3 solver statistics from the main satisfiability check
+    pub statistics: BTreeMap<String, f64>,
+    /// Ids of statements from which no predicates were extracted
+    pub unparsed_statements: Vec<String>,
+    /// Wall-clock time of the whole run, including contradiction analysis
+    pub elapsed_ms: f64,
+    /// The total deadline passed before the analysis finished
+    #[serde(default)]
+    pub truncated: bool,
+    /// Why Z3 returned unknown; `None` unless the status is unknown
+    #[serde(default)]
+    pub unknown_reason: Option<UnknownReason>,
+}
+
+/// How hard Z3 worked on one check, from the solver's statistics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SolverStats {
+    pub conflicts: u64,
+    pub decisions: u64,
+    pub propagations: u64,
+    /// Wall-clock seconds the check took
+    pub time: f64,
+    /// Peak memory of the Z3 process, in megabytes
+    pub max_memory: f64,
+    /// Every statistic Z3 reported, by Z3's name; which ones appear depends on the
+    /// theories the problem used
+    pub entries: BTreeMap<String, f64>,
+}
+
+#[cfg(feature = "z3")]
+impl SolverStats {
+    fn new(entries: BTreeMap<String, f64>, check_time: Duration) -> Self {
+        // The SAT core reports its counters under a `sat` prefix when it decides the
+        // problem instead of the SMT core
+        let count = |name: &str| entries.get(name).or_else(|| entries.get(&format!("sat {}", name))).copied().unwrap_or(0.0) as u64;
+        Self {
+            conflicts: count("conflicts"),
+            decisions: count("decisions"),
+            propagations: count("propagations"),
+            time: check_time.as_secs_f64(),
+            max_memory: entries.get("max memory").copied().unwrap_or(0.0),
+            entries,
+        }
+    }
+}
+
+/// Z3 sort used for the variables of arithmetic predicates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumericSort {
+    /// Integers, matching Python `int` semantics (e.g. nothing lies strictly between 0 and 1)
+    #[default]
+    Int,
+    /// Reals, for claims about continuous quantities
+    Real,
+}
+
+impl std::str::FromStr for NumericSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "int" => Ok(NumericSort::Int),
+            "real" => Ok(NumericSort::Real),
+            other => Err(format!("unknown numeric sort '{}' (expected 'int' or 'real')", other)),
+        }
+    }
+}
+
+/// Options controlling how statements are encoded for Z3
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyOptions {
+    pub numeric_sort: NumericSort,
+    /// Wall-clock deadline for a whole verification call, contradiction analysis
+    /// included. Solver checks are bounded by the time left, and analysis still
+    /// pending when it passes is skipped and the result marked truncated.
+    #[serde(skip)]
+    pub total_deadline: Option<Instant>,
+    /// Limit for each individual Z3 check; a check that hits it is reported as
+    /// unknown with [`UnknownReason::Timeout`]
+    pub timeout: Option<Duration>,
+    /// Argument sorts of predicates by name; arguments of predicates not listed are
+    /// objects
+    #[serde(default)]
+    pub signatures: BTreeMap<String, Vec<ArgType>>,
+    /// Put Z3's proof term, as an S-expression, in the `proof` of unsat results instead
+    /// of a summary. Proof generation slows solving and has to be enabled on the
+    /// context, see [`VerifyOptions::config`].
+    #[serde(default)]
+    pub proofs: bool,
+    /// Keep predicate atoms between verification calls, so re-verifying an
+    /// overlapping statement set reuses them instead of creating them again; see
+    /// [`CoherenceVerifier::clear_cache`]
+    #[serde(default)]
+    pub cache_predicates: bool,
+    /// Seed for Z3's random choices (`random_seed` and `sat.random_seed`), so the
+    /// same input gets the same model and counterexample on every run. Only holds
+    /// for a fixed Z3 version; `None` keeps Z3's default seed.
+    #[serde(default)]
+    pub seed: Option<u32>,
+    /// SMT-LIB logic to tell Z3 up front, e.g. `QF_UF` for propositional and
+    /// uninterpreted-function problems, so it can pick specialized tactics. `None`
+    /// (the default) uses the general solver, which also covers arithmetic and
+    /// quantifiers. Verifying a statement that needs more than the logic allows is an
+    /// error; a check Z3 itself finds outside the logic, e.g. from SMT-LIB input, comes
+    /// back unknown with [`UnknownReason::OutsideLogic`].
+    #[serde(default)]
+    pub logic: Option<String>,
+}
+
+#[cfg(feature = "z3")]
+impl VerifyOptions {
+    /// Z3 configuration for contexts these options are used with
+    pub fn config(&self) -> Config {
+        let mut cfg = Config::new();
+        if self.proofs {
+            cfg.set_proof_generation(true);
+        }
+        cfg
+    }
+}
+
+/// Lookups of the persistent predicate cache, see [`VerifyOptions::cache_predicates`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Atoms reused from an earlier verification call
+    pub hits: usize,
+    /// Atoms created because no earlier call had one
+    pub misses: usize,
+}
+
+/// Sort of a predicate argument
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgType {
+    /// Constant of the uninterpreted object sort, named by the argument
+    #[default]
+    Object,
+    /// Linear integer term, e.g. `x+1`
+    Int,
+    /// Linear real term
+    Real,
+    /// `true`, `false`, or a boolean constant named by the argument
+    Bool,
+}
+
+impl std::str::FromStr for ArgType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "object" => Ok(ArgType::Object),
+            "int" => Ok(ArgType::Int),
+            "real" => Ok(ArgType::Real),
+            "bool" => Ok(ArgType::Bool),
+            other => Err(format!("unknown argument type '{}' (expected 'object', 'int', 'real' or 'bool')", other)),
+        }
+    }
+}
+
+#[cfg(feature = "z3")]
 pub struct CoherenceVerifier<'ctx> {
     context: &'ctx Context,
     solver: Solver<'ctx>,
+    options: VerifyOptions,
+    /// Uninterpreted sort of the things predicates talk about, such as `socrates`
+    object_sort: Sort<'ctx>,
+    /// Uninterpreted function to `Bool` per predicate name and argument sorts
+    functions: HashMap<(String, Vec<ArgType>), FuncDecl<'ctx>>,
+    /// Constant of the object sort per predicate argument, so `human(socrates)` and
+    /// `mortal(socrates)` share the `socrates` term
+    objects: HashMap<String, Dynamic<'ctx>>,
+    /// Z3 atom per predicate key. Sharing it between the main solver and the scratch
+    /// solvers of contradiction analysis is safe: it is only a cache of function applications, which
+    /// Z3 identifies by declaration and arguments within the context, and carries no assertions.
     predicates: HashMap<String, Bool<'ctx>>,
+    int_vars: HashMap<String, Int<'ctx>>,
+    real_vars: HashMap<String, Real<'ctx>>,
+    /// Ground atoms kept across `reset` when predicate caching is on, keyed by the atom
+    /// and its argument sorts, which the signatures of a later call may change.
+    /// Declarations and object constants are identified by name and sort within the
+    /// context, so an atom built for an earlier problem is the same term a fresh build
+    /// would give.
+    atom_cache: HashMap<(String, Vec<ArgType>), Bool<'ctx>>,
+    cache_stats: CacheStats,
+    /// Background constraints that hold in every check of the current problem, such
+    /// as declared relation properties; cleared by `reset`
+    axioms: Vec<Bool<'ctx>>,
+    /// Statements asserted by `push_statements`, one entry per open solver scope
+    scopes: Vec<Vec<Statement>>,
+    /// Statistics of the most recent main check, see `last_statistics`
+    last_statistics: SolverStats,
 }
 
+#[cfg(feature = "z3")]
 impl<'ctx> CoherenceVerifier<'ctx> {
     pub fn new(context: &'ctx Context) -> Self {
-        let solver = Solver::new(context);
+        Self::with_options(context, VerifyOptions::default())
+    }
+
+    /// Verifier with the given options. A `logic` Z3 does not know falls back to the
+    /// general solver; see `try_with_options` to reject it instead.
+    pub fn with_options(context: &'ctx Context, options: VerifyOptions) -> Self {
+        let solver = new_solver(context, options.logic.as_deref());
         Self {
             context,
             solver,
+            options,
+            object_sort: Sort::uninterpreted(context, Symbol::String("Object".to_string())),
+            functions: HashMap::new(),
+            objects: HashMap::new(),
             predicates: HashMap::new(),
+            int_vars: HashMap::new(),
+            real_vars: HashMap::new(),
+            atom_cache: HashMap::new(),
+            cache_stats: CacheStats::default(),
+            last_statistics: SolverStats::default(),
+            axioms: Vec::new(),
+            scopes: Vec::new(),
         }
     }
 
-    /// Verify logical consistency of a set of statements
-    pub fn verify_statements(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationResult> {
-        // Clear previous state
+    /// Like `with_options`, but an error if Z3 does not know the configured `logic`
+    pub fn try_with_options(context: &'ctx Context, options: VerifyOptions) -> anyhow::Result<Self> {
+        if let Some(logic) = &options.logic {
+            if Solver::new_for_logic(context, logic.as_str()).is_none() {
+                anyhow::bail!("Z3 does not support the logic '{}' (logic names are case-sensitive, e.g. QF_UF or QF_LIA)", logic);
+            }
+        }
+        Ok(Self::with_options(context, options))
+    }
+
+    /// Verifier whose Z3 checks each give up after `timeout`
+    pub fn with_timeout(context: &'ctx Context, timeout: Duration) -> Self {
+        Self::with_options(context, VerifyOptions { timeout: Some(timeout), ..Default::default() })
+    }
+
+    /// Limit each subsequent Z3 check to `timeout`
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.options.timeout = Some(timeout);
+        self.apply_params(&self.solver);
+    }
+
+    pub fn options(&self) -> &VerifyOptions {
+        &self.options
+    }
+
+    /// Drop the predicate atoms kept between calls and zero the cache counters
+    pub fn clear_cache(&mut self) {
+        self.atom_cache.clear();
+        self.cache_stats = CacheStats::default();
+    }
+
+    /// Hits and misses of the persistent predicate cache since it was last cleared
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache_stats
+    }
+
+    /// Z3's statistics for the main check of the most recent verification, reasoning
+    /// or pushed-statement check, such as conflicts and decisions; zero before any
+    /// check. The checks of contradiction analysis are not included.
+    pub fn last_statistics(&self) -> &SolverStats {
+        &self.last_statistics
+    }
+
+    /// Clear the solver and all predicate/variable mappings before a new problem. The
+    /// persistent predicate cache, if enabled, survives.
+    fn reset(&mut self) {
         self.solver.reset();
+        self.functions.clear();
+        self.objects.clear();
         self.predicates.clear();
+        self.int_vars.clear();
+        self.real_vars.clear();
+        self.axioms.clear();
+        self.scopes.clear();
+        self.apply_params(&self.solver);
+    }
+
+    /// Whether the total deadline, if any, has passed
+    fn deadline_passed(&self) -> bool {
+        self.options.total_deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Bound the solver's checks by the per-check timeout and the time left until the
+    /// total deadline, whichever is shorter, and seed its random choices if configured
+    fn apply_params(&self, solver: &Solver<'ctx>) {
+        let timeout_ms = match self.check_limit() {
+            Some(limit) => limit.as_millis().clamp(1, u32::MAX as u128) as u32,
+            // Z3's default: no timeout
+            None => u32::MAX,
+        };
+        let mut params = Params::new(self.context);
+        params.set_u32("timeout", timeout_ms);
+        if let Some(seed) = self.options.seed {
+            params.set_u32("random_seed", seed);
+            params.set_u32("sat.random_seed", seed);
+        }
+        solver.set_params(&params);
+    }
+
+    /// Time one check may take: the per-check timeout or the time left until the total
+    /// deadline, whichever is shorter
+    fn check_limit(&self) -> Option<Duration> {
+        let remaining = self.options.total_deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match (self.options.timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        }
+    }
+
+    /// Check an optimizer within [`Self::check_limit`]. The `z3` crate cannot set
+    /// parameters on an `Optimize`, so the context is interrupted once the limit passes.
+    fn check_optimize(&self, optimize: &Optimize<'ctx>) -> SatResult {
+        let Some(limit) = self.check_limit() else {
+            return optimize.check(&[]);
+        };
+        let handle = self.context.handle();
+        let (done, finished) = std::sync::mpsc::channel::<()>();
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                if finished.recv_timeout(limit) == Err(std::sync::mpsc::RecvTimeoutError::Timeout) {
+                    handle.interrupt();
+                }
+            });
+            let result = optimize.check(&[]);
+            drop(done);
+            result
+        })
+    }
+
+    /// Classify the last unknown result of the main solver, given how long the check ran.
+    /// Z3 does not always say "timeout" when it is interrupted, so a check that used up
+    /// its whole time limit also counts as a timeout.
+    fn unknown_reason(&self, check_time: Duration) -> UnknownReason {
+        let reason = self.solver.get_reason_unknown().unwrap_or_default();
+        let out_of_time = self.options.timeout.is_some_and(|timeout| check_time >= timeout);
+        if reason.contains("timeout") || reason.contains("canceled") || out_of_time || self.deadline_passed() {
+            UnknownReason::Timeout
+        } else if reason.contains("logic does not support") {
+            UnknownReason::OutsideLogic
+        } else {
+            UnknownReason::Incomplete
+        }
+    }
+
+    /// Proof of the last unsat result of the main solver: Z3's proof term if proofs
+    /// are enabled, otherwise the summary
+    fn unsat_proof(&self, summary: &str) -> Option<String> {
+        if self.options.proofs {
+            self.solver.get_proof().map(|proof| Dynamic::from_ast(&proof).to_string())
+        } else {
+            Some(summary.to_string())
+        }
+    }
+
+    /// Verify logical consistency of a set of statements. A statement asserting the
+    /// same formula as an earlier one is left out, so results only name the first
+    /// occurrence and nothing is reported as contradicting its own duplicate.
+    pub fn verify_statements(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationResult> {
+        // Clear previous state
+        self.reset();
+        self.push_statements(&distinct_statements(statements))?;
+        self.check_pushed()
+    }
+
+    /// Verify the statements under each per-check timeout of `schedule` in turn,
+    /// stopping at the first consistent or inconsistent verdict: a quick first stage
+    /// settles easy problems, and only hard ones pay for the longer stages. A stage
+    /// that is unknown for any reason but a timeout ends the escalation, since more
+    /// time would not help. The verifier's own timeout is restored afterwards.
+    pub fn verify_with_escalation(&mut self, statements: &[Statement], schedule: &[Duration]) -> anyhow::Result<EscalationResult> {
+        let start = Instant::now();
+        let previous = self.options.timeout;
+        let outcome = self.escalate(statements, schedule);
+        self.options.timeout = previous;
+        self.apply_params(&self.solver);
+        let (result, stage) = outcome?;
+        Ok(EscalationResult { result, stage, elapsed_ms: start.elapsed().as_secs_f64() * 1000.0 })
+    }
+
+    fn escalate(&mut self, statements: &[Statement], schedule: &[Duration]) -> anyhow::Result<(VerificationResult, Option<usize>)> {
+        let mut unknown = None;
+        for (stage, &timeout) in schedule.iter().enumerate() {
+            self.options.timeout = Some(timeout);
+            let result = self.verify_statements(statements)?;
+            if result.status != VerificationStatus::Unknown {
+                return Ok((result, Some(stage)));
+            }
+            let timed_out = result.unknown_reason == Some(UnknownReason::Timeout) && !self.deadline_passed();
+            unknown = Some(result);
+            if !timed_out {
+                break;
+            }
+        }
+        match unknown {
+            Some(result) => Ok((result, None)),
+            None => anyhow::bail!("Escalation schedule has no timeouts"),
+        }
+    }
 
-        // Convert statements to Z3 expressions and assert them
+    /// The problem `verify_statements` would hand Z3, as Z3's SMT-LIB2 dump of the
+    /// solver: declarations followed by one `(assert ...)` per statement. Nothing is
+    /// checked.
+    pub fn to_smtlib(&mut self, statements: &[Statement]) -> anyhow::Result<String> {
+        self.reset();
         for statement in statements {
-            let z3_expr = self.statement_to_z3(statement)?;
-            self.solver.assert(&z3_expr);
+            let expr = self.statement_to_z3(statement)?;
+            self.solver.assert(&expr);
+        }
+        Ok(self.solver.to_string())
+    }
+
+    /// Check an SMT-LIB2 script's assertions for consistency, like `verify_statements`.
+    /// The result names no statements: contradictions are left empty. A script Z3
+    /// cannot read is an error carrying Z3's message and position.
+    pub fn check_smtlib(&mut self, source: &str) -> anyhow::Result<VerificationResult> {
+        if let Some(error) = smtlib::syntax_error(source) {
+            anyhow::bail!("Malformed SMT-LIB2: {}", error);
+        }
+        self.reset();
+        self.solver.from_string(source);
+        self.check_tracked(&[], &[], &[])
+    }
+
+    /// Assert statements in a new solver scope on top of those already pushed, for
+    /// incremental what-if analysis. Z3 keeps what it learned about the lower scopes
+    /// across `pop`. `verify_statements` and `verify_reasoning` start from an
+    /// empty stack and discard pushed scopes.
+    pub fn push_statements(&mut self, statements: &[Statement]) -> anyhow::Result<()> {
+        let mut exprs = Vec::new();
+        for statement in statements {
+            exprs.push(self.statement_to_z3(statement)?);
+        }
+
+        // Track each statement by its position across all scopes so Z3 can name the
+        // ones in its unsat core
+        let offset: usize = self.scopes.iter().map(Vec::len).sum();
+        self.solver.push();
+        for (i, expr) in exprs.iter().enumerate() {
+            let tracker = Bool::new_const(self.context, format!("__track_{}", offset + i));
+            self.solver.assert_and_track(expr, &tracker);
+        }
+        self.scopes.push(statements.to_vec());
+        Ok(())
+    }
+
+    /// Drop the statements of the most recent `push_statements`
+    pub fn pop(&mut self) -> anyhow::Result<()> {
+        if self.scopes.pop().is_none() {
+            anyhow::bail!("No pushed statements to pop");
+        }
+        self.solver.pop(1);
+        Ok(())
+    }
+
+    /// Verify logical consistency of all currently pushed statements
+    pub fn check_pushed(&mut self) -> anyhow::Result<VerificationResult> {
+        let statements: Vec<Statement> = self.scopes.concat();
+        let trackers = trackers(self.context, statements.len());
+        self.check_tracked(&statements, &trackers, &[])
+    }
+
+    /// Add one statement to a live knowledge base in its own solver scope, so
+    /// `remove_last` can retract it again
+    pub fn add_statement(&mut self, statement: &Statement) -> anyhow::Result<()> {
+        self.push_statements(std::slice::from_ref(statement))
+    }
+
+    /// Retract the most recent `add_statement` (or `push_statements` scope),
+    /// returning the statements it added. With nothing added this is a no-op and
+    /// returns no statements.
+    pub fn remove_last(&mut self) -> Vec<Statement> {
+        match self.scopes.pop() {
+            Some(statements) => {
+                self.solver.pop(1);
+                statements
+            }
+            None => vec![],
+        }
+    }
+
+    /// The statements of every pushed scope, oldest first: the knowledge base built by
+    /// `add_statement`
+    pub fn pushed_statements(&self) -> Vec<Statement> {
+        self.scopes.concat()
+    }
+
+    /// Consistency of the knowledge base built by `add_statement`; the same check as
+    /// `check_pushed`
+    pub fn current_status(&mut self) -> anyhow::Result<VerificationResult> {
+        self.check_pushed()
+    }
+
+    /// Verify the statements together with hypotheses that hold for this one check.
+    /// Starts from an empty stack like `verify_statements`; see
+    /// `check_pushed_under_assumptions` for what-if queries against pushed statements.
+    pub fn check_under_assumptions(&mut self, statements: &[Statement], assumptions: &[Statement]) -> anyhow::Result<VerificationResult> {
+        self.reset();
+        self.push_statements(statements)?;
+        self.check_pushed_under_assumptions(assumptions)
+    }
+
+    /// Verify the currently pushed statements together with hypotheses that hold for
+    /// this one check. Each assumption is guarded by a literal passed to Z3's
+    /// `check_assumptions`, so retracting it is just leaving it out of the next call:
+    /// the pushed statements and what Z3 learned about them stay in place.
+    /// Contradictions name assumptions by their IDs like any other statement.
+    pub fn check_pushed_under_assumptions(&mut self, assumptions: &[Statement]) -> anyhow::Result<VerificationResult> {
+        let mut exprs = Vec::new();
+        for assumption in assumptions {
+            exprs.push(self.statement_to_z3(assumption)?);
         }
+        let literals: Vec<Bool<'ctx>> = (0..assumptions.len())
+            .map(|i| Bool::new_const(self.context, format!("__assume_{}", i)))
+            .collect();
+
+        let mut statements: Vec<Statement> = self.scopes.concat();
+        let mut trackers = trackers(self.context, statements.len());
+        statements.extend_from_slice(assumptions);
+        trackers.extend(literals.iter().cloned());
+
+        // The guards live in a scratch scope so the literal names can be reused
+        self.solver.push();
+        for (expr, literal) in exprs.iter().zip(&literals) {
+            self.solver.assert(&literal.implies(expr));
+        }
+        let result = self.check_tracked(&statements, &trackers, &literals);
+        self.solver.pop(1);
+        result
+    }
 
+    /// Check the solver under the given assumption literals and build the result;
+    /// `trackers` name `statements` in unsat cores
+    fn check_tracked(
+        &mut self,
+        statements: &[Statement],
+        trackers: &[Bool<'ctx>],
+        assumptions: &[Bool<'ctx>],
+    ) -> anyhow::Result<VerificationResult> {
         // Check satisfiability
-        let result = self.solver.check();
+        let started = Instant::now();
+        let result = self.solver.check_assumptions(assumptions);
+        let check_time = started.elapsed();
+        self.last_statistics = SolverStats::new(solver_statistics(&self.solver), check_time);
         
         match result {
             SatResult::Sat => {
                 // Statements are consistent
                 Ok(VerificationResult {
-                    is_consistent: true,
+                    status: VerificationStatus::Consistent,
                     proof: Some("Z3 found satisfying model".to_string()),
                     contradictions: vec![],
                     confidence: 1.0,
+                    per_statement_witness: self.witness(statements)?,
+                    model: self.model(),
+                    truncated: false,
+                    unknown_reason: None,
+                    statement_triviality: self.statement_triviality(statements)?,
+                    unparsed_statements: unparsed_ids(statements),
                 })
             }
             SatResult::Unsat => {
                 // Statements are inconsistent - find contradictions
-                let contradictions = self.find_contradictions(statements)?;
+                let proof = self.unsat_proof("Z3 proved unsatisfiability");
+                let core = core_indices(&self.solver, trackers);
+                let (contradictions, truncated) = self.find_contradictions(statements, core)?;
                 Ok(VerificationResult {
-                    is_consistent: false,
-                    proof: Some("Z3 proved unsatisfiability".to_string()),
+                    status: VerificationStatus::Inconsistent,
+                    proof,
                     contradictions,
-                    confidence: 1.0,
+                    confidence: self.retained_fraction(statements)?,
+                    per_statement_witness: None,
+                    model: None,
+                    truncated,
+                    unknown_reason: None,
+                    statement_triviality: self.statement_triviality(statements)?,
+                    unparsed_statements: unparsed_ids(statements),
                 })
             }
             SatResult::Unknown => {
                 // Z3 couldn't determine - timeout or complexity
                 Ok(VerificationResult {
-                    is_consistent: false,
+                    status: VerificationStatus::Unknown,
                     proof: None,
                     contradictions: vec![],
                     confidence: 0.0,
+                    per_statement_witness: None,
+                    model: None,
+                    truncated: self.deadline_passed(),
+                    unknown_reason: Some(self.unknown_reason(check_time)),
+                    // Checking each statement alone could take as long again
+                    statement_triviality: statements.iter().map(|statement| (statement.id.clone(), None)).collect(),
+                    unparsed_statements: unparsed_ids(statements),
                 })
             }
         }
     }
 
-    /// Convert a statement to Z3 boolean expression
-    fn statement_to_z3(&mut self, statement: &Statement) -> anyhow::Result<Bool<'ctx>> {
-        let mut conjuncts = Vec::new();
+    /// Check each statement on its own, against the background axioms only: it is a
+    /// contradiction if it is unsatisfiable and a tautology if its negation is.
+    /// Statements without predicates are not classified (they are reported as
+    /// unparsed instead), nor is anything once the total deadline has passed.
+    fn statement_triviality(&mut self, statements: &[Statement]) -> anyhow::Result<Vec<(String, Option<Triviality>)>> {
+        // Scratch solvers start empty; they share only constants with the main solver
+        let temp_solver = new_solver(self.context, self.options.logic.as_deref());
+        self.apply_params(&temp_solver);
+        for axiom in &self.axioms {
+            temp_solver.assert(axiom);
+        }
+        let unsatisfiable = |expr: &Bool<'ctx>| {
+            temp_solver.push();
+            temp_solver.assert(expr);
+            let result = temp_solver.check();
+            temp_solver.pop(1);
+            result == SatResult::Unsat
+        };
 
-        for predicate in &statement.predicates {
-            let pred_name = format!("{}({})", predicate.name, predicate.args.join(","));
-            
-            let z3_pred = if let Some(existing) = self.predicates.get(&pred_name) {
-                existing.clone()
+        let mut triviality = Vec::new();
+        for statement in statements {
+            let kind = if statement.atoms().is_empty() || self.deadline_passed() {
+                None
             } else {
-                let new_pred = Bool::new_const(self.context, pred_name.clone());
-                self.predicates.insert(pred_name, new_pred.clone());
-                new_pred
+                let expr = self.statement_to_z3(statement)?;
+                if unsatisfiable(&expr) {
+                    Some(Triviality::Contradiction)
+                } else if unsatisfiable(&expr.not()) {
+                    Some(Triviality::Tautology)
+                } else {
+                    None
+                }
             };
-
-            if predicate.negated {
-                conjuncts.push(z3_pred.not());
-            } else {
-                conjuncts.push(z3_pred);
-            }
+            triviality.push((statement.id.clone(), kind));
         }
+        Ok(triviality)
+    }
 
-        // Combine predicates with AND
-        if conjuncts.is_empty() {
-            Ok(Bool::from_bool(self.context, true))
-        } else if conjuncts.len() == 1 {
-            Ok(conjuncts.into_iter().next().unwrap())
-        } else {
-            let mut result = conjuncts[0].clone();
-            for conjunct in conjuncts.into_iter().skip(1) {
-                result = Bool::and(self.context, &[&result, &conjunct]);
-            }
-            Ok(result)
-        }
+    /// Verify statements under the given options and collect everything known about the
+    /// run into a [`VerificationReport`]. The verifier's own options are left unchanged.
+    pub fn verify_report(&mut self, statements: &[Statement], options: &VerifyOptions) -> anyhow::Result<VerificationReport> {
+        let previous = std::mem::replace(&mut self.options, options.clone());
+        let report = self.build_report(statements);
+        self.options = previous;
+        report
     }
 
-    /// Find specific contradictions between statements
-    fn find_contradictions(&mut self, statements: &[Statement]) -> anyhow::Result<Vec<Contradiction>> {
-        let mut contradictions = Vec::new();
+    fn build_report(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationReport> {
+        self.reset();
+        self.check_report(statements)
+    }
 
-        // Check each pair of statements for contradiction
-        for i in 0..statements.len() {
-            for j in (i + 1)..statements.len() {
-                if let Some(contradiction) = self.check_pair_contradiction(&statements[i], &statements[j])? {
-                    contradictions.push(contradiction);
-                }
-            }
+    /// Check the statements on top of the current axioms and build the report
+    fn check_report(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationReport> {
+        let start = Instant::now();
+        for axiom in &self.axioms {
+            self.solver.assert(axiom);
         }
 
-        Ok(contradictions)
-    }
+        // Track each statement so Z3 can name the ones in its unsat core
+        let trackers = trackers(self.context, statements.len());
+        for (statement, tracker) in statements.iter().zip(&trackers) {
+            let z3_expr = self.statement_to_z3(statement)?;
+            self.solver.assert_and_track(&z3_expr, tracker);
+        }
 
-    /// Check if two statements contradict each other
-    fn check_pair_contradiction(&mut self, stmt1: &Statement, stmt2: &Statement) -> anyhow::Result<Option<Contradiction>> {
-        // Create fresh solver for this check
-        let temp_solver = Solver::new(self.context);
-        
-        // Convert statements to Z3
-        let z3_stmt1 = self.statement_to_z3(stmt1)?;
-        let z3_stmt2 = self.statement_to_z3(stmt2)?;
-        
-        // Assert both statements
-        temp_solver.assert(&z3_stmt1);
-        temp_solver.assert(&z3_stmt2);
-        
-        // Check if they can both be true
-        match temp_solver.check() {
+        let started = Instant::now();
+        let result = self.solver.check();
+        let check_time = started.elapsed();
+        let statistics = solver_statistics(&self.solver);
+        self.last_statistics = SolverStats::new(statistics.clone(), check_time);
+
+        let mut truncated = false;
+        let mut unknown_reason = None;
+        let (status, proof, confidence, unsat_core, contradictions) = match result {
+            SatResult::Sat => (
+                VerificationStatus::Consistent,
+                Some("Z3 found satisfying model".to_string()),
+                1.0,
+                vec![],
+                vec![],
+            ),
             SatResult::Unsat => {
-                // They contradict each other
-                Ok(Some(Contradiction {
-                    statement1: stmt1.id.clone(),
-                    statement2: stmt2.id.clone(),
-                    reason: "Statements are mutually exclusive".to_string(),
-                    formal_proof: "Z3 proved (stmt1 ∧ stmt2) is unsatisfiable".to_string(),
-                }))
+                let proof = self.unsat_proof("Z3 proved unsatisfiability");
+                let core = core_indices(&self.solver, &trackers);
+                let unsat_core = core.iter().map(|&k| statements[k].id.clone()).collect();
+                let (contradictions, analysis_truncated) = self.find_contradictions(statements, core)?;
+                truncated = analysis_truncated;
+                (
+                    VerificationStatus::Inconsistent,
+                    proof,
+                    self.retained_fraction(statements)?,
+                    unsat_core,
+                    contradictions,
+                )
+            }
+            SatResult::Unknown => {
+                truncated = self.deadline_passed();
+                unknown_reason = Some(self.unknown_reason(check_time));
+                (VerificationStatus::Unknown, None, 0.0, vec![], vec![])
+            }
+        };
+
+        Ok(VerificationReport {
+            status,
+            proof,
+            confidence,
+            clusters: cluster_contradictions(statements, &contradictions),
+            contradictions,
+            unsat_core,
+            statistics,
+            unparsed_statements: unparsed_ids(statements),
+            elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+            truncated,
+            unknown_reason,
+        })
+    }
+
+    /// Reject a statement that needs more than the configured logic. Z3 falls back
+    /// to its general solver once checks are incremental, so it would otherwise
+    /// decide the statement anyway instead of reporting the mismatch.
+    fn check_logic(&self, statement: &Statement) -> anyhow::Result<()> {
+        let Some(logic) = self.options.logic.as_deref() else {
+            return Ok(());
+        };
+        if statement.quantifier.is_some() && logic.starts_with("QF_") {
+            anyhow::bail!("Statement '{}' is quantified, which the logic {} excludes", statement.id, logic);
+        }
+        let arithmetic_logic = logic == "ALL" || ["IA", "RA", "DL"].iter().any(|theory| logic.contains(theory));
+        let uses_arithmetic = statement.atoms().iter().any(|predicate| {
+            (Comparison::from_name(&predicate.name).is_some() && predicate.args.len() == 2)
+                || signature(&self.options.signatures, &predicate.name)
+                    .is_some_and(|arg_types| arg_types.iter().any(|arg_type| matches!(arg_type, ArgType::Int | ArgType::Real)))
+        });
+        if uses_arithmetic && !arithmetic_logic {
+            anyhow::bail!("Statement '{}' uses arithmetic, which the logic {} excludes", statement.id, logic);
+        }
+        Ok(())
+    }
+
+    /// Convert a statement to Z3 boolean expression. A statement that cannot be
+    /// lowered, e.g. for a predicate used with the wrong number of arguments, is an
+    /// error naming the statement.
+    fn statement_to_z3(&mut self, statement: &Statement) -> anyhow::Result<Bool<'ctx>> {
+        self.check_logic(statement)?;
+        self.lower_statement(statement)
+            .with_context(|| format!("Cannot translate statement '{}' (\"{}\") to Z3", statement.id, statement.text))
+    }
+
+    fn lower_statement(&mut self, statement: &Statement) -> anyhow::Result<Bool<'ctx>> {
+        let Some(quantifier) = &statement.quantifier else {
+            return self.body_to_z3(&statement.formula(), &HashMap::new());
+        };
+
+        // Bound variables are constants of the object sort that the quantifier abstracts
+        let bound: HashMap<String, Dynamic<'ctx>> = quantifier.variables()
+            .iter()
+            .map(|variable| {
+                let constant = FuncDecl::new(self.context, variable.as_str(), &[], &self.object_sort).apply(&[]);
+                (normalize_symbol(variable), constant)
+            })
+            .collect();
+        let bounds: Vec<&dyn Ast<'ctx>> = bound.values().map(|constant| constant as &dyn Ast<'ctx>).collect();
+
+        let domain = quantifier.domain().iter().cloned().map(StatementBody::Atom).collect();
+        let domain = self.body_to_z3(&StatementBody::And(domain), &bound)?;
+        let body = self.body_to_z3(&statement.formula(), &bound)?;
+        match quantifier {
+            Quantifier::ForAll { .. } => {
+                Ok(z3::ast::forall_const(self.context, &bounds, &[], &domain.implies(&body)))
+            }
+            Quantifier::Exists { .. } => {
+                Ok(z3::ast::exists_const(self.context, &bounds, &[], &Bool::and(self.context, &[&domain, &body])))
+            }
+        }
+    }
+
+    /// Convert a formula to Z3, with arguments named in `bound` referring to
+    /// quantified variables
+    fn body_to_z3(
+        &mut self,
+        body: &StatementBody,
+        bound: &HashMap<String, Dynamic<'ctx>>,
+    ) -> anyhow::Result<Bool<'ctx>> {
+        match body {
+            StatementBody::Atom(predicate) => {
+                let z3_pred = self.bound_predicate_to_z3(predicate, bound)?;
+                Ok(if predicate.negated { z3_pred.not() } else { z3_pred })
+            }
+            StatementBody::Comparison { lhs, op, rhs } => self.terms_comparison_to_z3(*op, lhs, rhs),
+            StatementBody::Not(inner) => Ok(self.body_to_z3(inner, bound)?.not()),
+            StatementBody::Implies(antecedent, consequent) => {
+                let antecedent = self.body_to_z3(antecedent, bound)?;
+                Ok(antecedent.implies(&self.body_to_z3(consequent, bound)?))
+            }
+            StatementBody::And(parts) => {
+                let mut conjuncts = Vec::new();
+                for part in parts {
+                    conjuncts.push(self.body_to_z3(part, bound)?);
+                }
+
+                // Combine predicates with AND
+                if conjuncts.is_empty() {
+                    Ok(Bool::from_bool(self.context, true))
+                } else if conjuncts.len() == 1 {
+                    Ok(conjuncts.into_iter().next().unwrap())
+                } else {
+                    let conjuncts: Vec<&Bool<'ctx>> = conjuncts.iter().collect();
+                    Ok(Bool::and(self.context, &conjuncts))
+                }
+            }
+            StatementBody::Or(parts) => {
+                let mut disjuncts = Vec::new();
+                for part in parts {
+                    disjuncts.push(self.body_to_z3(part, bound)?);
+                }
+
+                // Combine alternatives with OR
+                if disjuncts.is_empty() {
+                    Ok(Bool::from_bool(self.context, false))
+                } else if disjuncts.len() == 1 {
+                    Ok(disjuncts.into_iter().next().unwrap())
+                } else {
+                    let disjuncts: Vec<&Bool<'ctx>> = disjuncts.iter().collect();
+                    Ok(Bool::or(self.context, &disjuncts))
+                }
+            }
+            StatementBody::ExactlyOne(parts) => {
+                let mut options = Vec::new();
+                for part in parts {
+                    options.push(self.body_to_z3(part, bound)?);
+                }
+
+                // At least one option, and no two options together
+                let mut constraints = vec![Bool::or(self.context, &options.iter().collect::<Vec<_>>())];
+                for (i, first) in options.iter().enumerate() {
+                    for second in &options[i + 1..] {
+                        constraints.push(Bool::and(self.context, &[first, second]).not());
+                    }
+                }
+                Ok(Bool::and(self.context, &constraints.iter().collect::<Vec<_>>()))
+            }
+        }
+    }
+
+    /// Convert a predicate to its Z3 atom, ignoring negation: the predicate's
+    /// uninterpreted function applied to its arguments
+    fn predicate_to_z3(&mut self, predicate: &Predicate) -> anyhow::Result<Bool<'ctx>> {
+        self.bound_predicate_to_z3(predicate, &HashMap::new())
+    }
+
+    /// Convert a predicate that may mention quantified variables to its Z3 atom. Only
+    /// ground atoms are cached, so models never report values for bound variables.
+    fn bound_predicate_to_z3(
+        &mut self,
+        predicate: &Predicate,
+        bound: &HashMap<String, Dynamic<'ctx>>,
+    ) -> anyhow::Result<Bool<'ctx>> {
+        if let (Some(op), [lhs, rhs]) = (Comparison::from_name(&predicate.name), predicate.args.as_slice()) {
+            return self.comparison_to_z3(op, lhs, rhs);
+        }
+        let predicate = &predicate.normalized();
+        if let Some(relation) = ObjectRelation::from_name(&predicate.name).filter(|_| predicate.args.len() >= 2) {
+            let terms: Vec<Dynamic<'ctx>> = predicate.args
+                .iter()
+                .map(|arg| bound.get(arg).cloned().unwrap_or_else(|| self.object(arg)))
+                .collect();
+            return Ok(match relation {
+                ObjectRelation::Eq => {
+                    let links: Vec<Bool<'ctx>> = terms.windows(2).map(|pair| pair[0]._eq(&pair[1])).collect();
+                    Bool::and(self.context, &links.iter().collect::<Vec<_>>())
+                }
+                ObjectRelation::Distinct => Dynamic::distinct(self.context, &terms.iter().collect::<Vec<_>>()),
+            });
+        }
+
+        let pred_name = predicate_key(predicate);
+        let ground = !predicate.args.iter().any(|arg| bound.contains_key(arg));
+        if let Some(atom) = self.predicates.get(&pred_name).filter(|_| ground) {
+            return Ok(atom.clone());
+        }
+        let arg_types = self.arg_types(predicate)?;
+        let cache_key = (pred_name.clone(), arg_types.clone());
+        if ground && self.options.cache_predicates {
+            if let Some(atom) = self.atom_cache.get(&cache_key).cloned() {
+                self.cache_stats.hits += 1;
+                self.predicates.insert(pred_name, atom.clone());
+                return Ok(atom);
+            }
+        }
+
+        let mut args = Vec::new();
+        for (arg, &arg_type) in predicate.args.iter().zip(&arg_types) {
+            args.push(match bound.get(arg) {
+                Some(variable) if arg_type == ArgType::Object => variable.clone(),
+                _ => self.argument_to_z3(arg, arg_type)?,
+            });
+        }
+        let args: Vec<&dyn Ast<'ctx>> = args.iter().map(|arg| arg as &dyn Ast<'ctx>).collect();
+        let atom = self.predicate_function(&predicate.name, &arg_types)
+            .apply(&args)
+            .as_bool()
+            .ok_or_else(|| anyhow::anyhow!("Predicate '{}' does not apply to a boolean", pred_name))?;
+        if ground {
+            if self.options.cache_predicates {
+                self.cache_stats.misses += 1;
+                self.atom_cache.insert(cache_key, atom.clone());
+            }
+            self.predicates.insert(pred_name, atom.clone());
+        }
+        Ok(atom)
+    }
+
+    /// Sorts of a predicate's arguments: its declared signature, or all objects
+    fn arg_types(&self, predicate: &Predicate) -> anyhow::Result<Vec<ArgType>> {
+        match signature(&self.options.signatures, &predicate.name) {
+            Some(arg_types) if arg_types.len() == predicate.args.len() => Ok(arg_types.clone()),
+            Some(arg_types) => anyhow::bail!(
+                "Predicate '{}' is declared with {} arguments but used with {}",
+                predicate.name, arg_types.len(), predicate.args.len()
+            ),
+            None => Ok(vec![ArgType::Object; predicate.args.len()]),
+        }
+    }
+
+    /// Convert a predicate argument to a Z3 term of the given sort. Numeric arguments
+    /// are linear terms over the same variables as arithmetic comparisons.
+    fn argument_to_z3(&mut self, arg: &str, arg_type: ArgType) -> anyhow::Result<Dynamic<'ctx>> {
+        Ok(match arg_type {
+            ArgType::Object => self.object(arg),
+            ArgType::Int => Dynamic::from_ast(&self.int_term_to_z3(&arithmetic::parse_term(arg)?)?),
+            ArgType::Real => Dynamic::from_ast(&self.real_term_to_z3(&arithmetic::parse_term(arg)?)),
+            ArgType::Bool => Dynamic::from_ast(&match arg.trim() {
+                "true" => Bool::from_bool(self.context, true),
+                "false" => Bool::from_bool(self.context, false),
+                name => Bool::new_const(self.context, name),
+            }),
+        })
+    }
+
+    /// Constant of the object sort naming a predicate argument
+    fn object(&mut self, name: &str) -> Dynamic<'ctx> {
+        let name = normalize_symbol(name);
+        self.objects
+            .entry(name.clone())
+            .or_insert_with(|| FuncDecl::new(self.context, name.as_str(), &[], &self.object_sort).apply(&[]))
+            .clone()
+    }
+
+    /// Uninterpreted function from the given argument sorts to `Bool` for a predicate name
+    fn predicate_function(&mut self, name: &str, arg_types: &[ArgType]) -> &FuncDecl<'ctx> {
+        let context = self.context;
+        let object_sort = &self.object_sort;
+        self.functions
+            .entry((name.to_string(), arg_types.to_vec()))
+            .or_insert_with(|| {
+                let domain: Vec<Sort<'ctx>> = arg_types
+                    .iter()
+                    .map(|arg_type| match arg_type {
+                        ArgType::Object => object_sort.clone(),
+                        ArgType::Int => Sort::int(context),
+                        ArgType::Real => Sort::real(context),
+                        ArgType::Bool => Sort::bool(context),
+                    })
+                    .collect();
+                let domain: Vec<&Sort<'ctx>> = domain.iter().collect();
+                FuncDecl::new(context, name, &domain, &Sort::bool(context))
+            })
+    }
+
+    /// Truth value of every named predicate under the model of the last satisfiable check
+    fn model(&self) -> Option<HashMap<String, bool>> {
+        let model = self.solver.get_model()?;
+        Some(self.predicates
+            .iter()
+            .map(|(name, atom)| {
+                let value = model.eval(atom, true).and_then(|v| v.as_bool()).unwrap_or(false);
+                (name.clone(), value)
+            })
+            .collect())
+    }
+
+    /// Truth value of every ground predicate of every statement under the model of the
+    /// last satisfiable check, showing a concrete world in which all statements hold
+    fn witness(&mut self, statements: &[Statement]) -> anyhow::Result<Option<Vec<StatementWitness>>> {
+        let Some(model) = self.solver.get_model() else {
+            return Ok(None);
+        };
+
+        let mut witness = Vec::new();
+        for statement in statements {
+            let mut values = Vec::new();
+            let variables = statement.quantifier.as_ref().map(Quantifier::variables).unwrap_or_default();
+            for predicate in statement.atoms() {
+                if predicate.args.iter().any(|arg| variables.iter().any(|variable| normalize_symbol(variable) == normalize_symbol(arg))) {
+                    continue;
+                }
+                let atom = self.predicate_to_z3(&predicate)?;
+                let value = model.eval(&atom, true).and_then(|v| v.as_bool()).unwrap_or(false);
+                values.push((predicate_key(&predicate), value));
+            }
+            witness.push((statement.id.clone(), values));
+        }
+
+        Ok(Some(witness))
+    }
+
+    /// Convert an arithmetic comparison between two linear terms to a Z3 constraint
+    /// over the configured numeric sort
+    fn comparison_to_z3(&mut self, op: Comparison, lhs: &str, rhs: &str) -> anyhow::Result<Bool<'ctx>> {
+        let lhs = arithmetic::parse_term(lhs)?;
+        let rhs = arithmetic::parse_term(rhs)?;
+        self.terms_comparison_to_z3(op, &lhs, &rhs)
+    }
+
+    /// Convert an arithmetic comparison between parsed terms to a Z3 constraint
+    fn terms_comparison_to_z3(&mut self, op: Comparison, lhs: &Term, rhs: &Term) -> anyhow::Result<Bool<'ctx>> {
+
+        macro_rules! compare {
+            ($lhs:expr, $rhs:expr) => {{
+                let (lhs, rhs) = ($lhs, $rhs);
+                match op {
+                    Comparison::Lt => lhs.lt(&rhs),
+                    Comparison::Le => lhs.le(&rhs),
+                    Comparison::Gt => lhs.gt(&rhs),
+                    Comparison::Ge => lhs.ge(&rhs),
+                    Comparison::Eq => lhs._eq(&rhs),
+                    Comparison::Ne => lhs._eq(&rhs).not(),
+                }
+            }};
+        }
+
+        Ok(match self.options.numeric_sort {
+            NumericSort::Int => compare!(self.int_term_to_z3(lhs)?, self.int_term_to_z3(rhs)?),
+            NumericSort::Real => compare!(self.real_term_to_z3(lhs), self.real_term_to_z3(rhs)),
+        })
+    }
+
+    /// Convert an arithmetic term to a Z3 integer expression
+    fn int_term_to_z3(&mut self, term: &Term) -> anyhow::Result<Int<'ctx>> {
+        Ok(match term {
+            Term::Const(n) => Int::from_i64(self.context, *n),
+            Term::Ratio(num, den) if num % den == 0 => Int::from_i64(self.context, num / den),
+            Term::Ratio(num, den) => anyhow::bail!(
+                "Decimal constant {}/{} requires the real numeric sort", num, den
+            ),
+            Term::Var(name) => self.int_vars
+                .entry(name.clone())
+                .or_insert_with(|| Int::new_const(self.context, name.clone()))
+                .clone(),
+            Term::Neg(inner) => self.int_term_to_z3(inner)?.unary_minus(),
+            Term::Add(a, b) => Int::add(self.context, &[&self.int_term_to_z3(a)?, &self.int_term_to_z3(b)?]),
+            Term::Sub(a, b) => Int::sub(self.context, &[&self.int_term_to_z3(a)?, &self.int_term_to_z3(b)?]),
+            Term::Mul(a, b) => Int::mul(self.context, &[&self.int_term_to_z3(a)?, &self.int_term_to_z3(b)?]),
+        })
+    }
+
+    /// Convert an arithmetic term to a Z3 real expression
+    fn real_term_to_z3(&mut self, term: &Term) -> Real<'ctx> {
+        match term {
+            Term::Const(n) => Int::from_i64(self.context, *n).to_real(),
+            Term::Ratio(num, den) => Int::from_i64(self.context, *num)
+                .to_real()
+                .div(&Int::from_i64(self.context, *den).to_real()),
+            Term::Var(name) => self.real_vars
+                .entry(name.clone())
+                .or_insert_with(|| Real::new_const(self.context, name.clone()))
+                .clone(),
+            Term::Neg(inner) => self.real_term_to_z3(inner).unary_minus(),
+            Term::Add(a, b) => Real::add(self.context, &[&self.real_term_to_z3(a), &self.real_term_to_z3(b)]),
+            Term::Sub(a, b) => Real::sub(self.context, &[&self.real_term_to_z3(a), &self.real_term_to_z3(b)]),
+            Term::Mul(a, b) => Real::mul(self.context, &[&self.real_term_to_z3(a), &self.real_term_to_z3(b)]),
+        }
+    }
+
+    /// Find minimal sets of jointly inconsistent statements, starting from an unsat core
+    /// of the full set (as statement indices). Each conflict found is shrunk to a
+    /// minimal unsatisfiable subset, its statements are set aside and the rest is
+    /// checked again, so disjoint conflicts are all reported, ordered by the position of
+    /// their statements in the input. Also returns whether the total deadline cut the
+    /// analysis short, in which case the list may be incomplete.
+    fn find_contradictions(&mut self, statements: &[Statement], core: Vec<usize>) -> anyhow::Result<(Vec<Contradiction>, bool)> {
+        let mut exprs = Vec::new();
+        for statement in statements {
+            exprs.push(self.statement_to_z3(statement)?);
+        }
+        let trackers = trackers(self.context, statements.len());
+
+        // Scratch solvers start empty; they share only constants with the main solver
+        let temp_solver = new_solver(self.context, self.options.logic.as_deref());
+        let mut conflicts: Vec<Vec<usize>> = Vec::new();
+        let mut remaining: Vec<usize> = (0..statements.len()).collect();
+        let mut next_core = Some(core);
+        let mut truncated = false;
+
+        while let Some(core) = next_core.take() {
+            let Some(conflict) = self.minimize_conflict(&exprs, core) else {
+                truncated = true;
+                break;
+            };
+            if conflict.is_empty() {
+                break;
+            }
+            remaining.retain(|i| !conflict.contains(i));
+            conflicts.push(conflict);
+
+            // Look for a further conflict among the statements not involved yet
+            if self.deadline_passed() {
+                truncated = true;
+                break;
+            }
+            temp_solver.reset();
+            self.apply_params(&temp_solver);
+            for axiom in &self.axioms {
+                temp_solver.assert(axiom);
+            }
+            for &k in &remaining {
+                temp_solver.assert_and_track(&exprs[k], &trackers[k]);
+            }
+            match temp_solver.check() {
+                SatResult::Unsat => next_core = Some(core_indices(&temp_solver, &trackers)),
+                SatResult::Sat => {}
+                SatResult::Unknown => truncated = self.deadline_passed(),
+            }
+        }
+
+        conflicts.sort();
+        let contradictions = conflicts
+            .iter()
+            .map(|conflict| conflict_to_contradiction(statements, conflict))
+            .collect();
+        Ok((contradictions, truncated))
+    }
+
+    /// Shrink an unsatisfiable set of statement indices to a minimal unsatisfiable
+    /// subset by dropping each statement in turn and keeping the drop whenever the rest
+    /// is still unsatisfiable. Returns `None` if the total deadline passes first.
+    fn minimize_conflict(&self, exprs: &[Bool<'ctx>], mut keep: Vec<usize>) -> Option<Vec<usize>> {
+        let temp_solver = new_solver(self.context, self.options.logic.as_deref());
+        let mut i = 0;
+        while i < keep.len() {
+            if self.deadline_passed() {
+                return None;
+            }
+            temp_solver.reset();
+            self.apply_params(&temp_solver);
+            for axiom in &self.axioms {
+                temp_solver.assert(axiom);
+            }
+            for &k in keep.iter().filter(|&&k| k != keep[i]) {
+                temp_solver.assert(&exprs[k]);
+            }
+            if temp_solver.check() == SatResult::Unsat {
+                keep.remove(i);
+            } else {
+                i += 1;
             }
-            _ => Ok(None),
         }
+
+        Some(keep)
     }
 
     /// Verify a reasoning chain (premises → conclusion)
-    pub fn verify_reasoning_chain(&mut self, premises: &[Statement], conclusion: &Statement) -> anyhow::Result<VerificationResult> {
+    pub fn verify_reasoning(&mut self, premises: &[Statement], conclusion: &Statement) -> anyhow::Result<ReasoningResult> {
         // Clear state
-        self.solver.reset();
-        self.predicates.clear();
+        self.reset();
+        self.push_statements(premises)?;
+        self.entails(conclusion)
+    }
 
-        // Convert to Z3
-        let mut premise_exprs = Vec::new();
-        for premise in premises {
-            let expr = self.statement_to_z3(premise)?;
-            premise_exprs.push(expr);
+    /// Verify a reasoning chain, reporting validity through `VerificationResult`
+    #[deprecated(note = "use `verify_reasoning`, which returns a `ReasoningResult`")]
+    pub fn verify_reasoning_chain(&mut self, premises: &[Statement], conclusion: &Statement) -> anyhow::Result<VerificationResult> {
+        let result = self.verify_reasoning(premises, conclusion)?;
+        Ok(VerificationResult {
+            status: if result.is_valid {
+                VerificationStatus::Consistent
+            } else if result.unknown_reason.is_some() {
+                VerificationStatus::Unknown
+            } else {
+                VerificationStatus::Inconsistent
+            },
+            proof: result.proof,
+            contradictions: vec![],
+            confidence: result.confidence,
+            per_statement_witness: None,
+            model: result.counterexample,
+            truncated: result.truncated,
+            unknown_reason: result.unknown_reason,
+            statement_triviality: Vec::new(),
+            unparsed_statements: result.unparsed_statements,
+        })
+    }
+
+    /// Check whether the currently pushed statements entail the conclusion. The
+    /// negated conclusion is asserted in a scratch scope, so the pushed statements
+    /// can be checked against any number of conclusions.
+    pub fn entails(&mut self, conclusion: &Statement) -> anyhow::Result<ReasoningResult> {
+        self.entails_all(std::slice::from_ref(conclusion))
+    }
+
+    /// Check whether the currently pushed statements entail every one of `conclusions`
+    fn entails_all(&mut self, conclusions: &[Statement]) -> anyhow::Result<ReasoningResult> {
+        let mut exprs = Vec::new();
+        for conclusion in conclusions {
+            exprs.push(self.statement_to_z3(conclusion)?);
         }
-        
-        let conclusion_expr = self.statement_to_z3(conclusion)?;
+        let conclusion_expr = Bool::and(self.context, &exprs.iter().collect::<Vec<_>>());
+        let mut unparsed_statements = unparsed_ids(&self.scopes.concat());
+        unparsed_statements.extend(unparsed_ids(conclusions));
 
         // Check if premises → conclusion is valid
         // This is equivalent to checking if ¬(premises → conclusion) is unsatisfiable
         // Which is equivalent to checking if (premises ∧ ¬conclusion) is unsatisfiable
-        
-        // Assert all premises
-        for premise_expr in &premise_exprs {
-            self.solver.assert(premise_expr);
-        }
-        
-        // Assert negation of conclusion
+        self.solver.push();
         self.solver.assert(&conclusion_expr.not());
-        
-        match self.solver.check() {
+
+        let started = Instant::now();
+        let result = self.solver.check();
+        let check_time = started.elapsed();
+        self.last_statistics = SolverStats::new(solver_statistics(&self.solver), check_time);
+        let result = match result {
             SatResult::Unsat => {
                 // Valid reasoning: premises logically entail conclusion
-                Ok(VerificationResult {
-                    is_consistent: true,
-                    proof: Some("Z3 proved premises logically entail conclusion".to_string()),
-                    contradictions: vec![],
+                ReasoningResult {
+                    is_valid: true,
+                    counterexample: None,
+                    proof: self.unsat_proof("Z3 proved premises logically entail conclusion"),
                     confidence: 1.0,
-                })
+                    truncated: false,
+                    unknown_reason: None,
+                    unparsed_statements,
+                }
             }
             SatResult::Sat => {
                 // Invalid reasoning: conclusion doesn't follow from premises
-                Ok(VerificationResult {
-                    is_consistent: false,
+                ReasoningResult {
+                    is_valid: false,
+                    counterexample: self.model(),
                     proof: Some("Z3 found counterexample where premises are true but conclusion is false".to_string()),
-                    contradictions: vec![],
                     confidence: 1.0,
-                })
+                    truncated: false,
+                    unknown_reason: None,
+                    unparsed_statements,
+                }
             }
             SatResult::Unknown => {
-                Ok(VerificationResult {
-                    is_consistent: false,
+                ReasoningResult {
+                    is_valid: false,
+                    counterexample: None,
                     proof: None,
-                    contradictions: vec![],
                     confidence: 0.0,
-                })
+                    truncated: self.deadline_passed(),
+                    unknown_reason: Some(self.unknown_reason(check_time)),
+                    unparsed_statements,
+                }
             }
-        }
-    }
-}
+        };
+        self.solver.pop(1);
 
-/// Parse natural language statement into formal predicates (simplified)
-pub fn parse_statement(text: &str, id: &str) -> Statement {
-    let mut predicates = Vec::new();
-    let text_lower = text.to_lowercase();
-    
-    // More precise pattern matching for logical contradictions
-    if text_lower.contains("all") && text_lower.contains("perfectly logical") {
-        // "All AI systems are perfectly logical" → ∀x: AI_system(x) → ¬Contains_contradictions(x)
-        predicates.push(Predicate {
-            name: "ai_system_perfectly_logical".to_string(),
-            args: vec!["ai_systems".to_string()],
-            negated: false,
-        });
-        // This implies no contradictions in AI systems
-        predicates.push(Predicate {
-            name: "ai_systems_contain_contradictions".to_string(),
-            args: vec!["ai_systems".to_string()],
-            negated: true,
-        });
+        Ok(result)
     }
-    
-    if text_lower.contains("ai systems contain contradictions") || 
-       text_lower.contains("current ai systems contain contradictions") {
-        // "Current AI systems contain contradictions" → ∃x: AI_system(x) ∧ Contains_contradictions(x)
-        predicates.push(Predicate {
-            name: "ai_systems_contain_contradictions".to_string(),
-            args: vec!["ai_systems".to_string()],
-            negated: false,
-        });
+
+    /// Compare a revised statement set with the original by checking entailment both
+    /// ways, e.g. to confirm a refactored knowledge base still says the same thing.
+    /// Unparsed statements constrain nothing, so they never make a set stronger.
+    pub fn compare(&mut self, old: &[Statement], new: &[Statement]) -> anyhow::Result<EquivalenceResult> {
+        self.reset();
+        self.push_statements(old)?;
+        let old_entails_new = self.entails_all(new)?;
+
+        self.reset();
+        self.push_statements(new)?;
+        let new_entails_old = self.entails_all(old)?;
+
+        let decided = |result: &ReasoningResult| result.unknown_reason.is_none();
+        let strength = match (old_entails_new.is_valid, new_entails_old.is_valid) {
+            _ if !decided(&old_entails_new) || !decided(&new_entails_old) => Strength::Unknown,
+            (true, true) => Strength::Equivalent,
+            (false, true) => Strength::Stronger,
+            (true, false) => Strength::Weaker,
+            (false, false) => Strength::Incomparable,
+        };
+        Ok(EquivalenceResult { strength, old_entails_new, new_entails_old })
     }
-    
-    if text_lower.contains("no") && text_lower.contains("ai systems exist") {
-        // "No AI systems exist" → ¬∃x: AI_system(x)
-        predicates.push(Predicate {
-            name: "ai_systems_exist".to_string(),
-            args: vec!["ai_systems".to_string()],
-            negated: true,
-        });
+
+    /// Find pairs of differently-named predicates that the statements force to be
+    /// equivalent (each entails the other), i.e. aliases that could be merged.
+    ///
+    /// This is opt-in and not run by `verify_statements`: it issues one Z3 check per
+    /// pair of predicates, so the cost is O(n²) in the number of distinct predicates.
+    /// Inconsistent statements entail everything, so no pairs are reported for them.
+    /// Pairs still unchecked when the total deadline passes are skipped.
+    pub fn discover_equivalences(&mut self, statements: &[Statement]) -> anyhow::Result<Vec<(String, String)>> {
+        self.reset();
+
+        for statement in statements {
+            let z3_expr = self.statement_to_z3(statement)?;
+            self.solver.assert(&z3_expr);
+        }
+
+        if self.solver.check() != SatResult::Sat {
+            return Ok(vec![]);
+        }
+
+        let mut names: Vec<String> = self.predicates.keys().cloned().collect();
+        names.sort();
+
+        let mut equivalences = Vec::new();
+        for i in 0..names.len() {
+            for j in (i + 1)..names.len() {
+                if self.deadline_passed() {
+                    return Ok(equivalences);
+                }
+                let a = &self.predicates[&names[i]];
+                let b = &self.predicates[&names[j]];
+
+                // A ⊢ B and B ⊢ A hold iff no model assigns them different values
+                self.solver.push();
+                self.solver.assert(&a._eq(b).not());
+                if self.solver.check() == SatResult::Unsat {
+                    equivalences.push((names[i].clone(), names[j].clone()));
+                }
+                self.solver.pop(1);
+            }
+        }
+
+        Ok(equivalences)
     }
-    
-    if text_lower.contains("we need") {
-        // Extract what we need
-        if text_lower.contains("coherent tools") {
-            predicates.push(Predicate {
-                name: "need_coherent_tools".to_string(),
-                args: vec!["we".to_string()],
-                negated: false,
-            });
+
+    /// IDs of the statements the others already entail, in input order: those for
+    /// which `others ∧ ¬statement` is unsatisfiable, so dropping one changes nothing.
+    /// Statements entailing each other are all reported; drop them one at a time.
+    ///
+    /// Inconsistent statements entail everything, so they are an error, as are
+    /// statements Z3 cannot decide. Statements still unchecked when the total
+    /// deadline passes are not reported.
+    pub fn find_redundant(&mut self, statements: &[Statement]) -> anyhow::Result<Vec<String>> {
+        self.reset();
+
+        // Each statement is guarded by a literal, so leaving one statement out of a
+        // check is leaving its literal out of the assumptions
+        let selectors = trackers(self.context, statements.len());
+        let mut exprs = Vec::new();
+        for (statement, selector) in statements.iter().zip(&selectors) {
+            let expr = self.statement_to_z3(statement)?;
+            self.solver.assert(&selector.implies(&expr));
+            exprs.push(expr);
         }
-        if text_lower.contains("validation") {
-            predicates.push(Predicate {
-                name: "need_validation".to_string(),
-                args: vec!["we".to_string()],
-                negated: false,
-            });
+
+        match self.solver.check_assumptions(&selectors) {
+            SatResult::Sat => {}
+            SatResult::Unsat => anyhow::bail!(
+                "Statements are inconsistent, so each is entailed by the others; resolve the contradictions first"
+            ),
+            SatResult::Unknown => anyhow::bail!("Z3 could not decide whether the statements are consistent"),
+        }
+
+        let mut redundant = Vec::new();
+        for (i, statement) in statements.iter().enumerate() {
+            if self.deadline_passed() {
+                break;
+            }
+            let others: Vec<Bool<'ctx>> = selectors
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, selector)| selector.clone())
+                .collect();
+            self.solver.push();
+            self.solver.assert(&exprs[i].not());
+            if self.solver.check_assumptions(&others) == SatResult::Unsat {
+                redundant.push(statement.id.clone());
+            }
+            self.solver.pop(1);
         }
+        Ok(redundant)
     }
-    
-    if text_lower.contains("coherent tools require validation") {
-        // "Coherent tools require validation" → ∀x: Coherent_tool(x) → Requires_validation(x)
-        predicates.push(Predicate {
-            name: "coherent_tools_require_validation".to_string(),
-            args: vec!["tools".to_string()],
-            negated: false,
-        });
-        // If we need coherent tools and they require validation, we need validation
-        predicates.push(Predicate {
-            name: "need_validation_implied".to_string(),
-            args: vec!["we".to_string()],
-            negated: false,
-        });
+
+    /// Largest jointly consistent subset of the statements, by statement ID in input
+    /// order. Each statement is a soft constraint for Z3's optimizer, weighted by its
+    /// `weight`, so when statements conflict the ones of higher total weight are kept.
+    /// Weights are resolved to thousandths.
+    pub fn max_consistent_subset(&mut self, statements: &[Statement]) -> anyhow::Result<Vec<String>> {
+        self.reset();
+
+        let kept = self.maximum_selection(statements)?
+            .ok_or_else(|| anyhow::anyhow!("Z3 could not determine a maximum consistent subset"))?;
+        Ok(statements
+            .iter()
+            .zip(kept)
+            .filter(|(_, kept)| *kept)
+            .map(|(statement, _)| statement.id.clone())
+            .collect())
     }
-    
-    // Handle some/all quantifiers more carefully
-    if text_lower.contains("some") && text_lower.contains("logical") {
-        predicates.push(Predicate {
-            name: "some_systems_logical".to_string(),
-            args: vec!["systems".to_string()],
-            negated: false,
-        });
+
+    /// Which statements the maximum consistent subset keeps, on top of the axioms;
+    /// `None` if Z3's optimizer does not find one within the check limit
+    fn maximum_selection(&mut self, statements: &[Statement]) -> anyhow::Result<Option<Vec<bool>>> {
+        let optimize = Optimize::new(self.context);
+        for axiom in &self.axioms {
+            optimize.assert(axiom);
+        }
+        let selectors = trackers(self.context, statements.len());
+        for (statement, selector) in statements.iter().zip(&selectors) {
+            let weight = statement.weight.unwrap_or(1.0);
+            if !(weight.is_finite() && weight > 0.0) {
+                anyhow::bail!("Statement '{}' has weight {}, expected a positive number", statement.id, weight);
+            }
+            let expr = self.statement_to_z3(statement)?;
+            optimize.assert(&selector.implies(&expr));
+            optimize.assert_soft(selector, (weight * 1000.0).round().max(1.0) as u64, None);
+        }
+
+        if self.check_optimize(&optimize) != SatResult::Sat {
+            return Ok(None);
+        }
+        let Some(model) = optimize.get_model() else {
+            return Ok(None);
+        };
+        Ok(Some(selectors
+            .iter()
+            .map(|selector| model.eval(selector, true).and_then(|value| value.as_bool()).unwrap_or(false))
+            .collect()))
     }
-    
-    if text_lower.contains("some") && text_lower.contains("errors") {
-        predicates.push(Predicate {
-            name: "some_systems_have_errors".to_string(),
-            args: vec!["systems".to_string()],
-            negated: false,
-        });
+
+    /// Confidence of an inconsistent verdict: the fraction of the statements the
+    /// maximum consistent subset keeps, so one conflicting statement among ten gives
+    /// 0.9. It is 0.0 when there are no statements to grade, the total deadline has
+    /// passed or the optimizer gives up.
+    ///
+    /// Z3's optimizer does not support quantifiers, so with quantified statements the
+    /// subset is grown greedily in input order instead, as the propositional backend
+    /// does: a lower bound on the maximum.
+    fn retained_fraction(&mut self, statements: &[Statement]) -> anyhow::Result<f64> {
+        if statements.is_empty() || self.deadline_passed() {
+            return Ok(0.0);
+        }
+        if statements.iter().any(|statement| statement.quantifier.is_some()) {
+            let solver = new_solver(self.context, self.options.logic.as_deref());
+            self.apply_params(&solver);
+            for axiom in &self.axioms {
+                solver.assert(axiom);
+            }
+            let mut kept = 0;
+            for statement in statements {
+                if self.deadline_passed() {
+                    return Ok(0.0);
+                }
+                solver.push();
+                solver.assert(&self.statement_to_z3(statement)?);
+                if solver.check() == SatResult::Sat {
+                    kept += 1;
+                } else {
+                    solver.pop(1);
+                }
+            }
+            return Ok(kept as f64 / statements.len() as f64);
+        }
+        Ok(match self.maximum_selection(statements)? {
+            Some(kept) => kept.iter().filter(|&&kept| kept).count() as f64 / statements.len() as f64,
+            None => 0.0,
+        })
     }
 
-    Statement {
-        id: id.to_string(),
-        text: text.to_string(),
-        predicates,
+    /// Enumerate every minimal inconsistent subset (MUS) of the statements, each as
+    /// statement IDs in input order, not just the conflicts `verify_statements` reports.
+    ///
+    /// MARCO-style: a map solver over one selector per statement proposes subsets not
+    /// yet explored. An unsatisfiable subset is shrunk to a MUS and its supersets are
+    /// blocked; a satisfiable one is grown to a maximal satisfiable subset and its
+    /// subsets are blocked. Enumeration stops early, marked truncated, when a check is
+    /// undecided or the total deadline passes.
+    pub fn find_all_muses(&mut self, statements: &[Statement]) -> anyhow::Result<MusEnumeration> {
+        self.reset();
+
+        let mut exprs = Vec::new();
+        for statement in statements {
+            exprs.push(self.statement_to_z3(statement)?);
+        }
+        let selectors = trackers(self.context, statements.len());
+        for (expr, selector) in exprs.iter().zip(&selectors) {
+            self.solver.assert(&selector.implies(expr));
+        }
+        let selected = |subset: &[usize]| -> Vec<Bool<'ctx>> {
+            subset.iter().map(|&i| selectors[i].clone()).collect()
+        };
+
+        let map = Solver::new(self.context);
+        let mut muses = Vec::new();
+        let mut truncated = false;
+        loop {
+            if self.deadline_passed() {
+                truncated = true;
+                break;
+            }
+            match map.check() {
+                SatResult::Sat => {}
+                SatResult::Unsat => break,
+                SatResult::Unknown => {
+                    truncated = true;
+                    break;
+                }
+            }
+            let Some(model) = map.get_model() else {
+                truncated = true;
+                break;
+            };
+            // Selectors the map leaves unconstrained are taken, biasing seeds upwards
+            let mut seed: Vec<usize> = (0..statements.len())
+                .filter(|&i| model.eval(&selectors[i], false).and_then(|value| value.as_bool()).unwrap_or(true))
+                .collect();
+
+            match self.solver.check_assumptions(&selected(&seed)) {
+                SatResult::Sat => {
+                    for i in 0..statements.len() {
+                        if seed.contains(&i) {
+                            continue;
+                        }
+                        seed.push(i);
+                        if self.solver.check_assumptions(&selected(&seed)) != SatResult::Sat {
+                            seed.pop();
+                        }
+                    }
+                    let outside: Vec<Bool> = (0..statements.len())
+                        .filter(|i| !seed.contains(i))
+                        .map(|i| selectors[i].clone())
+                        .collect();
+                    map.assert(&Bool::or(self.context, &outside.iter().collect::<Vec<_>>()));
+                }
+                SatResult::Unsat => {
+                    seed.sort();
+                    let Some(mus) = self.minimize_conflict(&exprs, seed) else {
+                        truncated = true;
+                        break;
+                    };
+                    let dropped: Vec<Bool> = mus.iter().map(|&i| selectors[i].not()).collect();
+                    map.assert(&Bool::or(self.context, &dropped.iter().collect::<Vec<_>>()));
+                    muses.push(mus);
+                }
+                SatResult::Unknown => {
+                    truncated = true;
+                    break;
+                }
+            }
+        }
+
+        muses.sort();
+        Ok(MusEnumeration {
+            muses: muses
+                .iter()
+                .map(|mus| mus.iter().map(|&i| statements[i].id.clone()).collect())
+                .collect(),
+            truncated,
+        })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Solver for the SMT-LIB `logic`, or the general solver without one or if Z3 does
+/// not know it
+#[cfg(feature = "z3")]
+fn new_solver<'ctx>(context: &'ctx Context, logic: Option<&str>) -> Solver<'ctx> {
+    logic
+        .and_then(|logic| Solver::new_for_logic(context, logic))
+        .unwrap_or_else(|| Solver::new(context))
+}
 
-    #[test]
-    fn test_contradiction_detection() {
-        let cfg = Config::new();
-        let ctx = Context::new(&cfg);
-        let mut verifier = CoherenceVerifier::new(&ctx);
+/// Every statistic the solver reported for its last check, by name
+#[cfg(feature = "z3")]
+fn solver_statistics(solver: &Solver) -> BTreeMap<String, f64> {
+    solver
+        .get_statistics()
+        .entries()
+        .map(|entry| {
+            let value = match entry.value {
+                StatisticsValue::UInt(v) => v as f64,
+                StatisticsValue::Double(v) => v,
+            };
+            (entry.key, value)
+        })
+        .collect()
+}
 
-        let stmt1 = parse_statement("All AI systems are perfectly logical", "stmt1");
-        let stmt2 = parse_statement("Current AI systems contain contradictions", "stmt2");
+/// Tracking literals `__track_0..n` naming statements in unsat cores
+#[cfg(feature = "z3")]
+fn trackers<'ctx>(context: &'ctx Context, n: usize) -> Vec<Bool<'ctx>> {
+    (0..n)
+        .map(|i| Bool::new_const(context, format!("__track_{}", i)))
+        .collect()
+}
 
-        let result = verifier.verify_statements(&[stmt1, stmt2]).unwrap();
-        assert!(!result.is_consistent);
+/// Indices of the trackers in the unsat core of the solver's last check
+#[cfg(feature = "z3")]
+fn core_indices(solver: &Solver, trackers: &[Bool]) -> Vec<usize> {
+    let core = solver.get_unsat_core();
+    trackers.iter()
+        .enumerate()
+        .filter(|(_, tracker)| core.contains(tracker))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Describe a minimal conflicting set of statements (indices in input order)
+#[cfg(feature = "z3")]
+fn conflict_to_contradiction(statements: &[Statement], conflict: &[usize]) -> Contradiction {
+    let ids: Vec<String> = conflict.iter().map(|&k| statements[k].id.clone()).collect();
+    let (reason, formal_proof) = match conflict {
+        [_] => (
+            "Statement contradicts itself".to_string(),
+            format!("Z3 proved {} is unsatisfiable", ids[0]),
+        ),
+        [_, _] => (
+            "Statements are mutually exclusive".to_string(),
+            "Z3 proved (stmt1 ∧ stmt2) is unsatisfiable".to_string(),
+        ),
+        _ => {
+            let chain: Vec<&str> = conflict.iter().map(|&k| statements[k].text.as_str()).collect();
+            (
+                format!("Statements form an unsatisfiable chain: {}", chain.join(" → ")),
+                format!(
+                    "Z3 proved ({}) is unsatisfiable while every proper subset is satisfiable",
+                    ids.join(" ∧ ")
+                ),
+            )
+        }
+    };
+
+    Contradiction {
+        statement1: ids[0].clone(),
+        statement2: ids[ids.len() - 1].clone(),
+        statements: ids,
+        reason,
+        formal_proof,
     }
+}
 
-    #[test]
-    fn test_valid_reasoning() {
-        let cfg = Config::new();
-        let ctx = Context::new(&cfg);
-        let mut verifier = CoherenceVerifier::new(&ctx);
+/// The statements without those repeating an earlier statement's formula. Unparsed
+/// statements are all kept, so each is still reported.
+fn distinct_statements(statements: &[Statement]) -> Vec<Statement> {
+    let mut distinct: Vec<Statement> = Vec::new();
+    for statement in statements {
+        if statement.is_unparsed() || !distinct.iter().any(|kept| kept.same_formula(statement)) {
+            distinct.push(statement.clone());
+        }
+    }
+    distinct
+}
 
-        let premise1 = parse_statement("All humans are mortal", "p1");
-        let premise2 = parse_statement("Socrates is human", "p2");
-        let conclusion = parse_statement("Socrates is mortal", "c1");
+/// Ids of the statements no predicates were extracted from, in input order
+fn unparsed_ids(statements: &[Statement]) -> Vec<String> {
+    statements
+        .iter()
+        .filter(|statement| statement.is_unparsed())
+        .map(|statement| statement.id.clone())
+        .collect()
+}
 
-        let result = verifier.verify_reasoning_chain(&[premise1, premise2], &conclusion).unwrap();
-        // Note: This would need more sophisticated parsing to work properly
-        // but demonstrates the approach
+/// Display name of a predicate: `name(args)`, or `lhs op rhs` for comparisons. It
+/// doubles as the key atoms are interned under, so a name or argument that is empty or
+/// contains `,`, `(`, `)` or `"` is written as a JSON string: `rel("a,b")` and
+/// `rel(a,b)` stay distinct.
+fn predicate_key(predicate: &Predicate) -> String {
+    match (Comparison::from_name(&predicate.name), predicate.args.as_slice()) {
+        (Some(op), [lhs, rhs]) => format!("{} {} {}", lhs.trim(), op.symbol(), rhs.trim()),
+        _ => {
+            let predicate = predicate.normalized();
+            let args: Vec<Cow<str>> = predicate.args.iter().map(|arg| key_symbol(arg)).collect();
+            format!("{}({})", key_symbol(&predicate.name), args.join(","))
+        }
+    }
+}
+
+/// Declared argument sorts of a predicate, matching names after normalization
+#[cfg(feature = "z3")]
+fn signature<'a>(signatures: &'a BTreeMap<String, Vec<ArgType>>, name: &str) -> Option<&'a Vec<ArgType>> {
+    let name = normalize_symbol(name);
+    signatures
+        .iter()
+        .find(|(declared, _)| normalize_symbol(declared) == name)
+        .map(|(_, arg_types)| arg_types)
+}
+
+/// A predicate name or argument as written in a predicate key, quoted when it could
+/// otherwise be mistaken for key syntax
+fn key_symbol(symbol: &str) -> Cow<'_, str> {
+    if symbol.is_empty() || symbol.contains([',', '(', ')', '"']) {
+        Cow::Owned(serde_json::Value::from(symbol).to_string())
+    } else {
+        Cow::Borrowed(symbol)
     }
-}
\ No newline at end of file
+}
+
+/// Group statements connected through contradictions, keeping input order within and
+/// across groups
+#[cfg(feature = "z3")]
+fn cluster_contradictions(statements: &[Statement], contradictions: &[Contradiction]) -> Vec<Vec<String>> {
+    let index: HashMap<&str, usize> = statements.iter()
+        .enumerate()
+        .map(|(i, statement)| (statement.id.as_str(), i))
+        .collect();
+    let mut parent: Vec<usize> = (0..statements.len()).collect();
+
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+
+    let mut involved = vec![false; statements.len()];
+    for contradiction in contradictions {
+        let members: Vec<usize> = contradiction.statements.iter()
+            .filter_map(|id| index.get(id.as_str()).copied())
+            .collect();
+        for &member in &members {
+            involved[member] = true;
+            let (a, b) = (find(&mut parent, members[0]), find(&mut parent, member));
+            parent[b] = a;
+        }
+    }
+
+    let mut clusters: Vec<(usize, Vec<String>)> = Vec::new();
+    for i in (0..statements.len()).filter(|&i| involved[i]) {
+        let root = find(&mut parent, i);
+        match clusters.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, cluster)) => cluster.push(statements[i].id.clone()),
+            None => clusters.push((root, vec![statements[i].id.clone()])),
+        }
+    }
+
+    clusters.into_iter().map(|(_, cluster)| cluster).collect()
+}
+
+/// Version of the Z3 library linked into this build, e.g. `4.12.2`
+#[cfg(feature = "z3")]
+pub fn z3_version() -> String {
+    let (mut major, mut minor, mut build, mut revision) = (0, 0, 0, 0);
+    // SAFETY: Z3_get_version only writes the four integers it is handed
+    unsafe { z3_sys::Z3_get_version(&mut major, &mut minor, &mut build, &mut revision) };
+    format!("{}.{}.{}", major, minor, build)
+}
+
+/// Parse natural language statement into formal predicates with the built-in rules
+/// of [`StatementParser`]
+pub fn parse_statement(text: &str, id: &str) -> Statement {
+    StatementParser::default().parse(text, id)
+}
+
+/// Parse text joining several independent clauses into one statement per clause with
+/// the built-in rules, see [`StatementParser::parse_all`]
+pub fn parse_statements(text: &str, base_id: &str) -> Vec<Statement> {
+    StatementParser::default().parse_all(text, base_id)
+}
+
+/// Plain-language account of a contradiction: each predicate that one of its
+/// statements asserts true and another asserts false, e.g. "`sorted(output)` is
+/// asserted true by stmt1 and false by stmt2.", one sentence per line. Only literals a
+/// statement asserts outright count, so a conflict that goes through disjunctions,
+/// quantifiers or arithmetic is reported as such instead.
+pub fn explain(contradiction: &Contradiction, statements: &[Statement]) -> String {
+    // Truth values asserted for each predicate key, with the statements asserting them
+    let mut asserted: Vec<(String, Vec<&str>, Vec<&str>)> = Vec::new();
+    for id in &contradiction.statements {
+        let Some(statement) = statements.iter().find(|statement| &statement.id == id) else {
+            continue;
+        };
+        if statement.quantifier.is_some() {
+            continue;
+        }
+        let mut literals = Vec::new();
+        asserted_literals(&statement.formula(), true, &mut literals);
+        for (key, value) in literals {
+            let index = match asserted.iter().position(|(known, _, _)| *known == key) {
+                Some(index) => index,
+                None => {
+                    asserted.push((key, Vec::new(), Vec::new()));
+                    asserted.len() - 1
+                }
+            };
+            let (_, truthy, falsy) = &mut asserted[index];
+            let ids = if value { truthy } else { falsy };
+            if !ids.contains(&id.as_str()) {
+                ids.push(id);
+            }
+        }
+    }
+
+    let clashes: Vec<String> = asserted
+        .iter()
+        .filter(|(_, truthy, falsy)| !truthy.is_empty() && !falsy.is_empty())
+        .map(|(key, truthy, falsy)| {
+            format!("`{}` is asserted true by {} and false by {}.", key, truthy.join(" and "), falsy.join(" and "))
+        })
+        .collect();
+    if clashes.is_empty() {
+        format!(
+            "No predicate is asserted both true and false outright by {}; the conflict follows from their \
+             connectives, quantifiers or arithmetic.",
+            contradiction.statements.join(", ")
+        )
+    } else {
+        clashes.join("\n")
+    }
+}
+
+/// Collect the predicate keys a formula forces to a truth value when it holds
+/// (`positive`) or fails, with that value
+fn asserted_literals(body: &StatementBody, positive: bool, literals: &mut Vec<(String, bool)>) {
+    match body {
+        StatementBody::Atom(predicate) => literals.push((predicate_key(predicate), positive != predicate.negated)),
+        StatementBody::Comparison { .. } => {}
+        StatementBody::Not(inner) => asserted_literals(inner, !positive, literals),
+        StatementBody::And(parts) if positive => {
+            parts.iter().for_each(|part| asserted_literals(part, true, literals));
+        }
+        StatementBody::Or(parts) if !positive => {
+            parts.iter().for_each(|part| asserted_literals(part, false, literals));
+        }
+        StatementBody::Implies(antecedent, consequent) if !positive => {
+            asserted_literals(antecedent, true, literals);
+            asserted_literals(consequent, false, literals);
+        }
+        StatementBody::ExactlyOne(parts) if positive && parts.len() == 1 => asserted_literals(&parts[0], true, literals),
+        StatementBody::And(_) | StatementBody::Or(_) | StatementBody::Implies(..) | StatementBody::ExactlyOne(_) => {}
+    }
+}
+
+#[cfg(all(test, feature = "z3"))]
+mod tests {
+    use super::*;
+    use z3::Config;
+
+    #[test]
+    fn test_custom_parser_rule() {
+        struct Overdrawn;
+
+        impl ParserRule for Overdrawn {
+            fn try_parse(&self, text: &str) -> Option<Vec<Predicate>> {
+                let account = text.strip_suffix(" is overdrawn")?;
+                Some(vec![Predicate { name: "<".to_string(), args: vec![account.to_string(), "0".to_string()], negated: false }])
+            }
+        }
+
+        let mut rules: Vec<Box<dyn ParserRule>> = vec![Box::new(Overdrawn)];
+        rules.extend(StatementParser::builtin_rules());
+        let parser = StatementParser::with_rules(rules);
+
+        let overdrawn = parser.parse("savings is overdrawn", "s1");
+        assert_eq!(overdrawn.predicates.len(), 1);
+        assert_eq!(overdrawn.predicates[0].name, "<");
+        assert_eq!(overdrawn.predicates[0].args, vec!["savings", "0"]);
+        // Without the rule, the categorical rule reads it as a property
+        assert_eq!(parse_statement("savings is overdrawn", "s1").predicates[0].name, "overdrawn");
+
+        // Sentences the custom rule does not recognize fall through to the built-ins
+        let statements = vec![overdrawn, parser.parse("savings is at least 10", "s2")];
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        assert_eq!(verifier.verify_statements(&statements).unwrap().status, VerificationStatus::Inconsistent);
+    }
+
+    #[test]
+    fn test_negation_words_flip_predicates() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let positive = parse_statement("AI systems contain contradictions", "s1");
+        for negated in ["AI systems do not contain contradictions", "AI systems never contain contradictions", "AI systems don't contain contradictions"] {
+            let negative = parse_statement(negated, "s2");
+            assert_eq!(negative.predicates.len(), 1, "{}", negated);
+            assert_eq!(negative.predicates[0].name, positive.predicates[0].name);
+            assert_ne!(negative.predicates[0].negated, positive.predicates[0].negated);
+            let result = verifier.verify_statements(&[positive.clone(), negative]).unwrap();
+            assert_eq!(result.status, VerificationStatus::Inconsistent);
+        }
+
+        let mortal = parse_statement("Socrates isn't mortal", "s3");
+        assert_eq!(mortal.predicates[0].name, "mortal");
+        assert!(mortal.predicates[0].negated);
+        let below = parse_statement("x is not less than 0", "s4");
+        assert_eq!(below.predicates[0].name, "<");
+        assert!(below.predicates[0].negated);
+
+        // A negation only reaches the predicates of its own clause
+        let scoped = parse_statement("We need coherent tools but we don't need validation", "s5");
+        let negated: Vec<(&str, bool)> = scoped.predicates.iter().map(|p| (p.name.as_str(), p.negated)).collect();
+        assert_eq!(negated, vec![("need_coherent_tools", false), ("need_validation", true)]);
+
+        // Rules that read the negation themselves keep their reading
+        let some_not = parse_statement("Some birds are not flyers", "s6");
+        assert!(some_not.quantifier.is_some());
+        assert!(some_not.predicates[0].negated);
+    }
+
+    #[test]
+    fn test_quantifier_words() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let all = parse_statement("All humans are mortal", "s1");
+        match &all.quantifier {
+            Some(Quantifier::ForAll { variables, domain }) => {
+                assert_eq!(variables, &vec!["x".to_string()]);
+                assert_eq!(domain[0].name, "human");
+            }
+            other => panic!("expected a universal, got {:?}", other),
+        }
+        assert_eq!(all.predicates[0].name, "mortal");
+        assert!(!all.predicates[0].negated);
+
+        for counterexample in [
+            "Some humans are not mortal",
+            "At least one human is not mortal",
+            "There exists a human who is not mortal",
+            "Not all humans are mortal",
+        ] {
+            let statement = parse_statement(counterexample, "s2");
+            assert!(matches!(statement.quantifier, Some(Quantifier::Exists { .. })), "{}", counterexample);
+            let result = verifier.verify_statements(&[all.clone(), statement]).unwrap();
+            assert_eq!(result.status, VerificationStatus::Inconsistent, "{}", counterexample);
+        }
+
+        // Every/each are universal, none is a negated universal; classes and properties
+        // may span several words
+        for universal in ["Every human is mortal", "Each human is a mortal"] {
+            let statement = parse_statement(universal, "s3");
+            assert!(matches!(statement.quantifier, Some(Quantifier::ForAll { .. })), "{}", universal);
+        }
+        let none = parse_statement("None of the AI systems are perfectly reliable", "s4");
+        assert!(matches!(&none.quantifier, Some(Quantifier::ForAll { domain, .. }) if domain[0].name == "ai_system"));
+        assert_eq!(none.predicates[0].name, "perfectly_reliable");
+        assert!(none.predicates[0].negated);
+
+        // "There exists" alone gives universals existential import
+        let exists = parse_statement("There exists a human", "s5");
+        assert!(exists.predicates.is_empty());
+        let no_mortals = parse_statement("No humans are mortal", "s6");
+        assert_eq!(verifier.verify_statements(&[all.clone(), no_mortals.clone()]).unwrap().status, VerificationStatus::Consistent);
+        assert_eq!(verifier.verify_statements(&[all, no_mortals, exists]).unwrap().status, VerificationStatus::Inconsistent);
+
+        // "There are no X" denies that any X exists rather than asserting a "no X"
+        let errors = parse_statement("There exists an error", "s7");
+        for denial in ["There are no errors", "There is no error", "There exists no error"] {
+            let statement = parse_statement(denial, "s8");
+            assert!(matches!(&statement.quantifier, Some(Quantifier::ForAll { domain, .. }) if domain.is_empty()), "{}", denial);
+            assert_eq!(statement.predicates[0].name, "error", "{}", denial);
+            assert!(statement.predicates[0].negated, "{}", denial);
+            let result = verifier.verify_statements(&[errors.clone(), statement]).unwrap();
+            assert_eq!(result.status, VerificationStatus::Inconsistent, "{}", denial);
+        }
+        let no_immortals = parse_statement("There is no human who is immortal", "s9");
+        let immortal = parse_statement("There exists a human who is immortal", "s10");
+        assert_eq!(verifier.verify_statements(&[no_immortals, immortal]).unwrap().status, VerificationStatus::Inconsistent);
+
+        // A negation word is never read as part of the class
+        let statement = parse_statement("There are not errors", "s11");
+        assert!(statement.quantifier.is_none() && statement.predicates.is_empty());
+    }
+
+    #[test]
+    fn test_compound_sentences_split_into_statements() {
+        let statements = parse_statements("Socrates is human and Plato is human", "facts");
+        let ids: Vec<&str> = statements.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["facts_1", "facts_2"]);
+        assert_eq!(statements[0].text, "Socrates is human");
+        assert_eq!(statements[1].predicates[0].args, vec!["plato"]);
+
+        let statements = parse_statements("x is at least 3; x is less than 5, but y equals x+1", "s");
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[2].predicates[0].name, "=");
+
+        // Conjunctions inside a predicate argument or quotes do not separate clauses
+        for single in ["We need coherent tools and validation", r#"Socrates is "human and Plato is human""#, "Socrates is human"] {
+            let statements = parse_statements(single, "s");
+            assert_eq!(statements.len(), 1, "{}", single);
+            assert_eq!(statements[0].id, "s");
+            assert_eq!(statements[0].text, single);
+        }
+    }
+
+    #[test]
+    fn test_smtlib_export() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = [
+            parse_statement("All humans are mortal", "rule"),
+            parse_statement("Socrates is human", "fact"),
+            parse_statement("x is at least 5", "bound"),
+        ];
+        let smtlib = verifier.to_smtlib(&statements).unwrap();
+        assert!(smtlib.contains("(declare-fun human (Object) Bool)"), "{}", smtlib);
+        assert!(smtlib.contains("(declare-fun socrates () Object)"), "{}", smtlib);
+        assert!(smtlib.contains("(declare-fun x () Int)"), "{}", smtlib);
+        assert_eq!(smtlib.matches("(assert").count(), 3, "{}", smtlib);
+        assert!(smtlib.contains("(assert (human socrates))"), "{}", smtlib);
+        assert!(smtlib.contains("(assert (>= x 5))"), "{}", smtlib);
+
+        // Exporting leaves nothing behind for the next check
+        let result = verifier.verify_statements(&[parse_statement("Socrates is human", "fact")]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_smtlib_import() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let unsat = std::env::temp_dir().join(format!("coherence-{}-unsat.smt2", std::process::id()));
+        std::fs::write(&unsat, "(declare-const x Int)\n(assert (> x 5))\n(assert (< x 3))\n(check-sat)\n").unwrap();
+        let result = verifier.check_smtlib(&std::fs::read_to_string(&unsat).unwrap()).unwrap();
+        std::fs::remove_file(unsat).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+
+        let result = verifier.check_smtlib("(declare-fun p () Bool) (assert p)").unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+
+        // The export reads back in
+        let exported = verifier.to_smtlib(&[parse_statement("Socrates is human", "s1"), parse_statement("Socrates is not human", "s2")]).unwrap();
+        assert_eq!(verifier.check_smtlib(&exported).unwrap().status, VerificationStatus::Inconsistent);
+
+        let error = verifier.check_smtlib("(assert (and p").unwrap_err().to_string();
+        assert!(error.contains("Malformed SMT-LIB2") && error.contains("line 1 column"), "{}", error);
+        assert!(verifier.check_smtlib("(assert p)\0").is_err());
+    }
+
+    #[test]
+    fn test_formal_syntax() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let conjunction = parse_formal("~sorted(output) & reversed(output)", "c").unwrap();
+        let atoms: Vec<(&str, bool)> = conjunction.predicates.iter().map(|p| (p.name.as_str(), p.negated)).collect();
+        assert_eq!(atoms, vec![("sorted", true), ("reversed", false)]);
+        assert!(conjunction.body.is_none());
+        let sorted = parse_formal("sorted(output)", "s").unwrap();
+        let result = verifier.verify_statements(&[conjunction, sorted]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+
+        let rule = parse_formal("forall x: human(x) -> mortal(x)", "rule").unwrap();
+        assert!(matches!(rule.quantifier, Some(Quantifier::ForAll { ref variables, .. }) if variables == &["x"]));
+        assert!(matches!(rule.body, Some(StatementBody::Implies(..))));
+        let fact = parse_formal("human(socrates)", "fact").unwrap();
+        let conclusion = parse_formal("mortal(socrates)", "c").unwrap();
+        assert!(verifier.verify_reasoning(&[rule, fact], &conclusion).unwrap().is_valid);
+
+        // `->` groups to the right and binds looser than `|`
+        let nested = parse_formal("p | q -> r -> s", "n").unwrap();
+        let Some(StatementBody::Implies(antecedent, consequent)) = nested.body else { panic!("{:?}", nested.body) };
+        assert!(matches!(*antecedent, StatementBody::Or(_)));
+        assert!(matches!(*consequent, StatementBody::Implies(..)));
+
+        let error = parse_formal("human(x) & -> mortal(x)", "bad").unwrap_err().to_string();
+        assert!(error.contains("position 11"), "{}", error);
+        let error = parse_formal("forall x human(x)", "bad").unwrap_err().to_string();
+        assert!(error.contains("Expected ':'") && error.contains("position 9"), "{}", error);
+        let error = parse_formal("human(x", "bad").unwrap_err().to_string();
+        assert!(error.contains("end of input at position 7"), "{}", error);
+        assert!(parse_formal("human(x) $ mortal(x)", "bad").unwrap_err().to_string().contains("position 9"));
+    }
+
+    #[test]
+    fn test_conditionals_support_modus_ponens() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let mut reason = |premises: &[&str], conclusion: &str| {
+            let premises: Vec<Statement> = premises.iter().enumerate().map(|(i, p)| parse_statement(p, &format!("p{}", i))).collect();
+            verifier.verify_reasoning(&premises, &parse_statement(conclusion, "c")).unwrap().is_valid
+        };
+
+        let rule = parse_statement("If Socrates is human then Socrates is mortal", "rule");
+        assert!(matches!(rule.body, Some(StatementBody::Implies(..))));
+        assert!(reason(&["If Socrates is human then Socrates is mortal", "Socrates is human"], "Socrates is mortal"));
+        // Affirming the consequent is not valid
+        assert!(!reason(&["If Socrates is human then Socrates is mortal", "Socrates is mortal"], "Socrates is human"));
+
+        // Conjunctions stay inside the antecedent
+        let both = ["If Socrates is human and Socrates is wise, Socrates is mortal", "Socrates is human"];
+        assert_eq!(parse_statements(both[0], "s").len(), 1);
+        assert!(!reason(&both, "Socrates is mortal"));
+        assert!(reason(&[both[0], both[1], "Socrates is wise"], "Socrates is mortal"));
+
+        assert!(reason(&["If a tool is coherent then it is validated", "hammer is a tool", "hammer is coherent"], "hammer is validated"));
+        assert!(reason(&["x is at least 5 implies y is at least 3", "x equals 7"], "y is at least 3"));
+        assert!(reason(&["We need coherent tools", "Coherent tools require validation"], "We need validation"));
+    }
+
+    #[test]
+    fn test_contradiction_detection() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let stmt1 = parse_statement("All AI systems are perfectly logical", "stmt1");
+        let stmt2 = parse_statement("Current AI systems contain contradictions", "stmt2");
+
+        let result = verifier.verify_statements(&[stmt1, stmt2]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+    }
+
+    #[test]
+    fn test_valid_reasoning() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let premise1 = parse_statement("All humans are mortal", "p1");
+        let premise2 = parse_statement("Socrates is human", "p2");
+        let conclusion = parse_statement("Socrates is mortal", "c1");
+
+        let result = verifier.verify_reasoning(&[premise1.clone(), premise2], &conclusion).unwrap();
+        assert!(result.is_valid);
+
+        // Without the ground fact the universal premise says nothing about Socrates
+        let result = verifier.verify_reasoning(&[premise1], &conclusion).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.counterexample.unwrap().get("mortal(socrates)"), Some(&false));
+    }
+
+    #[test]
+    fn test_predicates_share_arguments() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let fact = |id: &str, name: &str, arg: &str| Statement {
+            id: id.to_string(),
+            text: String::new(),
+            predicates: vec![Predicate { name: name.to_string(), args: vec![arg.to_string()], negated: false }],
+            body: None,
+            quantifier: None,
+            weight: None,
+        };
+
+        // All humans are mortal: ∀x human(x) → mortal(x), over the object sort
+        verifier.reset();
+        let x = FuncDecl::new(&ctx, "x", &[], &verifier.object_sort).apply(&[]);
+        let human = verifier.predicate_function("human", &[ArgType::Object]).apply(&[&x]).as_bool().unwrap();
+        let mortal = verifier.predicate_function("mortal", &[ArgType::Object]).apply(&[&x]).as_bool().unwrap();
+        let rule = z3::ast::forall_const(&ctx, &[&x], &[], &human.implies(&mortal));
+        verifier.solver.assert(&rule);
+
+        verifier.push_statements(&[fact("p2", "human", "socrates")]).unwrap();
+        let result = verifier.entails(&fact("c1", "mortal", "socrates")).unwrap();
+        assert!(result.is_valid);
+
+        // The rule says nothing about plato, who is not known to be human
+        let result = verifier.entails(&fact("c2", "mortal", "plato")).unwrap();
+        assert!(!result.is_valid);
+        let counterexample = result.counterexample.unwrap();
+        assert_eq!(counterexample.get("mortal(plato)"), Some(&false));
+    }
+
+    #[test]
+    fn test_existential_contradicts_universal() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let all = parse_statement("All birds are flyers", "s1");
+        let some_not = parse_statement("Some birds are not flyers", "s2");
+        assert!(matches!(some_not.quantifier, Some(Quantifier::Exists { .. })));
+
+        let result = verifier.verify_statements(&[all.clone(), some_not]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions[0].statements, vec!["s1", "s2"]);
+
+        // Opposite universals hold vacuously while no bird exists...
+        let none = parse_statement("No birds are flyers", "s3");
+        let result = verifier.verify_statements(&[all.clone(), none.clone()]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+
+        // ...and clash once one does
+        let some = parse_statement("Some birds are happy", "s4");
+        let result = verifier.verify_statements(&[all, none, some]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+    }
+
+    #[test]
+    fn test_modus_ponens() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let atom = |name: &str| StatementBody::Atom(Predicate {
+            name: name.to_string(),
+            args: vec!["tools".to_string()],
+            negated: false,
+        });
+        let statement = |id: &str, body: StatementBody| Statement {
+            id: id.to_string(),
+            text: String::new(),
+            predicates: vec![],
+            body: Some(body),
+            quantifier: None,
+            weight: None,
+        };
+
+        // P, P → Q ⊢ Q
+        let p = statement("p", atom("coherent"));
+        let rule = statement("rule", StatementBody::Implies(Box::new(atom("coherent")), Box::new(atom("validated"))));
+        let q = statement("q", atom("validated"));
+        let result = verifier.verify_reasoning(&[p.clone(), rule.clone()], &q).unwrap();
+        assert!(result.is_valid);
+
+        // Affirming the consequent is not valid: Q, P → Q ⊬ P
+        let result = verifier.verify_reasoning(&[q.clone(), rule.clone()], &p).unwrap();
+        assert!(!result.is_valid);
+
+        // P, P → Q and ¬Q cannot all hold
+        let not_q = statement("not_q", StatementBody::Not(Box::new(atom("validated"))));
+        let result = verifier.verify_statements(&[p, rule, not_q]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions[0].statements, vec!["p", "rule", "not_q"]);
+
+        // Bodies round-trip through JSON
+        let json = serde_json::to_string(&q).unwrap();
+        let parsed: Statement = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed.body, Some(StatementBody::Atom(_))));
+    }
+
+    #[test]
+    fn test_disjunction() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let atom = |name: &str, negated: bool| StatementBody::Atom(Predicate {
+            name: name.to_string(),
+            args: vec!["input".to_string()],
+            negated,
+        });
+        let statement = |id: &str, body: StatementBody| Statement {
+            id: id.to_string(),
+            text: String::new(),
+            predicates: vec![],
+            body: Some(body),
+            quantifier: None,
+            weight: None,
+        };
+
+        // Either empty or sorted; not empty; so sorted
+        let either = parse_statement("The input is either empty or sorted", "either");
+        assert!(matches!(&either.body, Some(StatementBody::Or(alternatives)) if alternatives.len() == 2));
+        let not_empty = statement("not_empty", atom("empty", true));
+        let sorted = statement("sorted", atom("sorted", false));
+        assert!(verifier.verify_reasoning(&[either.clone(), not_empty.clone()], &sorted).unwrap().is_valid);
+        assert!(!verifier.verify_reasoning(std::slice::from_ref(&either), &sorted).unwrap().is_valid);
+
+        // Neither alternative can hold
+        let not_sorted = statement("not_sorted", atom("sorted", true));
+        let result = verifier.verify_statements(&[either.clone(), not_empty.clone(), not_sorted.clone()]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+
+        // A single alternative behaves like the bare atom
+        let single = statement("single", StatementBody::Or(vec![atom("sorted", false)]));
+        let result = verifier.verify_statements(&[single.clone(), not_sorted]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert!(verifier.verify_reasoning(std::slice::from_ref(&single), &sorted).unwrap().is_valid);
+        assert!(verifier.verify_reasoning(&[sorted], &single).unwrap().is_valid);
+
+        // The empty disjunction is false, so it contradicts itself
+        let empty = statement("empty", StatementBody::Or(vec![]));
+        let result = verifier.verify_statements(&[not_empty, empty]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions[0].statements, vec!["empty"]);
+
+        // Disjunctions round-trip through JSON
+        let json = serde_json::to_string(&either).unwrap();
+        let parsed: Statement = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        assert!(json.contains(r#""or":[{"atom":{"name":"empty""#));
+    }
+
+    #[test]
+    fn test_typed_arguments() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut options = VerifyOptions::default();
+        options.signatures.insert("positive".to_string(), vec![ArgType::Int]);
+        let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+
+        let statements = vec![
+            parse_statement("x is at least 0", "s1"),
+            parse_statement("x is less than 0", "s2"),
+        ];
+        assert_eq!(verifier.verify_statements(&statements).unwrap().status, VerificationStatus::Inconsistent);
+
+        // positive(x+1) and ¬positive(y) force x+1 ≠ y, so they clash with y = x+1
+        let literal = |id: &str, arg: &str, negated: bool| Statement {
+            id: id.to_string(),
+            text: String::new(),
+            predicates: vec![Predicate { name: "positive".to_string(), args: vec![arg.to_string()], negated }],
+            body: None,
+            quantifier: None,
+            weight: None,
+        };
+        let mut statements = vec![
+            literal("s1", "x+1", false),
+            literal("s2", "y", true),
+            parse_statement("x+1 is greater than y", "s3"),
+        ];
+        assert_eq!(verifier.verify_statements(&statements).unwrap().status, VerificationStatus::Consistent);
+        statements[2] = parse_statement("y equals x+1", "s3");
+        assert_eq!(verifier.verify_statements(&statements).unwrap().status, VerificationStatus::Inconsistent);
+
+        // The declared arity is enforced
+        let wrong_arity = Statement {
+            id: "s4".to_string(),
+            text: String::new(),
+            predicates: vec![Predicate { name: "positive".to_string(), args: vec![], negated: false }],
+            body: None,
+            quantifier: None,
+            weight: None,
+        };
+        assert!(verifier.verify_statements(&[wrong_arity]).is_err());
+    }
+
+    #[test]
+    fn test_comparison_body() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let comparison = |id: &str, text: &str| {
+            let (lhs, op, rhs) = arithmetic::parse_comparison(text).unwrap();
+            Statement {
+                id: id.to_string(),
+                text: text.to_string(),
+                predicates: vec![],
+                body: Some(StatementBody::Comparison { lhs, op, rhs }),
+                quantifier: None,
+                weight: None,
+            }
+        };
+
+        // A non-negative input cannot make -x positive
+        let statements = vec![comparison("pre", "x >= 0"), comparison("post", "-x > 0"), comparison("x", "x > 0")];
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions[0].statements, vec!["pre", "post"]);
+
+        let result = verifier.verify_statements(&statements[1..2]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        assert_eq!(result.per_statement_witness.unwrap()[0].1, vec![("-x > 0".to_string(), true)]);
+
+        // Terms and operators serialize as text and read back unchanged
+        let statement = comparison("s", "2*(x - y) - (z + 1) != -0.25*w");
+        let json = serde_json::to_string(&statement).unwrap();
+        assert!(json.contains(r#""comparison":{"lhs":"2*(x - y) - (z + 1)","op":"!=","rhs":"-0.25*w"}"#));
+        let parsed: Statement = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        assert!(serde_json::from_str::<Statement>(&json.replace("!=", "=>")).is_err());
+    }
+
+    #[test]
+    fn test_object_equality() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        // Equality is transitive: a = b and b = c entail a = c
+        let chain = vec![
+            parse_statement("a is the same as b", "s1"),
+            parse_statement("b is the same as c", "s2"),
+        ];
+        let result = verifier.verify_statements(&chain).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        let a_is_c = parse_statement("a is the same as c", "c1");
+        assert!(verifier.verify_reasoning(&chain, &a_is_c).unwrap().is_valid);
+
+        // ...so it clashes with distinct(a, c)
+        let mut statements = chain.clone();
+        statements.push(parse_statement("a is different from c", "s3"));
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions[0].statements, vec!["s1", "s2", "s3"]);
+
+        // Equal objects satisfy the same predicates
+        let premises = vec![
+            parse_statement("Socrates is the same as philosopher", "p1"),
+            parse_statement("Socrates is human", "p2"),
+        ];
+        let conclusion = parse_statement("Philosopher is human", "c2");
+        assert!(verifier.verify_reasoning(&premises, &conclusion).unwrap().is_valid);
+    }
+
+    #[test]
+    fn test_discover_equivalences() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let pred = |name: &str, negated: bool| Predicate {
+            name: name.to_string(),
+            args: vec!["x".to_string()],
+            negated,
+        };
+        let stmt1 = Statement { id: "s1".to_string(), text: String::new(), predicates: vec![pred("p", false)], body: None, quantifier: None, weight: None };
+        let stmt2 = Statement { id: "s2".to_string(), text: String::new(), predicates: vec![pred("q", false), pred("r", true)], body: None, quantifier: None, weight: None };
+
+        let equivalences = verifier.discover_equivalences(&[stmt1, stmt2]).unwrap();
+        assert_eq!(equivalences, vec![("p(x)".to_string(), "q(x)".to_string())]);
+    }
+
+    #[test]
+    fn test_transitive_numeric_cycle() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = vec![
+            parse_statement("A is at least B+5", "s1"),
+            parse_statement("B is at least C+5", "s2"),
+            parse_statement("C is at least A", "s3"),
+            parse_statement("D is at least 0", "s4"),
+        ];
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions.len(), 1);
+        assert_eq!(result.contradictions[0].statements, vec!["s1", "s2", "s3"]);
+
+        let result = verifier.verify_statements(&statements[..2]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_numeric_sort_changes_verdict() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+
+        // A value strictly between 0 and 1 exists over the reals but not the integers
+        let statements = vec![
+            parse_statement("x is greater than 0", "s1"),
+            parse_statement("x is less than 1", "s2"),
+        ];
+
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        assert_eq!(verifier.verify_statements(&statements).unwrap().status, VerificationStatus::Inconsistent);
+
+        let options = VerifyOptions { numeric_sort: NumericSort::Real, ..Default::default() };
+        let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+        assert_eq!(verifier.verify_statements(&statements).unwrap().status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_verification_report() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = vec![
+            parse_statement("All AI systems are perfectly logical", "s1"),
+            parse_statement("The weather seems nice", "s2"),
+            parse_statement("Current AI systems contain contradictions", "s3"),
+            parse_statement("x is greater than 0", "s4"),
+            parse_statement("x is less than 0", "s5"),
+        ];
+
+        let report = verifier.verify_report(&statements, &VerifyOptions::default()).unwrap();
+        assert_eq!(report.status, VerificationStatus::Inconsistent);
+        assert!(!report.unsat_core.is_empty());
+        assert_eq!(report.clusters, vec![vec!["s1", "s3"], vec!["s4", "s5"]]);
+        assert_eq!(report.unparsed_statements, vec!["s2"]);
+        assert_eq!(report.contradictions.len(), 2);
+    }
+
+    #[test]
+    fn test_probabilities_must_sum_to_one() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let mut spec: probability::ProbabilitySpec = serde_json::from_str(
+            r#"{"probabilities": {"rain": 0.7, "no rain": 0.2}, "partitions": [["rain", "no rain"]]}"#,
+        ).unwrap();
+        let report = verifier.verify_probabilities(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Inconsistent);
+        assert_eq!(report.contradictions[0].statements, vec!["P(no rain)", "P(rain)", "partition_0"]);
+
+        spec.probabilities.insert("no rain".to_string(), 0.3);
+        let report = verifier.verify_probabilities(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Consistent);
+
+        spec.probabilities.insert("no rain".to_string(), 1.2);
+        spec.partitions.clear();
+        let report = verifier.verify_probabilities(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Inconsistent);
+    }
+
+    #[test]
+    fn test_probability_events_keep_distinct_variables() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let spec: probability::ProbabilitySpec = serde_json::from_str(
+            r#"{"probabilities": {"no rain": 0.7, "no_rain": 0.2}}"#,
+        ).unwrap();
+        let report = verifier.verify_probabilities(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_probabilities_with_long_decimals_are_exact() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        // 1e-20 prints with 20 decimal places, more than an i64 denominator holds
+        let mut spec: probability::ProbabilitySpec = serde_json::from_str(
+            r#"{"probabilities": {"meteor": 1e-20, "clear": 1.0}, "partitions": [["meteor", "clear"]]}"#,
+        ).unwrap();
+        let report = verifier.verify_probabilities(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Inconsistent);
+
+        spec.probabilities.insert("clear".to_string(), 0.99999999999999);
+        spec.probabilities.insert("meteor".to_string(), 1e-14);
+        let report = verifier.verify_probabilities(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_knowledge_base_commits_and_rolls_back() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut kb = knowledge_base::KnowledgeBase::new(&ctx);
+
+        let logical = parse_statement("All AI systems are perfectly logical", "s1");
+        let nice = parse_statement("x is greater than 0", "s2");
+        let flawed = parse_statement("Current AI systems contain contradictions", "s3");
+
+        assert!(kb.try_add(logical).unwrap().is_ok());
+        assert!(kb.try_add(nice).unwrap().is_ok());
+        assert!(kb.would_conflict(&flawed).unwrap().is_some());
+        assert_eq!(kb.statements().len(), 2);
+
+        let conflict = kb.try_add(flawed).unwrap().unwrap_err();
+        assert_eq!(conflict.statement, "s3");
+        assert_eq!(conflict.conflicting, vec!["s1"]);
+        assert_eq!(kb.statements().len(), 2);
+
+        // The rejected statement left nothing behind
+        let compatible = parse_statement("x is less than 5", "s4");
+        assert!(kb.try_add(compatible).unwrap().is_ok());
+        assert_eq!(kb.statements().len(), 3);
+    }
+
+    #[test]
+    fn test_per_statement_witness() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = vec![
+            parse_statement("No AI systems exist", "s1"),
+            parse_statement("x is at least 3", "s2"),
+        ];
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        let witness = result.per_statement_witness.unwrap();
+        assert_eq!(witness, vec![
+            ("s1".to_string(), vec![("ai_systems_exist(ai_systems)".to_string(), false)]),
+            ("s2".to_string(), vec![("x >= 3".to_string(), true)]),
+        ]);
+
+        let statements = vec![
+            parse_statement("x is at least 3", "s1"),
+            parse_statement("x is less than 3", "s2"),
+        ];
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert!(result.per_statement_witness.is_none());
+    }
+
+    #[test]
+    fn test_reporter_formats() {
+        use crate::reporter::{Format, Level, ReportEntry, Style};
+
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = vec![
+            parse_statement("x is at least 3", "s1"),
+            parse_statement("x is less than 3", "s2"),
+        ];
+        let result = verifier.verify_statements(&statements).unwrap();
+        let entry = ReportEntry::from_consistency("bounds", &result).with_statements(&statements);
+
+        let render = |format: Format| {
+            let mut out = Vec::new();
+            let mut reporter = format.reporter("coherence", "0.1.0", Style::DECORATED);
+            reporter.entry(&mut out, &entry).unwrap();
+            reporter.finish(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert!(render(Format::Human).starts_with("❌ INCONSISTENT"));
+
+        let json: serde_json::Value = serde_json::from_str(&render(Format::Json)).unwrap();
+        assert_eq!(json[0]["status"], "Inconsistent");
+
+        let sarif: serde_json::Value = serde_json::from_str(&render(Format::Sarif)).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["runs"][0]["results"][0]["ruleId"], "contradiction");
+        assert_eq!(sarif["runs"][0]["results"][0]["level"], "error");
+        assert_eq!(sarif["runs"][0]["tool"]["driver"]["version"], "0.1.0");
+
+        let junit = render(Format::Junit);
+        assert!(junit.contains(r#"tests="1" failures="1""#));
+        assert!(junit.contains("<failure"));
+
+        // Built-in test cases fail on a verdict other than the expected one
+        let mut reporter = Format::Junit.reporter("coherence", "0.1.0", Style::PLAIN);
+        let mut out = Vec::new();
+        for (expected, actual) in [
+            (VerificationStatus::Inconsistent, VerificationStatus::Inconsistent),
+            (VerificationStatus::Consistent, VerificationStatus::Inconsistent),
+        ] {
+            reporter.entry(&mut out, &ReportEntry::test_case("case", expected, actual)).unwrap();
+        }
+        reporter.finish(&mut out).unwrap();
+        let junit = String::from_utf8(out).unwrap();
+        assert!(junit.contains(r#"tests="2" failures="1""#), "{}", junit);
+        assert!(junit.contains(r#"<failure message="FAIL: Expected CONSISTENT, got INCONSISTENT">"#), "{}", junit);
+
+        assert_eq!(render(Format::Quiet), "1 checked: 0 passed, 1 failed, 0 unknown\n");
+
+        let summarize = |format: Format| {
+            let mut out = Vec::new();
+            let mut reporter = format.summary_reporter("consistent", "contradictions");
+            reporter.section(&mut out, "Problem bounds:", &["x is at least 3".to_string()]).unwrap();
+            reporter.entry(&mut out, &entry).unwrap();
+            reporter.finish(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(summarize(Format::Human), "0/1 consistent, 1 contradictions\n");
+        assert_eq!(
+            summarize(Format::Json),
+            "{\"checked\":1,\"contradictions\":1,\"failed\":1,\"passed\":0,\"unknown\":0}\n",
+        );
+
+        // Warnings are not counted as contradictions
+        let mut warned = entry.clone();
+        warned.status = VerificationStatus::Consistent;
+        warned.findings.iter_mut().for_each(|finding| finding.level = Level::Warning);
+        let mut out = Vec::new();
+        let mut reporter = Format::Human.summary_reporter("consistent", "contradictions");
+        reporter.entry(&mut out, &warned).unwrap();
+        reporter.finish(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "1/1 consistent, 0 contradictions\n");
+
+        let markdown = render(Format::Markdown);
+        assert!(markdown.starts_with("# coherence report\n\n**Summary:** 1 checked: 0 passed, 1 failed"), "{}", markdown);
+        assert!(markdown.contains("| s1 | x is at least 3 | `x >= 3` |"), "{}", markdown);
+        assert!(markdown.contains("\n## Contradictions\n\n### bounds\n"), "{}", markdown);
+        assert!(markdown.contains("- **Contradiction**: s1 ↔ s2"), "{}", markdown);
+        assert!(!markdown.contains('\x1b'));
+        assert_eq!(markdown, render(Format::Markdown));
+
+        use crate::reporter::exit_code;
+        assert_eq!(exit_code([VerificationStatus::Consistent]), 0);
+        assert_eq!(exit_code([VerificationStatus::Consistent, VerificationStatus::Unknown]), 2);
+        assert_eq!(exit_code([VerificationStatus::Unknown, VerificationStatus::Inconsistent]), 1);
+        assert_eq!(exit_code([]), 0);
+    }
+
+    #[test]
+    fn test_relation_properties() {
+        use crate::relations::{Edge, RelationProperty, RelationSpec};
+
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let edge = |relation: &str, from: &str, to: &str, holds: bool| Edge {
+            relation: relation.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            holds,
+        };
+
+        // A three-edge cycle in an acyclic relation is reported as a chain
+        let mut spec = RelationSpec::default();
+        spec.relations.insert("parent_of".to_string(), vec![RelationProperty::Acyclic]);
+        spec.edges = vec![
+            edge("parent_of", "a", "b", true),
+            edge("parent_of", "b", "c", true),
+            edge("parent_of", "c", "a", true),
+        ];
+        let report = verifier.verify_relations(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Inconsistent);
+        assert_eq!(report.contradictions[0].statements.len(), 3);
+
+        // Without the declaration the same edges are fine
+        spec.relations.clear();
+        assert_eq!(verifier.verify_relations(&spec).unwrap().status, VerificationStatus::Consistent);
+
+        // Denying the reverse of a symmetric edge conflicts with it
+        spec.relations.insert("sibling_of".to_string(), vec![RelationProperty::Symmetric]);
+        spec.edges = vec![edge("sibling_of", "b", "c", true), edge("sibling_of", "c", "b", false)];
+        let report = verifier.verify_relations(&spec).unwrap();
+        assert_eq!(report.contradictions[0].statements, vec!["sibling_of(b, c)", "¬sibling_of(c, b)"]);
+
+        // Transitivity derives the denied edge from a path
+        spec.relations.insert("ancestor_of".to_string(), vec![RelationProperty::Transitive]);
+        spec.edges = vec![
+            edge("ancestor_of", "a", "b", true),
+            edge("ancestor_of", "b", "c", true),
+            edge("ancestor_of", "a", "c", false),
+        ];
+        let report = verifier.verify_relations(&spec).unwrap();
+        assert_eq!(report.status, VerificationStatus::Inconsistent);
+        assert_eq!(report.unsat_core.len(), 3);
+    }
+
+    #[test]
+    fn test_total_deadline_truncates_analysis() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+
+        // Hundreds of disjoint contradictions: one core extraction and re-check each
+        let mut statements = Vec::new();
+        for i in 0..500 {
+            statements.push(parse_statement(&format!("x{} is at least {}", i, i), &format!("lo{}", i)));
+            statements.push(parse_statement(&format!("x{} is less than {}", i, i), &format!("hi{}", i)));
+        }
+
+        // A deadline that has already passed: whichever check runs first is the last
+        let options = VerifyOptions {
+            total_deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert!(result.truncated);
+        match result.status {
+            VerificationStatus::Inconsistent => assert!(result.contradictions.len() < 500),
+            VerificationStatus::Unknown => assert!(result.unknown_reason.is_some()),
+            VerificationStatus::Consistent => panic!("contradictory statements reported consistent"),
+        }
+
+        // Without a deadline the same problem is analysed in full
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let result = verifier.verify_statements(&statements[..10]).unwrap();
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_find_all_muses() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = vec![
+            parse_statement("The weather is nice", "f1"),
+            parse_statement("x is at least 0", "x1"),
+            parse_statement("z is at least 1", "f2"),
+            parse_statement("y is greater than 5", "y1"),
+            parse_statement("x is less than 0", "x2"),
+            parse_statement("z is less than 10", "f3"),
+            parse_statement("y is less than 3", "y2"),
+        ];
+        let enumeration = verifier.find_all_muses(&statements).unwrap();
+        assert_eq!(enumeration.muses, vec![vec!["x1", "x2"], vec!["y1", "y2"]]);
+        assert!(!enumeration.truncated);
+
+        assert!(verifier.find_all_muses(&statements[..4]).unwrap().muses.is_empty());
+
+        // Out of time before the first subset: nothing found, and said so
+        let options = VerifyOptions {
+            total_deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+        let enumeration = verifier.find_all_muses(&statements).unwrap();
+        assert!(enumeration.muses.is_empty());
+        assert!(enumeration.truncated);
+    }
+
+    #[test]
+    fn test_max_consistent_subset() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let mut statements = vec![
+            parse_statement("x is at least 5", "s1"),
+            parse_statement("x is less than 3", "s2"),
+            parse_statement("x is greater than 4", "s3"),
+        ];
+        assert_eq!(verifier.max_consistent_subset(&statements).unwrap(), vec!["s1", "s3"]);
+
+        // Enough weight on the odd one out outweighs the other two together
+        statements[1].weight = Some(2.5);
+        assert_eq!(verifier.max_consistent_subset(&statements).unwrap(), vec!["s2"]);
+    }
+
+    #[test]
+    fn test_confidence_is_retained_fraction() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let mut statements: Vec<Statement> = ["a", "b", "c", "d", "e", "f", "g", "h", "p", "~p"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| parse_formal(text, &format!("s{}", i)).unwrap())
+            .collect();
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert!((result.confidence - 0.9).abs() < 1e-9, "{}", result.confidence);
+        let report = verifier.verify_report(&statements, &VerifyOptions::default()).unwrap();
+        assert!((report.confidence - 0.9).abs() < 1e-9, "{}", report.confidence);
+        let result = propositional::PropositionalVerifier::new().verify_statements(&statements).unwrap();
+        assert!((result.confidence - 0.9).abs() < 1e-9, "{}", result.confidence);
+
+        statements.truncate(9);
+        assert_eq!(verifier.verify_statements(&statements).unwrap().confidence, 1.0);
+
+        // Quantified statements are graded without the optimizer
+        let syllogism = [
+            parse_statement("All humans are mortal", "s1"),
+            parse_statement("Socrates is a human", "s2"),
+            parse_statement("Socrates is not mortal", "s3"),
+        ];
+        assert!(syllogism[0].quantifier.is_some());
+        let result = verifier.verify_statements(&syllogism).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert!((result.confidence - 2.0 / 3.0).abs() < 1e-9, "{}", result.confidence);
+    }
+
+    #[test]
+    fn test_explain_names_clashing_predicate() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let statements = vec![
+            parse_formal("sorted(output) & reversed(output)", "stmt1").unwrap(),
+            parse_formal("~(sorted(output) | empty(output))", "stmt2").unwrap(),
+            parse_formal("p | q", "stmt3").unwrap(),
+            parse_formal("~p", "stmt4").unwrap(),
+            parse_formal("~q", "stmt5").unwrap(),
+        ];
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.contradictions.len(), 2);
+
+        assert_eq!(
+            explain(&result.contradictions[0], &statements),
+            "`sorted(output)` is asserted true by stmt1 and false by stmt2."
+        );
+        // The second conflict needs the disjunction, so no literal clashes directly
+        let explanation = explain(&result.contradictions[1], &statements);
+        assert!(explanation.starts_with("No predicate is asserted both true and false outright by stmt3, stmt4, stmt5"), "{}", explanation);
+    }
+
+    #[test]
+    fn test_contradictions_to_dot() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let statements = vec![
+            parse_statement("Socrates is mortal", "s1"),
+            parse_statement("Plato is wise", "s2"),
+            parse_statement("Socrates is not mortal", "s3"),
+        ];
+        let result = verifier.verify_statements(&statements).unwrap();
+
+        let dot = reporter::contradictions_to_dot(&result.contradictions);
+        assert!(dot.starts_with("digraph contradictions {"), "{}", dot);
+        assert!(dot.contains("\"s1\" -> \"s3\" [label=\"Statements are mutually exclusive\"];"), "{}", dot);
+        assert!(!dot.contains("\"s2\""), "{}", dot);
+    }
+
+    #[test]
+    fn test_check_under_assumptions() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = vec![
+            parse_statement("x is at least 0", "s1"),
+            parse_statement("y is at least 0", "s2"),
+        ];
+        let hypothesis = parse_statement("x is less than 0", "h1");
+
+        let result = verifier.check_under_assumptions(&statements, std::slice::from_ref(&hypothesis)).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions[0].statements, vec!["s1", "h1"]);
+
+        // Retracting the hypothesis restores consistency without re-asserting anything
+        let result = verifier.check_pushed_under_assumptions(&[]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        let result = verifier.check_pushed_under_assumptions(&[parse_statement("y is less than 5", "h2")]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        let result = verifier.check_pushed_under_assumptions(std::slice::from_ref(&hypothesis)).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+
+        let result = verifier.check_under_assumptions(&statements, &[]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_proof_term() {
+        let statements = vec![
+            parse_statement("x is at least 0", "s1"),
+            parse_statement("x is less than 0", "s2"),
+        ];
+
+        let options = VerifyOptions { proofs: true, ..Default::default() };
+        let cfg = options.config();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        let proof = result.proof.unwrap();
+        assert!(proof.starts_with('('), "not a proof term: {}", proof);
+        assert_ne!(proof, "Z3 proved unsatisfiability");
+
+        let premises = vec![
+            parse_statement("All humans are mortal", "p1"),
+            parse_statement("Socrates is human", "p2"),
+        ];
+        let conclusion = parse_statement("Socrates is mortal", "c1");
+        let result = verifier.verify_reasoning(&premises, &conclusion).unwrap();
+        assert!(result.is_valid);
+        assert!(result.proof.unwrap().starts_with('('));
+    }
+
+    #[test]
+    fn test_timeout_is_reported() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::with_timeout(&ctx, Duration::from_millis(50));
+
+        // Factoring a large semiprime: nonlinear and far too slow for a 50ms budget
+        let comparison = |op: &str, lhs: &str, rhs: &str| Predicate {
+            name: op.to_string(),
+            args: vec![lhs.to_string(), rhs.to_string()],
+            negated: false,
+        };
+        let statements = vec![Statement {
+            id: "factor".to_string(),
+            text: "x*y = 1000000016000000063".to_string(),
+            predicates: vec![
+                comparison("=", "x*y", "1000000016000000063"),
+                comparison(">=", "x", "2"),
+                comparison(">=", "y", "2"),
+            ],
+            body: None,
+            quantifier: None,
+            weight: None,
+        }];
+
+        let start = Instant::now();
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(result.proof.is_none());
+        assert_eq!(result.confidence, 0.0);
+        assert_eq!(result.unknown_reason, Some(UnknownReason::Timeout));
+
+        // Unknown is its own verdict, not a contradiction
+        assert_eq!(result.status, VerificationStatus::Unknown);
+        let entry = reporter::ReportEntry::from_consistency("factor", &result);
+        assert_eq!(entry.verdict, "UNDETERMINED");
+    }
+
+    #[test]
+    fn test_model_and_counterexample() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let pred = |name: &str, negated: bool| Predicate {
+            name: name.to_string(),
+            args: vec!["x".to_string()],
+            negated,
+        };
+        let stmt1 = Statement { id: "s1".to_string(), text: String::new(), predicates: vec![pred("p", false)], body: None, quantifier: None, weight: None };
+        let stmt2 = Statement { id: "s2".to_string(), text: String::new(), predicates: vec![pred("r", true)], body: None, quantifier: None, weight: None };
+
+        let result = verifier.verify_statements(&[stmt1.clone(), stmt2]).unwrap();
+        let model = result.model.unwrap();
+        assert_eq!(model.get("p(x)"), Some(&true));
+        assert_eq!(model.get("r(x)"), Some(&false));
+
+        // p does not entail q: the counterexample makes p true and q false
+        let conclusion = Statement { id: "c".to_string(), text: String::new(), predicates: vec![pred("q", false)], body: None, quantifier: None, weight: None };
+        let result = verifier.verify_reasoning(std::slice::from_ref(&stmt1), &conclusion).unwrap();
+        assert!(!result.is_valid);
+        let counterexample = result.counterexample.unwrap();
+        assert_eq!(counterexample.get("p(x)"), Some(&true));
+        assert_eq!(counterexample.get("q(x)"), Some(&false));
+
+        // The deprecated wrapper maps validity onto the old result type
+        #[allow(deprecated)]
+        let legacy = verifier.verify_reasoning_chain(&[stmt1], &conclusion).unwrap();
+        assert_eq!(legacy.status, VerificationStatus::Inconsistent);
+        assert_eq!(legacy.model.unwrap().get("q(x)"), Some(&false));
+    }
+
+    #[test]
+    fn test_minimal_unsat_core() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        // Every pair is satisfiable, all three together are not
+        let statements = vec![
+            parse_statement("x is greater than y", "s1"),
+            parse_statement("y is greater than z", "s2"),
+            parse_statement("z is greater than x", "s3"),
+        ];
+        for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+            let pair = [statements[a].clone(), statements[b].clone()];
+            assert_eq!(verifier.verify_statements(&pair).unwrap().status, VerificationStatus::Consistent);
+        }
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.contradictions.len(), 1);
+        assert_eq!(result.contradictions[0].statements, vec!["s1", "s2", "s3"]);
+
+        // Disjoint conflicts are each reported, including a self-contradictory statement
+        let self_contradiction = Statement {
+            id: "s4".to_string(),
+            text: "p and not p".to_string(),
+            predicates: vec![
+                Predicate { name: "p".to_string(), args: vec![], negated: false },
+                Predicate { name: "p".to_string(), args: vec![], negated: true },
+            ],
+            body: None,
+            quantifier: None,
+            weight: None,
+        };
+        let mut statements = statements;
+        statements.push(self_contradiction);
+        let result = verifier.verify_statements(&statements).unwrap();
+        let conflicts: Vec<&Vec<String>> = result.contradictions.iter().map(|c| &c.statements).collect();
+        assert_eq!(conflicts, vec![&vec!["s1".to_string(), "s2".to_string(), "s3".to_string()], &vec!["s4".to_string()]]);
+    }
+
+    #[test]
+    fn test_push_pop_matches_fresh_verify() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let base = vec![
+            parse_statement("x is at least 3", "s1"),
+            parse_statement("y is greater than x", "s2"),
+        ];
+        let what_if = vec![parse_statement("y is less than 2", "s3")];
+
+        let fresh = verifier.verify_statements(&base).unwrap();
+
+        verifier.reset();
+        verifier.push_statements(&base).unwrap();
+        verifier.push_statements(&what_if).unwrap();
+        let with_what_if = verifier.check_pushed().unwrap();
+        assert_eq!(with_what_if.status, VerificationStatus::Inconsistent);
+        assert_eq!(with_what_if.contradictions[0].statements, vec!["s1", "s2", "s3"]);
+
+        verifier.pop().unwrap();
+        let popped = verifier.check_pushed().unwrap();
+        assert_eq!(popped.status, fresh.status);
+        assert_eq!(popped.per_statement_witness, fresh.per_statement_witness);
+
+        // Premises stay asserted while conclusions are checked against them
+        let follows = parse_statement("y is greater than 3", "c1");
+        let does_not_follow = parse_statement("y is greater than 4", "c2");
+        assert!(verifier.entails(&follows).unwrap().is_valid);
+        assert!(!verifier.entails(&does_not_follow).unwrap().is_valid);
+        assert!(verifier.entails(&follows).unwrap().is_valid);
+        assert_eq!(
+            verifier.entails(&follows).unwrap().is_valid,
+            verifier.verify_reasoning(&base, &follows).unwrap().is_valid,
+        );
+
+        verifier.pop().unwrap();
+        assert!(verifier.pop().is_err());
+    }
+
+    #[test]
+    fn test_predicate_cache_reuses_atoms() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let options = VerifyOptions { cache_predicates: true, ..Default::default() };
+        let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+
+        let statements: Vec<Statement> = (0..100)
+            .map(|i| parse_formal(&format!("p{}(a)", i), &format!("s{}", i)).unwrap())
+            .collect();
+        let first = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(verifier.cache_stats(), CacheStats { hits: 0, misses: 100 });
+
+        // The second pass creates no atoms and reaches the same verdict and model
+        let second = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(verifier.cache_stats(), CacheStats { hits: 100, misses: 100 });
+        assert_eq!(second.status, first.status);
+        assert_eq!(second.model, first.model);
+
+        // Cached atoms still interact with fresh ones
+        let mut contradiction = statements[..2].to_vec();
+        contradiction.push(parse_formal("~p0(a)", "n0").unwrap());
+        let result = verifier.verify_statements(&contradiction).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+
+        verifier.clear_cache();
+        verifier.verify_statements(&statements[..10]).unwrap();
+        assert_eq!(verifier.cache_stats(), CacheStats { hits: 0, misses: 10 });
+
+        // A signature given later makes f(x) a different atom than the cached object one
+        verifier.verify_statements(&[Statement::builder("fx").predicate("f", ["x"]).build()]).unwrap();
+        let mut options = verifier.options().clone();
+        options.signatures.insert("f".to_string(), vec![ArgType::Int]);
+        let statements = vec![
+            Statement::builder("fx").predicate("f", ["x"]).build(),
+            Statement::builder("fy").not_predicate("f", ["y"]).build(),
+            Statement::builder("x0").predicate("=", ["x", "0"]).build(),
+            Statement::builder("y0").predicate("=", ["y", "0"]).build(),
+        ];
+        let report = verifier.verify_report(&statements, &options).unwrap();
+        assert_eq!(report.status, VerificationStatus::Inconsistent);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let mut statements = Vec::new();
+        for i in 0..5 {
+            statements.push(parse_statement(&format!("x{} is greater than y{}", i, i), &format!("a{}", i)));
+            statements.push(parse_statement(&format!("y{} is greater than z{}", i, i), &format!("b{}", i)));
+            statements.push(parse_statement(&format!("Team {} ships", i), &format!("c{}", i)));
+            // Only the even groups close their cycle
+            let closing = if i % 2 == 0 { format!("z{} is greater than x{}", i, i) } else { format!("z{} is less than x{}", i, i) };
+            statements.push(parse_statement(&closing, &format!("d{}", i)));
+        }
+        assert_eq!(statements.len(), 20);
+
+        let ids = |result: &VerificationResult| -> Vec<Vec<String>> {
+            result.contradictions.iter().map(|c| c.statements.clone()).collect()
+        };
+
+        let serial = verifier.verify_statements(&statements).unwrap();
+        let parallel = verifier.verify_statements_parallel(&statements).unwrap();
+        assert_eq!(parallel.status, VerificationStatus::Inconsistent);
+        assert_eq!(ids(&parallel), ids(&serial));
+        assert_eq!(ids(&parallel).len(), 3);
+
+        // Consistent input: same verdict and witness
+        let consistent: Vec<Statement> = statements.iter()
+            .filter(|s| !["d0", "d2", "d4"].contains(&s.id.as_str()))
+            .cloned()
+            .collect();
+        let serial = verifier.verify_statements(&consistent).unwrap();
+        let parallel = verifier.verify_statements_parallel(&consistent).unwrap();
+        assert_eq!(parallel.status, VerificationStatus::Consistent);
+        assert_eq!(parallel.per_statement_witness, serial.per_statement_witness);
+
+        // A universal links the ground atoms of its predicates into one group
+        let mut not_cute = parse_statement("Tom is cute", "n");
+        not_cute.predicates[0].negated = true;
+        let quantified = vec![
+            parse_statement("Tom is a cat", "t"),
+            not_cute,
+            parse_statement("All cats are cute", "u"),
+        ];
+        let parallel = verifier.verify_statements_parallel(&quantified).unwrap();
+        assert_eq!(parallel.status, VerificationStatus::Inconsistent);
+        assert_eq!(ids(&parallel), vec![vec!["t", "n", "u"]]);
+
+        // So does an equality between the objects of otherwise unrelated atoms
+        let mut not_cute = parse_statement("Felix is cute", "n");
+        not_cute.predicates[0].negated = true;
+        let equated = vec![
+            parse_statement("Tom is cute", "t"),
+            not_cute,
+            parse_statement("Tom is the same as Felix", "e"),
+        ];
+        let parallel = verifier.verify_statements_parallel(&equated).unwrap();
+        assert_eq!(parallel.status, VerificationStatus::Inconsistent);
+        assert_eq!(ids(&parallel), vec![vec!["t", "n", "e"]]);
+    }
+
+    #[test]
+    fn test_contradictions_independent_of_order() {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
+        let mut verifier = CoherenceVerifier::new(&ctx);
+
+        let statements = vec![
+            parse_statement("All AI systems are perfectly logical", "s1"),
+            parse_statement("x is greater than y", "s2"),
+            parse_statement("Current AI systems contain contradictions", "s3"),
+            parse_statement("y is greater than x", "s4"),
+            parse_statement("The weather is nice", "s5"),
+        ];
+        let conflict_sets = |result: VerificationResult| -> Vec<Vec<String>> {
+            let mut sets: Vec<Vec<String>> = result.contradictions
+                .into_iter()
+                .map(|c| {
+                    let mut ids = c.statements;
+                    ids.sort();
+                    ids
+                })
+                .collect();
+            sets.sort();
+            sets
+        };
+
+        let expected = conflict_sets(verifier.verify_statements(&statements).unwrap());
+        assert_eq!(expected, vec![vec!["s1", "s3"], vec!["s2", "s4"]]);
+
+        // Predicates cached by earlier checks must not change later verdicts
+        let mut reversed = statements.clone();
+        reversed.reverse();
+        assert_eq!(conflict_sets(verifier.verify_statements(&reversed).unwrap()), expected);
+
+        let mut rotated = statements.clone();
+        rotated.rotate_left(2);
+        assert_eq!(conflict_sets(verifier.verify_statements(&rotated).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_statement_triviality() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let statements = vec![
+            parse_formal("valid(x) | ~valid(x)", "tautology").unwrap(),
+            parse_formal("ready(x)", "ordinary").unwrap(),
+        ];
+        // x > 0 → x >= 0, over the integers
+        let comparison = |op: &str| StatementBody::Atom(Predicate {
+            name: op.to_string(),
+            args: vec!["x".to_string(), "0".to_string()],
+            negated: false,
+        });
+        let mut statements = statements;
+        statements.push(Statement {
+            id: "arithmetic".to_string(),
+            text: "x > 0 -> x >= 0".to_string(),
+            predicates: vec![],
+            body: Some(StatementBody::Implies(Box::new(comparison(">")), Box::new(comparison(">=")))),
+            quantifier: None,
+            weight: None,
+        });
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        assert_eq!(result.triviality("tautology"), Some(Triviality::Tautology));
+        assert_eq!(result.triviality("ordinary"), None);
+        assert_eq!(result.triviality("arithmetic"), Some(Triviality::Tautology));
+        let entry = reporter::ReportEntry::from_consistency("set", &result);
+        assert_eq!(entry.findings.iter().filter(|finding| finding.rule == "tautology").count(), 2);
+
+        // The self-contradiction is named on its own, not lumped in with the others
+        statements.push(parse_formal("done(x) & ~done(x)", "self").unwrap());
+        statements.push(parse_formal("~ready(x)", "negation").unwrap());
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.triviality("self"), Some(Triviality::Contradiction));
+        assert_eq!(result.triviality("negation"), None);
+        let ids: Vec<&Vec<String>> = result.contradictions.iter().map(|contradiction| &contradiction.statements).collect();
+        assert_eq!(ids, vec![&vec!["ordinary".to_string(), "negation".to_string()], &vec!["self".to_string()]]);
+    }
+
+    #[test]
+    fn test_find_redundant() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let statements = vec![
+            parse_formal("forall x: human(x) -> mortal(x)", "rule").unwrap(),
+            parse_formal("human(socrates)", "fact").unwrap(),
+            parse_formal("mortal(socrates)", "consequence").unwrap(),
+            parse_formal("greek(socrates)", "independent").unwrap(),
+        ];
+        assert_eq!(verifier.find_redundant(&statements).unwrap(), vec!["consequence"]);
+
+        let mut contradictory = statements;
+        contradictory.push(parse_formal("~mortal(socrates)", "denial").unwrap());
+        let error = verifier.find_redundant(&contradictory).unwrap_err().to_string();
+        assert!(error.contains("inconsistent"), "{}", error);
+    }
+    #[test]
+    fn test_predicate_keys_do_not_alias() {
+        let atom = |args: &[&str], negated: bool| Predicate {
+            name: "rel".to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            negated,
+        };
+        let pair = atom(&["a", "b"], false);
+        let joined = atom(&["a,b"], true);
+        assert_eq!(predicate_key(&pair), "rel(a,b)");
+        assert_eq!(predicate_key(&joined), "rel(\"a,b\")");
+
+        let statements: Vec<Statement> = [("pair", pair), ("joined", joined)]
+            .into_iter()
+            .map(|(id, predicate)| Statement {
+                id: id.to_string(),
+                text: predicate_key(&predicate),
+                predicates: vec![predicate],
+                body: None,
+                quantifier: None,
+                weight: None,
+            })
+            .collect();
+        let ctx = Context::new(&Config::new());
+        let result = CoherenceVerifier::new(&ctx).verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        let result = propositional::PropositionalVerifier::new().verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_predicate_names_are_normalized() {
+        let statement = |id: &str, name: &str, arg: &str, negated: bool| Statement {
+            id: id.to_string(),
+            text: String::new(),
+            predicates: vec![Predicate { name: name.to_string(), args: vec![arg.to_string()], negated }],
+            body: None,
+            quantifier: None,
+            weight: None,
+        };
+        let statements = vec![
+            statement("upper", "Sorted", "Items", false),
+            statement("lower", "  sorted ", "items", true),
+        ];
+        assert_eq!(predicate_key(&statements[1].predicates[0]), "sorted(items)");
+
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        let result = propositional::PropositionalVerifier::new().verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+
+        // Arguments are normalized the same way, including inside multi-word names
+        let spaced = vec![
+            statement("a", "mortal", "  Socrates  the   Greek ", false),
+            statement("b", "Mortal", "socrates the greek", true),
+        ];
+        let result = verifier.verify_statements(&spaced).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+    }
+
+    #[test]
+    fn test_unparsed_statement_is_flagged() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let statements = vec![
+            parse_statement("Socrates is human", "fact"),
+            parse_statement("Colourless green ideas sleep furiously", "nonsense"),
+        ];
+        assert!(statements[1].is_unparsed());
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        assert_eq!(result.unparsed_statements, vec!["nonsense"]);
+        let entry = reporter::ReportEntry::from_consistency("statements", &result);
+        let finding = entry.findings.iter().find(|finding| finding.rule == "unparsed-statement").unwrap();
+        assert_eq!(finding.level, reporter::Level::Warning);
+        assert_eq!(finding.location.as_deref(), Some("nonsense"));
+
+        let reasoning = verifier.verify_reasoning(&statements[..1], &statements[1]).unwrap();
+        assert!(reasoning.is_valid);
+        assert_eq!(reasoning.unparsed_statements, vec!["nonsense"]);
+    }
+
+    #[test]
+    fn test_statement_builder() {
+        let built = Statement::builder("stmt1")
+            .text("The output is sorted and not reversed")
+            .predicate("sorted", ["output"])
+            .not_predicate("reversed", ["output"])
+            .build();
+        let written = Statement {
+            id: "stmt1".to_string(),
+            text: "The output is sorted and not reversed".to_string(),
+            predicates: vec![
+                Predicate { name: "sorted".to_string(), args: vec!["output".to_string()], negated: false },
+                Predicate { name: "reversed".to_string(), args: vec!["output".to_string()], negated: true },
+            ],
+            body: None,
+            quantifier: None,
+            weight: None,
+        };
+        assert_eq!(built, written);
+
+        let first = StatementBuilder::new().predicate("p", ["a"]).build();
+        let second = StatementBuilder::new().predicate("p", ["a"]).build();
+        assert!(first.id.starts_with("auto_"));
+        assert_ne!(first.id, second.id);
+    }
+
+    #[test]
+    fn test_incremental_knowledge_base() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        assert!(verifier.remove_last().is_empty());
+
+        verifier.add_statement(&parse_statement("All AI systems are perfectly logical", "s1")).unwrap();
+        verifier.add_statement(&parse_statement("x is greater than 0", "s2")).unwrap();
+        assert_eq!(verifier.current_status().unwrap().status, VerificationStatus::Consistent);
+
+        verifier.add_statement(&parse_statement("Current AI systems contain contradictions", "s3")).unwrap();
+        let result = verifier.current_status().unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions[0].statements, vec!["s1", "s3"]);
+
+        let ids: Vec<String> = verifier.pushed_statements().into_iter().map(|statement| statement.id).collect();
+        assert_eq!(ids, vec!["s1", "s2", "s3"]);
+
+        let removed = verifier.remove_last();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id, "s3");
+        assert_eq!(verifier.current_status().unwrap().status, VerificationStatus::Consistent);
+
+        verifier.remove_last();
+        verifier.remove_last();
+        assert!(verifier.remove_last().is_empty());
+        assert_eq!(verifier.current_status().unwrap().status, VerificationStatus::Consistent);
+    }
+
+    #[test]
+    fn test_compare_statement_sets() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let parse = |texts: &[&str]| -> Vec<Statement> {
+            texts.iter().enumerate().map(|(i, text)| parse_formal(text, &format!("s{}", i)).unwrap()).collect()
+        };
+
+        let result = verifier.compare(&parse(&["p -> q", "p"]), &parse(&["p & q"])).unwrap();
+        assert_eq!(result.strength, Strength::Equivalent);
+        assert!(result.old_entails_new.counterexample.is_none());
+        assert!(result.new_entails_old.counterexample.is_none());
+
+        let result = verifier.compare(&parse(&["p"]), &parse(&["p", "q"])).unwrap();
+        assert_eq!(result.strength, Strength::Stronger);
+        assert!(result.new_entails_old.is_valid);
+        let witness = result.old_entails_new.counterexample.unwrap();
+        assert_eq!(witness.get("p()"), Some(&true));
+        assert_eq!(witness.get("q()"), Some(&false));
+
+        let result = verifier.compare(&parse(&["p"]), &parse(&["q"])).unwrap();
+        assert_eq!(result.strength, Strength::Incomparable);
+    }
+
+    #[test]
+    fn test_seed_makes_models_reproducible() {
+        let statements: Vec<Statement> = ["p | q | r", "~p | ~q", "x is greater than 3", "x is less than 100"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| match parse_formal(text, &format!("s{}", i)) {
+                Ok(statement) => statement,
+                Err(_) => parse_statement(text, &format!("s{}", i)),
+            })
+            .collect();
+        let run = || {
+            let ctx = Context::new(&Config::new());
+            let options = VerifyOptions { seed: Some(42), ..Default::default() };
+            let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+            let result = verifier.verify_statements(&statements).unwrap();
+            assert_eq!(result.status, VerificationStatus::Consistent);
+            let model: BTreeMap<String, bool> = result.model.unwrap().into_iter().collect();
+            serde_json::to_string(&(model, result.per_statement_witness)).unwrap()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_last_statistics_after_check() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        assert_eq!(verifier.last_statistics(), &SolverStats::default());
+
+        // Three pigeons in two holes: no assignment works, and Z3 has to search to see it
+        let mut texts = Vec::new();
+        for pigeon in 1..=3 {
+            texts.push(format!("in(p{}, h1) | in(p{}, h2)", pigeon, pigeon));
+        }
+        for hole in 1..=2 {
+            for a in 1..=3 {
+                for b in (a + 1)..=3 {
+                    texts.push(format!("~in(p{}, h{}) | ~in(p{}, h{})", a, hole, b, hole));
+                }
+            }
+        }
+        let statements: Vec<Statement> = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| parse_formal(text, &format!("s{}", i)).unwrap())
+            .collect();
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        let stats = verifier.last_statistics();
+        assert!(stats.decisions > 0, "{:?}", stats);
+        assert!(stats.conflicts > 0, "{:?}", stats);
+    }
+
+    #[test]
+    fn test_logic_fragment() {
+        let ctx = Context::new(&Config::new());
+        let propositional: Vec<Statement> = ["coherent(tool) -> validated(tool)", "coherent(tool)", "~validated(tool)", "fast(tool) | cheap(tool)"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| parse_formal(text, &format!("s{}", i)).unwrap())
+            .collect();
+        let qf_uf = VerifyOptions { logic: Some("QF_UF".to_string()), ..Default::default() };
+        let mut general = CoherenceVerifier::new(&ctx);
+        let mut verifier = CoherenceVerifier::try_with_options(&ctx, qf_uf).unwrap();
+        for statements in [&propositional[..], &propositional[..2], &propositional[3..]] {
+            let expected = general.verify_statements(statements).unwrap();
+            let actual = verifier.verify_statements(statements).unwrap();
+            assert_eq!(actual.status, expected.status);
+            assert_eq!(actual.contradictions.len(), expected.contradictions.len());
+        }
+
+        // Arithmetic and quantifiers are outside QF_UF
+        let error = verifier.verify_statements(&[parse_statement("x is greater than 3", "a")]).unwrap_err();
+        assert_eq!(error.to_string(), "Statement 'a' uses arithmetic, which the logic QF_UF excludes");
+        let quantified = parse_formal("forall x: human(x) -> mortal(x)", "q").unwrap();
+        assert!(verifier.verify_statements(&[quantified]).unwrap_err().to_string().contains("quantified"));
+
+        let unknown = VerifyOptions { logic: Some("NOT_A_LOGIC".to_string()), ..Default::default() };
+        let error = CoherenceVerifier::try_with_options(&ctx, unknown).err().unwrap();
+        assert!(error.to_string().contains("NOT_A_LOGIC"));
+    }
+
+    #[test]
+    fn test_exactly_one_of() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let mut propositional = propositional::PropositionalVerifier::new();
+        let states = parse_statement("The request is exactly one of pending, approved, or rejected", "states");
+        assert!(matches!(&states.body, Some(StatementBody::ExactlyOne(options)) if options.len() == 3));
+
+        let approved = parse_statement("The request is approved", "approved");
+        let rejected = parse_statement("The request is rejected", "rejected");
+        let both = [states.clone(), approved.clone(), rejected];
+        for result in [verifier.verify_statements(&both).unwrap(), propositional.verify_statements(&both).unwrap()] {
+            assert_eq!(result.status, VerificationStatus::Inconsistent);
+            assert_eq!(result.contradictions[0].statements, vec!["states", "approved", "rejected"]);
+        }
+
+        let one = [states.clone(), approved];
+        assert_eq!(verifier.verify_statements(&one).unwrap().status, VerificationStatus::Consistent);
+        assert_eq!(propositional.verify_statements(&one).unwrap().status, VerificationStatus::Consistent);
+
+        // Exactly one of a single option just asserts it
+        let only = parse_statement("The request is exactly one of [pending]", "only");
+        assert_eq!(only.predicates.len(), 1);
+        assert_eq!(only.body, None);
+        let result = verifier.verify_statements(&[only, parse_statement("The request is not pending", "not")]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+    }
+
+    #[test]
+    fn test_line_reader_recalls_history() {
+        use crate::repl::LineReader;
+        use rustyline::history::MemHistory;
+
+        let mut lines = LineReader::with_history(MemHistory::new()).unwrap();
+        assert_eq!(lines.recall(0), None);
+
+        lines.remember("verify All AI systems are perfectly logical | Current AI systems contain contradictions").unwrap();
+        lines.remember("   ").unwrap();
+        lines.remember("add x is greater than 0").unwrap();
+        lines.remember("add x is greater than 0").unwrap();
+
+        assert_eq!(lines.recall(0).as_deref(), Some("add x is greater than 0"));
+        assert_eq!(
+            lines.recall(1).as_deref(),
+            Some("verify All AI systems are perfectly logical | Current AI systems contain contradictions"),
+        );
+        assert_eq!(lines.recall(2), None);
+    }
+
+    #[test]
+    fn test_duplicate_statements_checked_once() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let first = parse_statement("The system is reliable", "first");
+        let again = parse_statement("The system is reliable", "again");
+        assert!(first.same_formula(&again));
+
+        let result = verifier.verify_statements(&[first.clone(), again.clone()]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+
+        let denial = parse_statement("The system is not reliable", "denial");
+        let result = verifier.verify_statements(&[first, again, denial]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions.len(), 1);
+        assert_eq!(result.contradictions[0].statements, vec!["first", "denial"]);
+        assert!(result.statement_triviality.iter().all(|(id, _)| id != "again"));
+
+        // Predicates are compared normalized, and every backend leaves duplicates out
+        let sorted = Statement::builder("sorted").predicate("Sorted", ["X"]).build();
+        let lower = Statement::builder("lower").predicate("sorted", ["x"]).build();
+        let unsorted = Statement::builder("unsorted").not_predicate("sorted", ["x"]).build();
+        assert!(sorted.same_formula(&lower));
+        let statements = [sorted, lower, unsorted];
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.contradictions[0].statements, vec!["sorted", "unsorted"]);
+        let ids = |result: &VerificationResult| result.statement_triviality.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&result), vec!["sorted", "unsorted"]);
+
+        let result = propositional::PropositionalVerifier::new().verify_statements(&statements).unwrap();
+        assert_eq!(result.contradictions[0].statements, vec!["sorted", "unsorted"]);
+        assert_eq!(ids(&result), vec!["sorted", "unsorted"]);
+
+        #[cfg(feature = "rayon")]
+        {
+            let result = verifier.verify_statements_parallel(&statements).unwrap();
+            assert_eq!(result.contradictions[0].statements, vec!["sorted", "unsorted"]);
+            assert_eq!(ids(&result), vec!["sorted", "unsorted"]);
+        }
+    }
+
+    #[test]
+    fn test_escalation_retries_with_longer_timeouts() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::with_timeout(&ctx, Duration::from_secs(5));
+        // Refuting it takes thousands of conflicts: out of reach in a millisecond
+        let statements = [pigeonhole(6)];
+
+        let escalated = verifier.verify_with_escalation(&statements, &[Duration::from_millis(1), Duration::from_secs(60)]).unwrap();
+        assert_eq!(escalated.result.status, VerificationStatus::Inconsistent);
+        assert_eq!(escalated.stage, Some(1));
+        assert_eq!(verifier.options().timeout, Some(Duration::from_secs(5)));
+
+        let schedule = [Duration::from_millis(1), Duration::from_millis(2)];
+        let escalated = verifier.verify_with_escalation(&statements, &schedule).unwrap();
+        assert_eq!(escalated.result.status, VerificationStatus::Unknown);
+        assert_eq!(escalated.result.unknown_reason, Some(UnknownReason::Timeout));
+        assert_eq!(escalated.stage, None);
+        assert!(escalated.elapsed_ms >= 3.0);
+
+        assert!(verifier.verify_with_escalation(&statements, &[]).is_err());
+    }
+
+    /// One more pigeon than holes, each in some hole and no two sharing one
+    fn pigeonhole(holes: usize) -> Statement {
+        let sits = |pigeon: usize, hole: usize| StatementBody::Atom(Predicate {
+            name: "sits_in".to_string(),
+            args: vec![format!("pigeon{}", pigeon), format!("hole{}", hole)],
+            negated: false,
+        });
+        let mut clauses = Vec::new();
+        for pigeon in 0..=holes {
+            clauses.push(StatementBody::Or((0..holes).map(|hole| sits(pigeon, hole)).collect()));
+        }
+        for hole in 0..holes {
+            for first in 0..=holes {
+                for second in first + 1..=holes {
+                    clauses.push(StatementBody::Not(Box::new(StatementBody::And(vec![sits(first, hole), sits(second, hole)]))));
+                }
+            }
+        }
+        Statement::builder("pigeonhole").text(format!("{} pigeons in {} holes", holes + 1, holes)).body(StatementBody::And(clauses)).build()
+    }
+
+    #[test]
+    fn test_translation_error_names_statement() {
+        let ctx = Context::new(&Config::new());
+        let mut verifier = CoherenceVerifier::new(&ctx);
+        let fine = parse_statement("x is greater than 3", "fine");
+        let decimal = Statement::builder("half")
+            .text("x is greater than 0.5")
+            .predicate(">", ["x", "0.5"])
+            .build();
+
+        let error = verifier.verify_statements(&[fine, decimal]).unwrap_err();
+        assert!(error.to_string().contains("'half'"), "{}", error);
+        assert!(error.to_string().contains("x is greater than 0.5"), "{}", error);
+        assert!(format!("{:#}", error).contains("requires the real numeric sort"), "{:#}", error);
+    }
+
+    #[test]
+    fn test_contradiction_without_statements_deserializes() {
+        // Contradictions written before they listed every statement of the set
+        let json = r#"{"statement1": "a", "statement2": "b", "reason": "r", "formal_proof": "p"}"#;
+        let contradiction: Contradiction = serde_json::from_str(json).unwrap();
+        assert_eq!(contradiction.statement1, "a");
+        assert!(contradiction.statements.is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_links_atoms_with_non_object_arguments() {
+        let ctx = Context::new(&Config::new());
+        let verdicts = |signature: (&str, ArgType), statements: &[Statement]| {
+            let mut options = VerifyOptions::default();
+            options.signatures.insert(signature.0.to_string(), vec![signature.1]);
+            let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+            let serial = verifier.verify_statements(statements).unwrap().status;
+            let parallel = verifier.verify_statements_parallel(statements).unwrap().status;
+            (serial, parallel)
+        };
+
+        // f(x) and f(y) are the same atom once x = y = 0
+        let statements = vec![
+            Statement::builder("fx").predicate("f", ["x"]).build(),
+            Statement::builder("fy").not_predicate("f", ["y"]).build(),
+            Statement::builder("x0").predicate(">=", ["x", "0"]).predicate("<=", ["x", "0"]).build(),
+            Statement::builder("y0").predicate(">=", ["y", "0"]).predicate("<=", ["y", "0"]).build(),
+        ];
+        let (serial, parallel) = verdicts(("f", ArgType::Int), &statements);
+        assert_eq!(serial, VerificationStatus::Inconsistent);
+        assert_eq!(parallel, serial);
+
+        // b is either true or false, and g holds for neither
+        let statements = vec![
+            Statement::builder("gb").predicate("g", ["b"]).build(),
+            Statement::builder("gt").not_predicate("g", ["true"]).build(),
+            Statement::builder("gf").not_predicate("g", ["false"]).build(),
+        ];
+        let (serial, parallel) = verdicts(("g", ArgType::Bool), &statements);
+        assert_eq!(serial, VerificationStatus::Inconsistent);
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn test_max_consistent_subset_honours_deadline() {
+        let ctx = Context::new(&Config::new());
+        let Some(StatementBody::And(clauses)) = pigeonhole(9).body else { unreachable!() };
+        let statements: Vec<Statement> = clauses
+            .into_iter()
+            .enumerate()
+            .map(|(i, clause)| Statement::builder(format!("clause{}", i)).body(clause).build())
+            .collect();
+
+        // Dropping a single clause restores consistency, but proving no smaller drop
+        // will do is a pigeonhole refutation: far beyond a deadline already passed
+        let options = VerifyOptions {
+            total_deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        let mut verifier = CoherenceVerifier::with_options(&ctx, options);
+        assert!(verifier.max_consistent_subset(&statements).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+/*!
+Parallel verification (`rayon` feature)
+
+Statements that share no predicate or variable cannot constrain each other, so the
+input splits into independent components. Each component is verified on its own
+thread with its own Z3 `Context` (contexts are not `Sync`) and the results are merged
+back in input order, giving the same verdict and contradictions as the serial path.
+*/
+
+use crate::arithmetic::{self, Comparison};
+use crate::{
+    distinct_statements, normalize_symbol, predicate_key, signature, unparsed_ids, ArgType, CoherenceVerifier, ObjectRelation, Predicate, Quantifier, Statement, StatementWitness, Triviality,
+    VerificationResult, VerificationStatus,
+};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use z3::Context;
+
+impl<'ctx> CoherenceVerifier<'ctx> {
+    /// Verify logical consistency like [`CoherenceVerifier::verify_statements`], checking
+    /// independent groups of statements concurrently under the verifier's options
+    pub fn verify_statements_parallel(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationResult> {
+        let statements = &distinct_statements(statements);
+        let components = independent_components(statements, &self.options().signatures)?;
+        let options = self.options().clone();
+
+        let results = components
+            .par_iter()
+            .map(|component| {
+                let cfg = options.config();
+                let context = Context::new(&cfg);
+                let mut verifier = CoherenceVerifier::with_options(&context, options.clone());
+                let subset: Vec<Statement> = component.iter().map(|&i| statements[i].clone()).collect();
+                verifier.verify_statements(&subset)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(merge_results(statements, &components, results))
+    }
+}
+
+/// Group statement indices into connected components of the "shares a symbol" graph,
+/// each in input order, ordered by their first statement
+fn independent_components(
+    statements: &[Statement],
+    signatures: &BTreeMap<String, Vec<ArgType>>,
+) -> anyhow::Result<Vec<Vec<usize>>> {
+    let mut parent: Vec<usize> = (0..statements.len()).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+
+    // Predicates a quantifier ranges over constrain every ground atom of that predicate
+    let quantified: HashSet<String> = statements
+        .iter()
+        .filter(|statement| statement.quantifier.is_some())
+        .flat_map(|statement| {
+            let mut atoms = statement.atoms();
+            atoms.extend_from_slice(quantified_domain(statement));
+            atoms
+        })
+        .map(|predicate| function_symbol(&predicate))
+        .collect();
+
+    // Objects an equality or distinctness mentions connect every atom about them
+    let equated: HashSet<String> = statements
+        .iter()
+        .flat_map(Statement::atoms)
+        .filter(|predicate| ObjectRelation::from_name(&predicate.name).is_some())
+        .flat_map(|predicate| predicate.args)
+        .map(|arg| normalize_symbol(&arg))
+        .collect();
+
+    let mut owner: HashMap<String, usize> = HashMap::new();
+    for (i, statement) in statements.iter().enumerate() {
+        for symbol in symbols(statement, &quantified, &equated, signatures)? {
+            let first = *owner.entry(symbol).or_insert(i);
+            let (a, b) = (find(&mut parent, first), find(&mut parent, i));
+            parent[a.max(b)] = a.min(b);
+        }
+    }
+
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut component_of_root: HashMap<usize, usize> = HashMap::new();
+    for i in 0..statements.len() {
+        let root = find(&mut parent, i);
+        let index = *component_of_root.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[index].push(i);
+    }
+    Ok(components)
+}
+
+/// Names a statement's predicates refer to: arithmetic variables for comparisons,
+/// the predicate itself otherwise, plus the variables of its numeric arguments and the
+/// constants of its boolean ones, its function when a quantified statement uses it or
+/// it takes non-object arguments, and its equated objects
+fn symbols(
+    statement: &Statement,
+    quantified: &HashSet<String>,
+    equated: &HashSet<String>,
+    signatures: &BTreeMap<String, Vec<ArgType>>,
+) -> anyhow::Result<Vec<String>> {
+    let mut symbols = Vec::new();
+    for predicate in statement.atoms().iter().chain(quantified_domain(statement)) {
+        match (Comparison::from_name(&predicate.name), predicate.args.as_slice()) {
+            (Some(_), [lhs, rhs]) => {
+                for side in [lhs, rhs] {
+                    let term = arithmetic::parse_term(side)?;
+                    symbols.extend(term.variables().into_iter().map(|v| format!("var {}", v)));
+                }
+            }
+            _ => {
+                symbols.push(predicate_key(predicate));
+                let arg_types = signature(signatures, &predicate.name).map(Vec::as_slice).unwrap_or_default();
+                for (arg, arg_type) in predicate.normalized().args.iter().zip(arg_types) {
+                    match arg_type {
+                        ArgType::Int | ArgType::Real => {
+                            let term = arithmetic::parse_term(arg)?;
+                            symbols.extend(term.variables().into_iter().map(|v| format!("var {}", v)));
+                        }
+                        ArgType::Bool if !matches!(arg.trim(), "true" | "false") => {
+                            symbols.push(format!("bool {}", arg.trim()));
+                        }
+                        _ => {}
+                    }
+                }
+                // Differently written numeric or boolean arguments can still be equal
+                // (`f(x)` and `f(y)` once x = y), so such atoms all share their function
+                let function = function_symbol(predicate);
+                if quantified.contains(&function) || arg_types.iter().any(|arg_type| *arg_type != ArgType::Object) {
+                    symbols.push(function);
+                }
+                for arg in predicate.args.iter().map(|arg| normalize_symbol(arg)) {
+                    if equated.contains(&arg) {
+                        symbols.push(format!("obj {}", arg));
+                    }
+                }
+            }
+        }
+    }
+    Ok(symbols)
+}
+
+fn quantified_domain(statement: &Statement) -> &[Predicate] {
+    statement.quantifier.as_ref().map(Quantifier::domain).unwrap_or_default()
+}
+
+fn function_symbol(predicate: &Predicate) -> String {
+    format!("fn {}/{}", normalize_symbol(&predicate.name), predicate.args.len())
+}
+
+/// Combine per-component results: inconsistent if any component is, unknown if any is
+/// undecided, otherwise consistent with the witnesses and models joined
+fn merge_results(
+    statements: &[Statement],
+    components: &[Vec<usize>],
+    results: Vec<VerificationResult>,
+) -> VerificationResult {
+    let truncated = results.iter().any(|result| result.truncated);
+    let mut triviality = vec![None; statements.len()];
+    for (component, result) in components.iter().zip(&results) {
+        for (&i, (_, statement_triviality)) in component.iter().zip(&result.statement_triviality) {
+            triviality[i] = *statement_triviality;
+        }
+    }
+    let statement_triviality: Vec<(String, Option<Triviality>)> = statements
+        .iter()
+        .zip(triviality)
+        .map(|(statement, triviality)| (statement.id.clone(), triviality))
+        .collect();
+
+    if let Some(proof) = results.iter()
+        .find(|result| result.status == VerificationStatus::Inconsistent)
+        .map(|result| result.proof.clone())
+    {
+        // Components are independent, so the maximum consistent subset of the whole is
+        // the union of theirs
+        let retained: f64 = components
+            .iter()
+            .zip(&results)
+            .map(|(component, result)| result.confidence * component.len() as f64)
+            .sum();
+
+        // Order contradictions by the input positions of their statements, as the
+        // serial path does
+        let mut contradictions = Vec::new();
+        for (component, result) in components.iter().zip(results) {
+            let mut position: HashMap<&str, usize> = HashMap::new();
+            for &i in component {
+                position.entry(statements[i].id.as_str()).or_insert(i);
+            }
+            for contradiction in result.contradictions {
+                let positions: Vec<usize> = contradiction.statements
+                    .iter()
+                    .map(|id| position[id.as_str()])
+                    .collect();
+                contradictions.push((positions, contradiction));
+            }
+        }
+        contradictions.sort_by(|a, b| a.0.cmp(&b.0));
+
+        return VerificationResult {
+            status: VerificationStatus::Inconsistent,
+            proof,
+            contradictions: contradictions.into_iter().map(|(_, contradiction)| contradiction).collect(),
+            confidence: retained / statements.len() as f64,
+            per_statement_witness: None,
+            model: None,
+            truncated,
+            unknown_reason: None,
+            statement_triviality,
+            unparsed_statements: unparsed_ids(statements),
+        };
+    }
+
+    if let Some(undecided) = results.iter().find(|result| result.status == VerificationStatus::Unknown) {
+        return VerificationResult {
+            status: VerificationStatus::Unknown,
+            proof: None,
+            contradictions: vec![],
+            confidence: 0.0,
+            per_statement_witness: None,
+            model: None,
+            truncated,
+            unknown_reason: undecided.unknown_reason,
+            statement_triviality,
+            unparsed_statements: unparsed_ids(statements),
+        };
+    }
+
+    let mut witness: Vec<Option<StatementWitness>> = vec![None; statements.len()];
+    let mut model = HashMap::new();
+    for (component, result) in components.iter().zip(results) {
+        for (&i, statement_witness) in component.iter().zip(result.per_statement_witness.into_iter().flatten()) {
+            witness[i] = Some(statement_witness);
+        }
+        model.extend(result.model.into_iter().flatten());
+    }
+
+    VerificationResult {
+        status: VerificationStatus::Consistent,
+        proof: Some("Z3 found satisfying model".to_string()),
+        contradictions: vec![],
+        confidence: 1.0,
+        per_statement_witness: witness.into_iter().collect(),
+        model: Some(model),
+        truncated,
+        unknown_reason: None,
+        statement_triviality,
+        unparsed_statements: unparsed_ids(statements),
+    }
+}
@@ -0,0 +1,656 @@
+/*!
+Natural language statement parsing
+
+A [`StatementParser`] holds an ordered list of [`ParserRule`]s. The first rule that
+recognizes a sentence decides its predicates; a sentence no rule recognizes parses into
+a statement without predicates. The built-in rules cover the demo vocabulary, linear
+comparisons ("A is at least B+5"), quantified sentences ("All humans are mortal") and
+ground sentences ("Socrates is human").
+Put your own rules in front of them (`StatementParser::with_rules`) to teach the parser
+a domain vocabulary.
+
+Negation is handled once for all rules. A sentence with "not", "no", "never",
+"doesn't", "isn't", "cannot" and the like is also parsed without them; when the rules
+read both versions the same (they ignore the negation), the predicates of each negated
+clause are negated in the affirmative reading. Clauses are separated by "and", "but",
+commas and semicolons, so "We need coherent tools but we don't need validation" only
+negates the need for validation. Rules that read a negation themselves, such as
+"Some birds are not flyers", keep their own reading.
+
+Conditionals ("If A then B", "A implies B", "A leads to B", "A requires B") are read
+into an implication between the sentences on either side.
+*/
+
+use crate::arithmetic;
+use crate::{predicate_key, Predicate, Quantifier, Statement, StatementBody};
+
+/// What a rule recognized in a sentence
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedSentence {
+    pub predicates: Vec<Predicate>,
+    pub body: Option<StatementBody>,
+    pub quantifier: Option<Quantifier>,
+}
+
+/// Recognizes one kind of sentence
+pub trait ParserRule {
+    /// Predicates the sentence states, or None if the rule does not recognize it
+    fn try_parse(&self, text: &str) -> Option<Vec<Predicate>>;
+
+    /// Like [`ParserRule::try_parse`], for rules whose sentences also carry a quantifier
+    /// or connectives
+    fn try_parse_sentence(&self, text: &str) -> Option<ParsedSentence> {
+        self.try_parse(text).map(|predicates| ParsedSentence { predicates, ..Default::default() })
+    }
+}
+
+/// Parses sentences with an ordered list of rules
+pub struct StatementParser {
+    rules: Vec<Box<dyn ParserRule>>,
+}
+
+impl Default for StatementParser {
+    /// A parser with the built-in rules
+    fn default() -> Self {
+        Self::with_rules(Self::builtin_rules())
+    }
+}
+
+impl StatementParser {
+    /// A parser trying `rules` in order
+    pub fn with_rules(rules: Vec<Box<dyn ParserRule>>) -> Self {
+        Self { rules }
+    }
+
+    /// The rules of the default parser, in order
+    pub fn builtin_rules() -> Vec<Box<dyn ParserRule>> {
+        vec![Box::new(KeywordRule), Box::new(ComparisonRule), Box::new(QuantifiedRule), Box::new(CategoricalRule)]
+    }
+
+    /// Parse a natural language sentence into a statement
+    pub fn parse(&self, text: &str, id: &str) -> Statement {
+        let parsed = self.read(text);
+
+        Statement {
+            id: id.to_string(),
+            text: text.to_string(),
+            predicates: parsed.predicates,
+            body: parsed.body,
+            quantifier: parsed.quantifier,
+            weight: None,
+        }
+    }
+
+    /// Parse text that may join independent clauses with "and", "but" or semicolons
+    /// into one statement per clause, with IDs `base_id_1`, `base_id_2`, ... Text that
+    /// is a single clause keeps `base_id`. A conjunction only separates clauses when
+    /// every part is recognized on its own, so "We need coherent tools and validation"
+    /// stays whole, and conjunctions inside double quotes never separate clauses.
+    pub fn parse_all(&self, text: &str, base_id: &str) -> Vec<Statement> {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let clauses = self.split_clauses(&tokens).unwrap_or_else(|| vec![text.trim().to_string()]);
+        if clauses.len() == 1 {
+            return vec![self.parse(text, base_id)];
+        }
+        clauses
+            .iter()
+            .enumerate()
+            .map(|(i, clause)| self.parse(clause, &format!("{}_{}", base_id, i + 1)))
+            .collect()
+    }
+
+    /// Split `tokens` into clauses that are each recognized, preferring the earliest
+    /// boundaries; None when no such split exists
+    fn split_clauses(&self, tokens: &[&str]) -> Option<Vec<String>> {
+        let mut quoted = false;
+        // A conditional's conjunctions belong to its antecedent or consequent
+        let conditional = tokens.first().is_some_and(|token| token.eq_ignore_ascii_case("if"));
+        for (i, token) in tokens.iter().enumerate() {
+            quoted ^= token.matches('"').count() % 2 == 1;
+            if quoted || conditional {
+                continue;
+            }
+            // A conjunction word is dropped; a semicolon ends the clause it closes
+            let (left, right) = if matches!(token.to_lowercase().as_str(), "and" | "but") {
+                (&tokens[..i], &tokens[i + 1..])
+            } else if token.ends_with(';') && i + 1 < tokens.len() {
+                (&tokens[..=i], &tokens[i + 1..])
+            } else {
+                continue;
+            };
+            let left = left.join(" ").trim_end_matches([',', ';']).to_string();
+            if left.is_empty() || !self.recognizes(&left) {
+                continue;
+            }
+            if let Some(rest) = self.split_clauses(right) {
+                return Some(std::iter::once(left).chain(rest).collect());
+            }
+        }
+
+        let whole = tokens.join(" ").trim_end_matches([',', ';']).to_string();
+        self.recognizes(&whole).then(|| vec![whole])
+    }
+
+    /// Whether some rule reads anything in `text`
+    fn recognizes(&self, text: &str) -> bool {
+        recognized(&self.read(text))
+    }
+
+    /// What `text` says, negation words and connectives included. A conditional ("If
+    /// ... then ...") is read before the rules; a sentence joined by "implies", "leads
+    /// to" or "requires" only when no rule reads it whole.
+    fn read(&self, text: &str) -> ParsedSentence {
+        if let Some(parsed) = self.parse_conditional(text) {
+            return parsed;
+        }
+        if let Some(parsed) = self.parse_negated(text) {
+            return parsed;
+        }
+        let parsed = self.parse_sentence(text);
+        if recognized(&parsed) {
+            return parsed;
+        }
+        self.parse_connective(text).unwrap_or(parsed)
+    }
+
+    /// What the first rule recognizing `text` reads in it
+    fn parse_sentence(&self, text: &str) -> ParsedSentence {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.try_parse_sentence(text))
+            .unwrap_or_default()
+    }
+
+    /// Read a sentence containing negation words as its affirmative form with the
+    /// predicates of each negated clause negated. None when there is no negation word,
+    /// when a rule reads the negation itself, or when a negated clause matches none of
+    /// the affirmative predicates.
+    fn parse_negated(&self, text: &str) -> Option<ParsedSentence> {
+        let (affirmative_text, clauses) = affirmative_clauses(text);
+        if !clauses.iter().any(|(_, negated)| *negated) {
+            return None;
+        }
+
+        let original = self.parse_sentence(text);
+        let mut parsed = self.parse_sentence(&affirmative_text);
+        if !recognized(&parsed) || (recognized(&original) && original != parsed) {
+            return None;
+        }
+
+        let key = |predicate: &Predicate| (predicate.negated, predicate_key(predicate));
+        let mut scopes: Vec<Vec<usize>> = Vec::new();
+        for (clause, _) in clauses.iter().filter(|(_, negated)| *negated) {
+            let in_clause: Vec<_> = self.parse_sentence(clause).predicates.iter().map(key).collect();
+            let scope: Vec<usize> = (0..parsed.predicates.len())
+                .filter(|&i| in_clause.contains(&key(&parsed.predicates[i])))
+                .collect();
+            if scope.is_empty() {
+                return None;
+            }
+            scopes.push(scope);
+        }
+
+        // A clause stating one predicate negates it; a clause stating several negates
+        // their conjunction
+        let mut removed = Vec::new();
+        for scope in scopes {
+            if let [i] = scope[..] {
+                parsed.predicates[i].negated = !parsed.predicates[i].negated;
+                continue;
+            }
+            let atoms = scope.iter().map(|&i| StatementBody::Atom(parsed.predicates[i].clone())).collect();
+            let negation = StatementBody::Not(Box::new(StatementBody::And(atoms)));
+            parsed.body = Some(match parsed.body.take() {
+                Some(body) => StatementBody::And(vec![body, negation]),
+                None => negation,
+            });
+            removed.extend(scope);
+        }
+        removed.sort_unstable();
+        removed.dedup();
+        for i in removed.into_iter().rev() {
+            parsed.predicates.remove(i);
+        }
+        Some(parsed)
+    }
+
+    /// "If A then B" / "If A, B" → A → B, where A and B are sentences the parser reads.
+    /// With an indefinite subject, "If a tool is coherent then it is validated" reads
+    /// as ∀x: tool(x) → (coherent(x) → validated(x)).
+    fn parse_conditional(&self, text: &str) -> Option<ParsedSentence> {
+        let tokens: Vec<&str> = text.trim().trim_end_matches('.').split_whitespace().collect();
+        let (first, rest) = tokens.split_first()?;
+        if !first.eq_ignore_ascii_case("if") {
+            return None;
+        }
+        let (antecedent, consequent) = match rest.iter().position(|token| token.eq_ignore_ascii_case("then")) {
+            Some(p) => (&rest[..p], &rest[p + 1..]),
+            None => {
+                let p = rest.iter().position(|token| token.ends_with(','))?;
+                (&rest[..=p], &rest[p + 1..])
+            }
+        };
+        if antecedent.is_empty() || consequent.is_empty() {
+            return None;
+        }
+
+        // "a tool is coherent ... it ..." speaks of any tool
+        let lower: Vec<String> = antecedent.iter().map(|token| token.to_lowercase()).collect();
+        if matches!(lower[0].as_str(), "a" | "an" | "any") && consequent[0].eq_ignore_ascii_case("it") {
+            let copula = lower.iter().position(|word| word == "is")?;
+            let class = &lower[1..copula];
+            if class.is_empty() {
+                return None;
+            }
+            let antecedent = format!("x {}", antecedent[copula..].join(" "));
+            let consequent = format!("x {}", consequent[1..].join(" "));
+            let body = implication(self.read_side(&antecedent)?, self.read_side(&consequent)?);
+            let domain = Predicate { name: class.join("_"), args: vec!["x".to_string()], negated: false };
+            return Some(ParsedSentence {
+                predicates: Vec::new(),
+                body: Some(body),
+                quantifier: Some(Quantifier::ForAll { variables: vec!["x".to_string()], domain: vec![domain] }),
+            });
+        }
+
+        let body = implication(self.read_side(&antecedent.join(" "))?, self.read_side(&consequent.join(" "))?);
+        Some(ParsedSentence { body: Some(body), ..Default::default() })
+    }
+
+    /// "A implies B" / "A leads to B" / "A requires B" → A → B between sentences the
+    /// parser reads. Between noun phrases, "Coherent tools require validation" reads as
+    /// whoever needs coherent tools needs validation:
+    /// ∀x: need_coherent_tools(x) → need_validation(x).
+    fn parse_connective(&self, text: &str) -> Option<ParsedSentence> {
+        let tokens: Vec<&str> = text.trim().trim_end_matches('.').split_whitespace().collect();
+        let lower: Vec<String> = tokens.iter().map(|token| token.to_lowercase()).collect();
+        let (p, length) = (1..tokens.len()).find_map(|i| match lower[i].as_str() {
+            "implies" | "imply" | "requires" | "require" => Some((i, 1)),
+            "leads" | "lead" if lower.get(i + 1).is_some_and(|next| next == "to") => Some((i, 2)),
+            _ => None,
+        })?;
+        let (antecedent, consequent) = (&tokens[..p], &tokens[p + length..]);
+        if consequent.is_empty() {
+            return None;
+        }
+
+        if let (Some(antecedent), Some(consequent)) =
+            (self.read_side(&antecedent.join(" ")), self.read_side(&consequent.join(" ")))
+        {
+            return Some(ParsedSentence { body: Some(implication(antecedent, consequent)), ..Default::default() });
+        }
+        if !lower[p].starts_with("require") {
+            return None;
+        }
+
+        let need = |phrase: &[String]| -> Option<Predicate> {
+            let phrase = match phrase {
+                [article, rest @ ..] if matches!(article.as_str(), "a" | "an" | "the") => rest,
+                _ => phrase,
+            };
+            let (_, clauses) = affirmative_clauses(&phrase.join(" "));
+            if phrase.is_empty() || clauses.iter().any(|(_, negated)| *negated) {
+                return None;
+            }
+            let name = phrase.iter().map(|word| word.trim_end_matches([',', ';'])).collect::<Vec<_>>().join("_");
+            Some(Predicate { name: format!("need_{}", name), args: vec!["x".to_string()], negated: false })
+        };
+        let domain = need(&lower[..p])?;
+        let required = need(&lower[p + 1..])?;
+        Some(ParsedSentence {
+            predicates: vec![required],
+            body: None,
+            quantifier: Some(Quantifier::ForAll { variables: vec!["x".to_string()], domain: vec![domain] }),
+        })
+    }
+
+    /// One side of an implication: the conjunction of its clauses, which must all be
+    /// read and unquantified
+    fn read_side(&self, text: &str) -> Option<StatementBody> {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let mut parts = Vec::new();
+        for clause in self.split_clauses(&tokens)? {
+            let parsed = self.read(&clause);
+            if parsed.quantifier.is_some() {
+                return None;
+            }
+            parts.extend(parsed.predicates.into_iter().map(StatementBody::Atom));
+            parts.extend(parsed.body);
+        }
+        Some(match parts.len() {
+            1 => parts.pop()?,
+            _ => StatementBody::And(parts),
+        })
+    }
+}
+
+/// Whether a rule read anything in a sentence
+fn recognized(parsed: &ParsedSentence) -> bool {
+    !parsed.predicates.is_empty() || parsed.body.is_some() || parsed.quantifier.is_some()
+}
+
+/// Words that negate what follows them
+fn is_negation_word(word: &str) -> bool {
+    matches!(
+        word.replace('’', "'").as_str(),
+        "not" | "no" | "none" | "never" | "doesn't" | "don't" | "didn't" | "isn't" | "aren't" | "wasn't" | "cannot" | "can't" | "won't"
+    )
+}
+
+fn implication(antecedent: StatementBody, consequent: StatementBody) -> StatementBody {
+    StatementBody::Implies(Box::new(antecedent), Box::new(consequent))
+}
+
+/// The sentence with its negation words removed ("does not contain" → "contain",
+/// "isn't" → "is"), and its clauses in the same form, each with whether it was negated
+fn affirmative_clauses(text: &str) -> (String, Vec<(String, bool)>) {
+    let mut affirmative: Vec<String> = Vec::new();
+    let mut clauses: Vec<(Vec<String>, bool)> = vec![(Vec::new(), false)];
+
+    for token in text.split_whitespace() {
+        let normalized = token.replace('’', "'");
+        let word = normalized.trim_end_matches([',', ';', '.']).to_lowercase();
+        let clause = clauses.last_mut().unwrap();
+
+        let replacement = match word.as_str() {
+            "not" | "no" | "never" | "doesn't" | "don't" | "didn't" => Some(None),
+            "isn't" => Some(Some("is")),
+            "aren't" => Some(Some("are")),
+            "wasn't" => Some(Some("was")),
+            "cannot" | "can't" => Some(Some("can")),
+            "won't" => Some(Some("will")),
+            _ => None,
+        };
+        match replacement {
+            Some(affirmative_word) => {
+                clause.1 = true;
+                // "does not contain" → "contain"
+                if word == "not" && clause.0.last().is_some_and(|last| matches!(last.to_lowercase().as_str(), "do" | "does" | "did")) {
+                    clause.0.pop();
+                    affirmative.pop();
+                }
+                if let Some(affirmative_word) = affirmative_word {
+                    clause.0.push(affirmative_word.to_string());
+                    affirmative.push(affirmative_word.to_string());
+                }
+            }
+            None if matches!(word.as_str(), "and" | "but") => {
+                affirmative.push(token.to_string());
+                clauses.push((Vec::new(), false));
+                continue;
+            }
+            None => {
+                clause.0.push(token.trim_end_matches([',', ';']).to_string());
+                affirmative.push(token.to_string());
+            }
+        }
+        if token.ends_with([',', ';']) {
+            clauses.push((Vec::new(), false));
+        }
+    }
+
+    let clauses = clauses
+        .into_iter()
+        .filter(|(words, _)| !words.is_empty())
+        .map(|(words, negated)| (words.join(" "), negated))
+        .collect();
+    (affirmative.join(" "), clauses)
+}
+
+/// The demo vocabulary about AI systems and coherent tools, matched by keywords
+pub struct KeywordRule;
+
+impl ParserRule for KeywordRule {
+    fn try_parse(&self, text: &str) -> Option<Vec<Predicate>> {
+        let mut predicates = Vec::new();
+        let text_lower = text.to_lowercase();
+        let predicate = |name: &str, arg: &str, negated: bool| Predicate {
+            name: name.to_string(),
+            args: vec![arg.to_string()],
+            negated,
+        };
+
+        if text_lower.contains("all") && text_lower.contains("perfectly logical") {
+            // "All AI systems are perfectly logical" → ∀x: AI_system(x) → ¬Contains_contradictions(x)
+            predicates.push(predicate("ai_system_perfectly_logical", "ai_systems", false));
+            predicates.push(predicate("ai_systems_contain_contradictions", "ai_systems", true));
+        }
+
+        if text_lower.contains("ai systems contain contradictions") {
+            // "Current AI systems contain contradictions" → ∃x: AI_system(x) ∧ Contains_contradictions(x)
+            predicates.push(predicate("ai_systems_contain_contradictions", "ai_systems", false));
+        }
+
+        if text_lower.contains("no") && text_lower.contains("ai systems exist") {
+            // "No AI systems exist" → ¬∃x: AI_system(x)
+            predicates.push(predicate("ai_systems_exist", "ai_systems", true));
+        }
+
+        if text_lower.contains("we need") {
+            if text_lower.contains("coherent tools") {
+                predicates.push(predicate("need_coherent_tools", "we", false));
+            }
+            if text_lower.contains("validation") {
+                predicates.push(predicate("need_validation", "we", false));
+            }
+        }
+
+        (!predicates.is_empty()).then_some(predicates)
+    }
+}
+
+/// Numeric comparisons between linear terms: "A is at least B+5" → A >= B+5
+pub struct ComparisonRule;
+
+impl ParserRule for ComparisonRule {
+    fn try_parse(&self, text: &str) -> Option<Vec<Predicate>> {
+        let comparisons = [
+            (" is at least ", ">="),
+            (" is at most ", "<="),
+            (" is greater than ", ">"),
+            (" is more than ", ">"),
+            (" is less than ", "<"),
+            (" equals ", "="),
+        ];
+        comparisons.iter().find_map(|(phrase, op)| {
+            let (lhs, rhs) = text.trim().trim_end_matches('.').split_once(phrase)?;
+            if arithmetic::parse_term(lhs).is_err() || arithmetic::parse_term(rhs).is_err() {
+                return None;
+            }
+            Some(vec![Predicate {
+                name: op.to_string(),
+                args: vec![lhs.trim().to_string(), rhs.trim().to_string()],
+                negated: false,
+            }])
+        })
+    }
+}
+
+/// Sentences opening with a quantifier, over a class and a property of one or more
+/// words joined with `_`:
+/// - universal: "All humans are mortal" / "Every human is a mortal" / "Each human is
+///   mortal" → ∀x: human(x) → mortal(x)
+/// - negated universal: "No humans are immortal" / "None of the humans are immortal"
+///   → ∀x: human(x) → ¬immortal(x)
+/// - existential: "Some humans are not mortal" / "At least one human is not mortal" /
+///   "There exists a human who is not mortal" / "Not all humans are mortal" →
+///   ∃x: human(x) ∧ ¬mortal(x)
+///
+/// "There exists a human" alone asserts ∃x: human(x), and "There are no errors" /
+/// "There exists no error" denies it: ¬∃x: error(x), read as ∀x: ¬error(x). "There is
+/// no human who is immortal" → ∀x: human(x) → ¬immortal(x).
+pub struct QuantifiedRule;
+
+impl ParserRule for QuantifiedRule {
+    fn try_parse(&self, text: &str) -> Option<Vec<Predicate>> {
+        self.try_parse_sentence(text).map(|parsed| parsed.predicates)
+    }
+
+    fn try_parse_sentence(&self, text: &str) -> Option<ParsedSentence> {
+        let text_lower = text.to_lowercase();
+        let words: Vec<&str> = text_lower.trim().trim_end_matches('.').split_whitespace().collect();
+        if words.iter().any(|word| matches!(*word, "and" | "or" | "but")) {
+            return None;
+        }
+
+        // (universal, property negated by the opener, words after the opener, relative clause)
+        let (universal, opener_negates, rest, relative) = match words.as_slice() {
+            ["all" | "every" | "each", rest @ ..] => (true, false, rest, false),
+            ["no", rest @ ..] | ["none", "of", "the", rest @ ..] | ["none", "of", rest @ ..] => (true, true, rest, false),
+            ["some", rest @ ..] | ["at", "least", "one", rest @ ..] => (false, false, rest, false),
+            ["not", "all" | "every", rest @ ..] => (false, true, rest, false),
+            ["there", "exists" | "exist" | "is" | "are", "no", rest @ ..] => (true, true, rest, true),
+            ["there", "exists" | "exist" | "is" | "are", rest @ ..] => (false, false, rest, true),
+            _ => return None,
+        };
+
+        let (class, clause) = if relative {
+            let rest = match rest {
+                ["a" | "an" | "some", rest @ ..] => rest,
+                _ => rest,
+            };
+            match rest.iter().position(|word| matches!(*word, "that" | "who" | "which")) {
+                Some(p) => (&rest[..p], &rest[p + 1..]),
+                None => (rest, &[][..]),
+            }
+        } else {
+            let p = rest.iter().position(|word| matches!(*word, "is" | "are"))?;
+            (&rest[..p], &rest[p..])
+        };
+        // "There are not any errors" and the like are left to the negation handling
+        if class.is_empty() || class.iter().any(|word| is_negation_word(word)) {
+            return None;
+        }
+
+        let plural = match clause.first() {
+            Some(copula) => *copula == "are",
+            None => matches!(words[1], "are" | "exist"),
+        };
+        let (last, init) = class.split_last()?;
+        let mut class_words: Vec<&str> = init.to_vec();
+        class_words.push(if plural { singular_noun(last) } else { last });
+        let domain = Predicate { name: class_words.join("_"), args: vec!["x".to_string()], negated: false };
+
+        let mut parsed = ParsedSentence::default();
+        if let Some((copula, property)) = clause.split_first() {
+            if !matches!(*copula, "is" | "are") {
+                return None;
+            }
+            let (not, property) = match property {
+                ["not", property @ ..] => (true, property),
+                _ => (false, property),
+            };
+            let property = match property {
+                ["a" | "an", property @ ..] => property,
+                _ => property,
+            };
+            if property.is_empty() {
+                return None;
+            }
+            parsed.predicates.push(Predicate {
+                name: property.join("_"),
+                args: vec!["x".to_string()],
+                negated: not != opener_negates,
+            });
+        } else if !relative {
+            return None;
+        } else if universal {
+            // ¬∃x: class(x) as ∀x: ¬class(x), over every object
+            let variables = vec!["x".to_string()];
+            parsed.predicates.push(Predicate { negated: true, ..domain });
+            parsed.quantifier = Some(Quantifier::ForAll { variables, domain: vec![] });
+            return Some(parsed);
+        }
+
+        let variables = vec!["x".to_string()];
+        parsed.quantifier = Some(if universal {
+            Quantifier::ForAll { variables, domain: vec![domain] }
+        } else {
+            Quantifier::Exists { variables, domain: vec![domain] }
+        });
+        Some(parsed)
+    }
+}
+
+/// Ground sentences about named objects over single words:
+/// "Socrates is human" / "The request is approved" → human(socrates) / approved(request),
+/// "The input is either empty or sorted" → empty(input) ∨ sorted(input),
+/// "The request is exactly one of pending, approved, or rejected" → exactly one of
+/// pending(request), approved(request), rejected(request), and
+/// "a is the same as b" / "a is different from b" → eq(a, b) / distinct(a, b)
+pub struct CategoricalRule;
+
+impl ParserRule for CategoricalRule {
+    fn try_parse(&self, text: &str) -> Option<Vec<Predicate>> {
+        self.try_parse_sentence(text).map(|parsed| parsed.predicates)
+    }
+
+    fn try_parse_sentence(&self, text: &str) -> Option<ParsedSentence> {
+        let text_lower = text.to_lowercase();
+        let words: Vec<&str> = text_lower.trim().trim_end_matches('.').split_whitespace().collect();
+        let mut parsed = ParsedSentence::default();
+        if let Some(p) = words.windows(4).position(|window| window == ["is", "exactly", "one", "of"]) {
+            let subject = match &words[..p] {
+                ["the", subject] | [subject] => *subject,
+                _ => return None,
+            };
+            let mut options: Vec<Predicate> = exclusive_options(&words[p + 4..])
+                .into_iter()
+                .map(|option| Predicate { name: option, args: vec![subject.to_string()], negated: false })
+                .collect();
+            match options.len() {
+                0 => return None,
+                // Exactly one of a single option is the option itself
+                1 => parsed.predicates.append(&mut options),
+                _ => parsed.body = Some(StatementBody::ExactlyOne(options.into_iter().map(StatementBody::Atom).collect())),
+            }
+            return Some(parsed);
+        }
+        match words.as_slice() {
+            ["the", subject, "is", "either", first, "or", second] | [subject, "is", "either", first, "or", second] => {
+                let alternative = |property: &str| StatementBody::Atom(Predicate {
+                    name: property.to_string(),
+                    args: vec![subject.to_string()],
+                    negated: false,
+                });
+                parsed.body = Some(StatementBody::Or(vec![alternative(first), alternative(second)]));
+            }
+            [subject, "is", "the", "same", "as", other] | [subject, "is", "different", "from", other] => {
+                parsed.predicates.push(Predicate {
+                    name: if words[2] == "the" { "eq" } else { "distinct" }.to_string(),
+                    args: vec![subject.to_string(), other.to_string()],
+                    negated: false,
+                });
+            }
+            ["the", subject, "is", "a" | "an", property]
+            | ["the", subject, "is", property]
+            | [subject, "is", "a" | "an", property]
+            | [subject, "is", property] => {
+                parsed.predicates.push(Predicate { name: property.to_string(), args: vec![subject.to_string()], negated: false });
+            }
+            _ => return None,
+        }
+        Some(parsed)
+    }
+}
+
+/// The options of a list such as "pending, approved, or rejected" or "[draft]", each
+/// of one or more words joined with `_`
+fn exclusive_options(words: &[&str]) -> Vec<String> {
+    let list = words.join(" ");
+    let list = list.trim_start_matches('[').trim_end_matches(']');
+    list.split(',')
+        .flat_map(|item| item.split(" or ").flat_map(|item| item.split(" and ")))
+        .map(|item| {
+            let item = item.trim();
+            let item = item.strip_prefix("or ").or_else(|| item.strip_prefix("and ")).unwrap_or(item);
+            item.split_whitespace().collect::<Vec<_>>().join("_")
+        })
+        .filter(|option| !option.is_empty())
+        .collect()
+}
+
+/// Singular of a plural class noun such as "humans"
+fn singular_noun(plural: &str) -> &str {
+    plural.strip_suffix('s').filter(|singular| !singular.is_empty()).unwrap_or(plural)
+}
@@ -0,0 +1,148 @@
+/*!
+Probability consistency
+
+Checks a set of probability assignments such as `P(rain) = 0.7, P(no rain) = 0.2`
+against the axioms: every probability lies in [0, 1] and the events of each declared
+partition sum to one. The spec is lowered to arithmetic statements and verified over
+Z3 reals, so conflicts are reported like any other contradiction.
+*/
+
+use crate::{CoherenceVerifier, NumericSort, Predicate, Statement, VerificationReport, VerifyOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Probability assignments and the partitions they must sum to one over
+///
+/// ```json
+/// {
+///   "probabilities": { "rain": 0.7, "no rain": 0.2 },
+///   "partitions": [["rain", "no rain"]]
+/// }
+/// ```
+///
+/// Events that only appear in a partition are unknowns, still bounded to [0, 1].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProbabilitySpec {
+    #[serde(default)]
+    pub probabilities: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub partitions: Vec<Vec<String>>,
+}
+
+impl ProbabilitySpec {
+    /// Lower the spec to arithmetic statements: bounds per event, one equality per
+    /// assigned value and one sum-to-one constraint per partition
+    pub fn to_statements(&self) -> Vec<Statement> {
+        let mut events: Vec<&String> = self.probabilities.keys().collect();
+        for event in self.partitions.iter().flatten() {
+            if !events.contains(&event) {
+                events.push(event);
+            }
+        }
+
+        let mut statements = Vec::new();
+
+        for event in &events {
+            statements.push(Statement {
+                id: format!("bounds({})", event),
+                text: format!("0 <= P({}) <= 1", event),
+                predicates: vec![
+                    comparison(">=", &variable(event), "0"),
+                    comparison("<=", &variable(event), "1"),
+                ],
+                body: None,
+                quantifier: None,
+                weight: None,
+            });
+        }
+
+        for (event, value) in &self.probabilities {
+            statements.push(Statement {
+                id: format!("P({})", event),
+                text: format!("P({}) = {}", event, value),
+                predicates: vec![equals_value(&variable(event), *value)],
+                body: None,
+                quantifier: None,
+                weight: None,
+            });
+        }
+
+        for (i, partition) in self.partitions.iter().enumerate() {
+            let sum = partition.iter().map(|event| variable(event)).collect::<Vec<_>>().join(" + ");
+            let text = partition.iter().map(|event| format!("P({})", event)).collect::<Vec<_>>().join(" + ");
+            statements.push(Statement {
+                id: format!("partition_{}", i),
+                text: format!("{} = 1", text),
+                predicates: vec![comparison("=", &sum, "1")],
+                body: None,
+                quantifier: None,
+                weight: None,
+            });
+        }
+
+        statements
+    }
+}
+
+impl<'ctx> CoherenceVerifier<'ctx> {
+    /// Verify a probability spec over real arithmetic
+    pub fn verify_probabilities(&mut self, spec: &ProbabilitySpec) -> anyhow::Result<VerificationReport> {
+        let mut options: VerifyOptions = self.options().clone();
+        options.numeric_sort = NumericSort::Real;
+        self.verify_report(&spec.to_statements(), &options)
+    }
+}
+
+/// Arithmetic variable standing for the probability of an event
+///
+/// Underscores are doubled and every other non-alphanumeric character is written as
+/// `_{hex}_`, so distinct events such as `no rain` and `no_rain` never share a variable.
+fn variable(event: &str) -> String {
+    let mut name = String::from("P_");
+    for c in event.chars() {
+        match c {
+            '_' => name.push_str("__"),
+            c if c.is_alphanumeric() => name.push(c),
+            c => name.push_str(&format!("_{:x}_", c as u32)),
+        }
+    }
+    name
+}
+
+/// Equality between a variable and the shortest decimal form of `value`, scaled to
+/// integers on both sides so no digit is lost to the parser's i64 decimals
+fn equals_value(variable: &str, value: f64) -> Predicate {
+    let scientific = format!("{:e}", value);
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return comparison("=", variable, &value.to_string());
+    };
+    let exponent: i32 = exponent.parse().expect("f64 exponent is an integer");
+    let places = mantissa.split_once('.').map_or(0, |(_, fraction)| fraction.len() as i32);
+    let digits = mantissa.replace('.', "");
+    let shift = exponent - places;
+    if shift < 0 {
+        comparison("=", &scaled(variable, shift.unsigned_abs()), &digits)
+    } else {
+        comparison("=", variable, &scaled(&digits, shift as u32))
+    }
+}
+
+/// `term` multiplied by `10^exponent`, written as a product of factors that fit an i64
+fn scaled(term: &str, mut exponent: u32) -> String {
+    let mut factors = Vec::new();
+    while exponent > 0 {
+        let step = exponent.min(18);
+        factors.push(10i64.pow(step).to_string());
+        exponent -= step;
+    }
+    factors.push(term.to_string());
+    factors.join("*")
+}
+
+fn comparison(op: &str, lhs: &str, rhs: &str) -> Predicate {
+    Predicate {
+        name: op.to_string(),
+        args: vec![lhs.to_string(), rhs.to_string()],
+        negated: false,
+    }
+}
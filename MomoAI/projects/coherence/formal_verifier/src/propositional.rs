@@ -0,0 +1,339 @@
+/*!
+Propositional fallback backend
+
+Decides ground statements over predicates with the pure-Rust `varisat` SAT solver, for
+builds without Z3. Each ground atom such as `human(socrates)` is a propositional
+variable, which is exactly how Z3 treats it when no equalities between objects are
+asserted, so verdicts on these statements agree with [`CoherenceVerifier`]'s.
+Quantifiers, arithmetic comparisons and `eq`/`distinct` need a theory solver: a
+problem containing any of them is reported as unknown.
+
+[`CoherenceVerifier`]: crate::CoherenceVerifier
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use varisat::{ExtendFormula, Lit, Solver};
+
+use crate::arithmetic::Comparison;
+use crate::{
+    distinct_statements, predicate_key, unparsed_ids, Contradiction, ObjectRelation, Predicate, Statement, StatementBody, Triviality, UnknownReason,
+    VerificationResult, VerificationStatus,
+};
+
+/// Consistency checker for propositional statements, see the module documentation
+#[derive(Debug, Default)]
+pub struct PropositionalVerifier;
+
+impl PropositionalVerifier {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Verify logical consistency of a set of statements. Contradictions are disjoint
+    /// minimal conflicting sets, and repeated formulas are checked once, as with Z3.
+    pub fn verify_statements(&mut self, statements: &[Statement]) -> anyhow::Result<VerificationResult> {
+        let statements = &distinct_statements(statements);
+        if !statements.iter().all(is_propositional) {
+            return Ok(VerificationResult {
+                status: VerificationStatus::Unknown,
+                proof: None,
+                contradictions: vec![],
+                confidence: 0.0,
+                per_statement_witness: None,
+                model: None,
+                truncated: false,
+                unknown_reason: Some(UnknownReason::Incomplete),
+                statement_triviality: statements.iter().map(|statement| (statement.id.clone(), None)).collect(),
+                unparsed_statements: unparsed_ids(statements),
+            });
+        }
+
+        let mut encoder = Encoder::default();
+        let formulas: Vec<Lit> = statements.iter().map(|statement| encoder.encode(&statement.formula())).collect();
+        let selectors: Vec<Lit> = formulas
+            .iter()
+            .map(|&formula| {
+                let selector = encoder.solver.new_lit();
+                encoder.solver.add_clause(&[!selector, formula]);
+                selector
+            })
+            .collect();
+
+        // A statement alone: contradictory if its formula is unsatisfiable, a
+        // tautology if its negation is
+        let mut statement_triviality = Vec::new();
+        for (statement, &formula) in statements.iter().zip(&formulas) {
+            let triviality = if statement.atoms().is_empty() {
+                None
+            } else if !encoder.solve(&[formula])? {
+                Some(Triviality::Contradiction)
+            } else if !encoder.solve(&[!formula])? {
+                Some(Triviality::Tautology)
+            } else {
+                None
+            };
+            statement_triviality.push((statement.id.clone(), triviality));
+        }
+
+        let all: Vec<usize> = (0..statements.len()).collect();
+        if encoder.satisfiable(&selectors, &all)? {
+            let model = encoder.model();
+            let witness = statements
+                .iter()
+                .map(|statement| {
+                    let values = statement
+                        .atoms()
+                        .iter()
+                        .map(|predicate| {
+                            let key = predicate_key(predicate);
+                            let value = model.get(&key).copied().unwrap_or(false);
+                            (key, value)
+                        })
+                        .collect();
+                    (statement.id.clone(), values)
+                })
+                .collect();
+            return Ok(VerificationResult {
+                status: VerificationStatus::Consistent,
+                proof: Some("SAT solver found satisfying model".to_string()),
+                contradictions: vec![],
+                confidence: 1.0,
+                per_statement_witness: Some(witness),
+                model: Some(model),
+                truncated: false,
+                unknown_reason: None,
+                statement_triviality,
+                unparsed_statements: unparsed_ids(statements),
+            });
+        }
+
+        // Shrink a conflict to a minimal one by deletion, then look for another
+        // among the statements it does not involve
+        let mut contradictions = Vec::new();
+        let mut remaining = all;
+        while !encoder.satisfiable(&selectors, &remaining)? {
+            let mut conflict = remaining.clone();
+            let mut i = 0;
+            while i < conflict.len() {
+                let mut without = conflict.clone();
+                without.remove(i);
+                if encoder.satisfiable(&selectors, &without)? {
+                    i += 1;
+                } else {
+                    conflict = without;
+                }
+            }
+            remaining.retain(|k| !conflict.contains(k));
+            contradictions.push(contradiction(statements, &conflict));
+        }
+
+        // Without an optimizer, grade by a maximal consistent subset grown greedily in
+        // input order: a lower bound on the maximum Z3 finds
+        let mut kept = Vec::new();
+        for k in 0..statements.len() {
+            kept.push(k);
+            if !encoder.satisfiable(&selectors, &kept)? {
+                kept.pop();
+            }
+        }
+
+        Ok(VerificationResult {
+            status: VerificationStatus::Inconsistent,
+            proof: Some("SAT solver proved unsatisfiability".to_string()),
+            contradictions,
+            confidence: kept.len() as f64 / statements.len() as f64,
+            per_statement_witness: None,
+            model: None,
+            truncated: false,
+            unknown_reason: None,
+            statement_triviality,
+            unparsed_statements: unparsed_ids(statements),
+        })
+    }
+}
+
+/// Whether a statement is ground and free of arithmetic and object relations
+fn is_propositional(statement: &Statement) -> bool {
+    fn theory_free(body: &StatementBody) -> bool {
+        match body {
+            StatementBody::Atom(predicate) => !needs_theory(predicate),
+            StatementBody::Comparison { .. } => false,
+            StatementBody::Not(inner) => theory_free(inner),
+            StatementBody::And(parts) | StatementBody::Or(parts) | StatementBody::ExactlyOne(parts) => parts.iter().all(theory_free),
+            StatementBody::Implies(antecedent, consequent) => theory_free(antecedent) && theory_free(consequent),
+        }
+    }
+    statement.quantifier.is_none() && theory_free(&statement.formula())
+}
+
+/// Comparisons and `eq`/`distinct` are interpreted by Z3, not uninterpreted atoms
+fn needs_theory(predicate: &Predicate) -> bool {
+    (Comparison::from_name(&predicate.name).is_some() && predicate.args.len() == 2)
+        || (ObjectRelation::from_name(&predicate.name).is_some() && predicate.args.len() >= 2)
+}
+
+/// Describe a minimal conflicting set of statements (indices in input order)
+fn contradiction(statements: &[Statement], conflict: &[usize]) -> Contradiction {
+    let ids: Vec<String> = conflict.iter().map(|&k| statements[k].id.clone()).collect();
+    let reason = match conflict {
+        [_] => "Statement contradicts itself".to_string(),
+        [_, _] => "Statements are mutually exclusive".to_string(),
+        _ => {
+            let chain: Vec<&str> = conflict.iter().map(|&k| statements[k].text.as_str()).collect();
+            format!("Statements form an unsatisfiable chain: {}", chain.join(" → "))
+        }
+    };
+
+    Contradiction {
+        statement1: ids[0].clone(),
+        statement2: ids[ids.len() - 1].clone(),
+        formal_proof: format!("SAT solver proved ({}) is unsatisfiable", ids.join(" ∧ ")),
+        statements: ids,
+        reason,
+    }
+}
+
+/// Tseitin encoding of statement formulas into a SAT solver
+#[derive(Default)]
+struct Encoder {
+    solver: Solver<'static>,
+    /// Variable per ground atom, by predicate key
+    atoms: HashMap<String, Lit>,
+}
+
+impl Encoder {
+    /// Literal equivalent to the formula
+    fn encode(&mut self, body: &StatementBody) -> Lit {
+        match body {
+            StatementBody::Atom(predicate) => {
+                let solver = &mut self.solver;
+                let atom = *self.atoms.entry(predicate_key(predicate)).or_insert_with(|| solver.new_lit());
+                if predicate.negated { !atom } else { atom }
+            }
+            StatementBody::Not(inner) => !self.encode(inner),
+            StatementBody::And(parts) => {
+                let parts: Vec<Lit> = parts.iter().map(|part| self.encode(part)).collect();
+                !self.disjunction(parts.into_iter().map(|part| !part).collect())
+            }
+            StatementBody::Or(parts) => {
+                let parts: Vec<Lit> = parts.iter().map(|part| self.encode(part)).collect();
+                self.disjunction(parts)
+            }
+            StatementBody::Implies(antecedent, consequent) => {
+                let parts = vec![!self.encode(antecedent), self.encode(consequent)];
+                self.disjunction(parts)
+            }
+            StatementBody::ExactlyOne(parts) => {
+                let parts: Vec<Lit> = parts.iter().map(|part| self.encode(part)).collect();
+                // Neither "no option" nor any pair of options may hold
+                let mut violations = vec![!self.disjunction(parts.clone())];
+                for (i, &first) in parts.iter().enumerate() {
+                    for &second in &parts[i + 1..] {
+                        violations.push(!self.disjunction(vec![!first, !second]));
+                    }
+                }
+                !self.disjunction(violations)
+            }
+            // Excluded by `is_propositional`
+            StatementBody::Comparison { .. } => unreachable!("comparison in a propositional statement"),
+        }
+    }
+
+    /// Fresh literal equivalent to the disjunction of `parts`
+    fn disjunction(&mut self, parts: Vec<Lit>) -> Lit {
+        let or = self.solver.new_lit();
+        for &part in &parts {
+            self.solver.add_clause(&[or, !part]);
+        }
+        let mut clause = parts;
+        clause.push(!or);
+        self.solver.add_clause(&clause);
+        or
+    }
+
+    /// Whether the statements at `enabled` are jointly satisfiable
+    fn satisfiable(&mut self, selectors: &[Lit], enabled: &[usize]) -> anyhow::Result<bool> {
+        let assumptions: Vec<Lit> = enabled.iter().map(|&k| selectors[k]).collect();
+        self.solve(&assumptions)
+    }
+
+    /// Whether the clauses are satisfiable with the `assumptions` literals true
+    fn solve(&mut self, assumptions: &[Lit]) -> anyhow::Result<bool> {
+        self.solver.assume(assumptions);
+        self.solver.solve().map_err(|error| anyhow::anyhow!("SAT solver failed: {}", error))
+    }
+
+    /// Truth value of every atom under the model of the last satisfiable check
+    fn model(&self) -> HashMap<String, bool> {
+        // Atom literals are positive, so an atom is true when its literal is in the model
+        let model: HashSet<Lit> = self.solver.model().unwrap_or_default().into_iter().collect();
+        self.atoms
+            .iter()
+            .map(|(key, atom)| (key.clone(), model.contains(atom)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_formal, parse_statement};
+
+    #[test]
+    fn test_propositional_contradiction() {
+        let statements = vec![
+            parse_formal("coherent(tool) -> validated(tool)", "s1").unwrap(),
+            parse_formal("coherent(tool)", "s2").unwrap(),
+            parse_formal("fast(tool)", "s3").unwrap(),
+            parse_formal("~validated(tool)", "s4").unwrap(),
+        ];
+        let mut verifier = PropositionalVerifier::new();
+
+        let result = verifier.verify_statements(&statements).unwrap();
+        assert_eq!(result.status, VerificationStatus::Inconsistent);
+        assert_eq!(result.contradictions.len(), 1);
+        assert_eq!(result.contradictions[0].statements, vec!["s1", "s2", "s4"]);
+
+        let result = verifier.verify_statements(&statements[..3]).unwrap();
+        assert_eq!(result.status, VerificationStatus::Consistent);
+        assert!(result.model.unwrap()["validated(tool)"]);
+
+        let trivial = [parse_formal("p | ~p", "t").unwrap(), parse_formal("p & ~p", "c").unwrap()];
+        let result = verifier.verify_statements(&trivial).unwrap();
+        assert_eq!(result.triviality("t"), Some(Triviality::Tautology));
+        assert_eq!(result.triviality("c"), Some(Triviality::Contradiction));
+
+        // Quantifiers and arithmetic need Z3
+        for statement in [parse_formal("forall x: human(x) -> mortal(x)", "q").unwrap(), parse_statement("x is less than 0", "a")] {
+            let result = verifier.verify_statements(&[statement]).unwrap();
+            assert_eq!(result.status, VerificationStatus::Unknown);
+        }
+    }
+
+    #[cfg(feature = "z3")]
+    #[test]
+    fn test_verdicts_match_z3() {
+        use crate::{Backend, CoherenceVerifier};
+
+        let problems = [
+            vec!["p | q", "~p", "~q"],
+            vec!["p -> q", "q -> r", "p", "~r", "s & ~t"],
+            vec!["~(p & q)", "p | q", "p -> q"],
+            vec!["p(a) & ~p(b)", "p(a) -> q"],
+        ];
+        let ctx = z3::Context::new(&z3::Config::new());
+        let mut z3: Box<dyn Backend> = Box::new(CoherenceVerifier::new(&ctx));
+        let mut sat: Box<dyn Backend> = Box::new(PropositionalVerifier::new());
+        for problem in problems {
+            let statements: Vec<Statement> = problem
+                .iter()
+                .enumerate()
+                .map(|(i, text)| parse_formal(text, &format!("s{}", i)).unwrap())
+                .collect();
+            let expected = z3.verify_statements(&statements).unwrap();
+            let actual = sat.verify_statements(&statements).unwrap();
+            assert_eq!(actual.status, expected.status, "{:?}", problem);
+        }
+    }
+}
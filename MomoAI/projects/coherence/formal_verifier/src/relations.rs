@@ -0,0 +1,185 @@
+/*!
+Relation consistency
+
+Checks asserted edges such as `parent_of(alice, bob)` against properties declared for
+their relation: acyclic, symmetric or transitive. Each edge is an ordinary statement,
+while the properties become background axioms over the nodes that appear in the
+edges. Acyclicity reuses the ordering encoding: every edge forces its source to rank
+strictly below its target, so a cycle is an unsatisfiable chain of comparisons.
+*/
+
+use crate::arithmetic::Comparison;
+use crate::{CoherenceVerifier, ObjectRelation, Predicate, Statement, VerificationReport};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use z3::ast::Bool;
+
+/// Property a relation is declared to have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationProperty {
+    /// No chain of edges leads from a node back to itself
+    Acyclic,
+    /// `r(a, b)` implies `r(b, a)`
+    Symmetric,
+    /// `r(a, b)` and `r(b, c)` imply `r(a, c)`
+    Transitive,
+}
+
+/// One asserted (or explicitly denied) edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edge {
+    pub relation: String,
+    pub from: String,
+    pub to: String,
+    /// `false` asserts that the edge does not hold
+    #[serde(default = "default_holds")]
+    pub holds: bool,
+}
+
+fn default_holds() -> bool {
+    true
+}
+
+/// Declared relation properties and the edges to check against them
+///
+/// ```json
+/// {
+///   "relations": { "parent_of": ["acyclic"], "sibling_of": ["symmetric"] },
+///   "edges": [
+///     { "relation": "parent_of", "from": "alice", "to": "bob" },
+///     { "relation": "sibling_of", "from": "bob", "to": "carol" },
+///     { "relation": "sibling_of", "from": "carol", "to": "bob", "holds": false }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RelationSpec {
+    #[serde(default)]
+    pub relations: BTreeMap<String, Vec<RelationProperty>>,
+    #[serde(default)]
+    pub edges: Vec<Edge>,
+}
+
+impl RelationSpec {
+    /// One statement per edge, identified as `r(a, b)` or `¬r(a, b)`
+    pub fn to_statements(&self) -> Vec<Statement> {
+        self.edges
+            .iter()
+            .map(|edge| {
+                let atom = format!("{}({}, {})", edge.relation, edge.from, edge.to);
+                Statement {
+                    id: if edge.holds { atom } else { format!("¬{}", atom) },
+                    text: format!(
+                        "{} {}{} {}",
+                        edge.from,
+                        if edge.holds { "" } else { "not " },
+                        edge.relation,
+                        edge.to
+                    ),
+                    predicates: vec![Predicate {
+                        name: edge.relation.clone(),
+                        args: vec![edge.from.clone(), edge.to.clone()],
+                        negated: !edge.holds,
+                    }],
+                    body: None,
+                    quantifier: None,
+                    weight: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Nodes touched by the relation's edges, in first-seen order
+    fn nodes(&self, relation: &str) -> Vec<&str> {
+        let mut nodes: Vec<&str> = Vec::new();
+        for edge in self.edges.iter().filter(|edge| edge.relation == relation) {
+            for node in [edge.from.as_str(), edge.to.as_str()] {
+                if !nodes.contains(&node) {
+                    nodes.push(node);
+                }
+            }
+        }
+        nodes
+    }
+}
+
+impl<'ctx> CoherenceVerifier<'ctx> {
+    /// Verify the edges of a relation spec against the declared relation properties
+    pub fn verify_relations(&mut self, spec: &RelationSpec) -> anyhow::Result<VerificationReport> {
+        for relation in spec.relations.keys().chain(spec.edges.iter().map(|edge| &edge.relation)) {
+            if Comparison::from_name(relation).is_some() {
+                anyhow::bail!("Relation name '{}' is reserved for arithmetic comparisons", relation);
+            }
+            if ObjectRelation::from_name(relation).is_some() {
+                anyhow::bail!("Relation name '{}' is reserved for object equality", relation);
+            }
+        }
+
+        self.reset();
+        for (relation, properties) in &spec.relations {
+            let nodes = spec.nodes(relation);
+            for property in properties {
+                let axioms = self.relation_axioms(relation, *property, &nodes)?;
+                self.axioms.extend(axioms);
+            }
+        }
+
+        self.check_report(&spec.to_statements())
+    }
+
+    /// Instantiate a relation property over a finite set of nodes
+    fn relation_axioms(
+        &mut self,
+        relation: &str,
+        property: RelationProperty,
+        nodes: &[&str],
+    ) -> anyhow::Result<Vec<Bool<'ctx>>> {
+        let mut axioms = Vec::new();
+
+        for &a in nodes {
+            for &b in nodes {
+                let ab = self.relation_atom(relation, a, b)?;
+                match property {
+                    RelationProperty::Acyclic => {
+                        let below = self.comparison_to_z3(
+                            Comparison::Lt,
+                            &rank_variable(relation, a),
+                            &rank_variable(relation, b),
+                        )?;
+                        axioms.push(ab.implies(&below));
+                    }
+                    RelationProperty::Symmetric => {
+                        axioms.push(ab.implies(&self.relation_atom(relation, b, a)?));
+                    }
+                    RelationProperty::Transitive => {
+                        for &c in nodes {
+                            let bc = self.relation_atom(relation, b, c)?;
+                            let ac = self.relation_atom(relation, a, c)?;
+                            axioms.push(Bool::and(self.context, &[&ab, &bc]).implies(&ac));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(axioms)
+    }
+
+    fn relation_atom(&mut self, relation: &str, from: &str, to: &str) -> anyhow::Result<Bool<'ctx>> {
+        self.predicate_to_z3(&Predicate {
+            name: relation.to_string(),
+            args: vec![from.to_string(), to.to_string()],
+            negated: false,
+        })
+    }
+}
+
+/// Arithmetic variable ordering a node within an acyclic relation
+fn rank_variable(relation: &str, node: &str) -> String {
+    let name: String = format!("{}_{}", relation, node)
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("rank_{}", name)
+}
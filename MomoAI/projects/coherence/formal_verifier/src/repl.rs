@@ -0,0 +1,83 @@
+/*!
+Line editing for the interactive modes
+
+`LineReader` reads one command at a time with `rustyline`: the arrow keys move through
+the line and recall earlier entries, and the history is kept in a dotfile in the home
+directory between sessions. When stdin is not a terminal, lines are read as they come.
+*/
+
+use rustyline::error::ReadlineError;
+use rustyline::history::{DefaultHistory, History, SearchDirection};
+use rustyline::{Config, Editor};
+use std::path::PathBuf;
+
+/// Reads REPL input with line editing and history recall
+pub struct LineReader<H: History = DefaultHistory> {
+    editor: Editor<(), H>,
+    history_file: Option<PathBuf>,
+}
+
+impl LineReader {
+    /// A reader whose history is loaded from `~/<dotfile>` and saved back when it is
+    /// dropped. Without a home directory the history only lasts for the session.
+    pub fn open(dotfile: &str) -> anyhow::Result<Self> {
+        let history_file = std::env::var_os("HOME").map(|home| PathBuf::from(home).join(dotfile));
+        let mut editor = Editor::new()?;
+        if let Some(path) = &history_file {
+            // Missing on the first session
+            let _ = editor.load_history(path);
+        }
+        Ok(Self { editor, history_file })
+    }
+}
+
+impl<H: History> LineReader<H> {
+    /// A reader keeping its history in `history` only, e.g. a `MemHistory`
+    pub fn with_history(history: H) -> anyhow::Result<Self> {
+        Ok(Self {
+            editor: Editor::with_history(Config::default(), history)?,
+            history_file: None,
+        })
+    }
+
+    /// The next line without its line break, or None at the end of input (Ctrl-D).
+    /// Ctrl-C drops the line being typed and returns an empty one. Lines that are not
+    /// blank are remembered.
+    pub fn read_line(&mut self, prompt: &str) -> anyhow::Result<Option<String>> {
+        match self.editor.readline(prompt) {
+            Ok(line) => {
+                self.remember(&line)?;
+                Ok(Some(line))
+            }
+            Err(ReadlineError::Interrupted) => Ok(Some(String::new())),
+            Err(ReadlineError::Eof) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Add `line` to the history unless it is blank or repeats the latest entry
+    pub fn remember(&mut self, line: &str) -> anyhow::Result<()> {
+        let line = line.trim();
+        if !line.is_empty() {
+            self.editor.add_history_entry(line)?;
+        }
+        Ok(())
+    }
+
+    /// The entry the up arrow recalls when pressed `n + 1` times: 0 is the latest
+    pub fn recall(&self, n: usize) -> Option<String> {
+        let history = self.editor.history();
+        let index = history.len().checked_sub(n + 1)?;
+        let result = history.get(index, SearchDirection::Forward).ok()??;
+        Some(result.entry.into_owned())
+    }
+}
+
+impl<H: History> Drop for LineReader<H> {
+    fn drop(&mut self) {
+        if let Some(path) = &self.history_file {
+            // Losing the history is no reason to fail on exit
+            let _ = self.editor.save_history(path);
+        }
+    }
+}
@@ -0,0 +1,997 @@
+/*!
+Result reporters
+
+Both CLIs convert what they verified into [`ReportEntry`] values and hand them to a
+[`Reporter`] chosen with `--format`, so output formats live in one place instead of
+being hard-coded into each binary.
+*/
+
+use crate::{predicate_key, Contradiction, EquivalenceResult, ReasoningResult, Strength, Statement, Triviality, UnknownReason, VerificationReport, VerificationResult, VerificationStatus};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::{self, IsTerminal, Write};
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+}
+
+/// One problem (or observation) found while verifying a unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    /// Stable machine-readable rule id, e.g. `contradiction`
+    pub rule: String,
+    /// Human-readable rule name, e.g. `Contradiction`
+    pub title: String,
+    pub level: Level,
+    pub message: String,
+    pub location: Option<String>,
+    /// Source file the finding is in, for findings about code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// 1-based line in `file`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column on `line`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// Extra explanation lines such as the formal reason
+    pub details: Vec<String>,
+}
+
+/// One input of a verified unit, e.g. a statement with the predicates parsed from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputSummary {
+    pub id: String,
+    pub text: String,
+    /// Display names of the predicates, `~` marking negated ones
+    pub predicates: Vec<String>,
+}
+
+/// Outcome of verifying one unit (a statement set, a reasoning chain, a function, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportEntry {
+    /// What was verified
+    pub name: String,
+    pub status: VerificationStatus,
+    /// Short verdict word, e.g. `CONSISTENT`, `VALID` or `COHERENT`
+    pub verdict: String,
+    pub summary: String,
+    /// Confidence in the verdict, from 0 to 1
+    pub confidence: f64,
+    pub proof: Option<String>,
+    /// Supporting lines shown with the verdict, e.g. a witness assignment
+    pub notes: Vec<String>,
+    pub findings: Vec<Finding>,
+    /// What was verified, for document formats that list it; empty when not recorded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<InputSummary>,
+    /// The verifier's own result, e.g. a serialized [`VerificationResult`], for scripts
+    /// that want more than the summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+}
+
+impl ReportEntry {
+    /// Entry for a consistency check of a set of statements
+    pub fn from_consistency(name: &str, result: &VerificationResult) -> Self {
+        let status = result.status;
+
+        let mut notes: Vec<String> = result.per_statement_witness.iter()
+            .flatten()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(id, values)| {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|(predicate, value)| format!("{} = {}", predicate, value))
+                    .collect();
+                format!("Witness {}: {}", id, values.join(", "))
+            })
+            .collect();
+        if result.truncated {
+            notes.push(TRUNCATED_NOTE.to_string());
+        }
+
+        Self {
+            name: name.to_string(),
+            status,
+            verdict: status_verdict(status).to_string(),
+            summary: match result.unknown_reason {
+                Some(UnknownReason::Timeout) => "Z3 timed out before deciding consistency",
+                Some(UnknownReason::OutsideLogic) => OUTSIDE_LOGIC_SUMMARY,
+                _ => status_summary(status),
+            }.to_string(),
+            confidence: result.confidence,
+            proof: result.proof.clone(),
+            notes,
+            findings: result.contradictions
+                .iter()
+                .map(contradiction_finding)
+                .chain(result.statement_triviality
+                    .iter()
+                    .filter(|(_, triviality)| *triviality == Some(Triviality::Tautology))
+                    .map(|(id, _)| tautology_finding(id)))
+                .chain(result.unparsed_statements.iter().map(|id| unparsed_finding(id)))
+                .collect(),
+            inputs: vec![],
+            result: serde_json::to_value(result).ok(),
+        }
+    }
+
+    /// Entry for a premises → conclusion validity check
+    pub fn from_reasoning(name: &str, result: &ReasoningResult) -> Self {
+        let status = if result.is_valid {
+            VerificationStatus::Consistent
+        } else if result.unknown_reason.is_some() {
+            VerificationStatus::Unknown
+        } else {
+            VerificationStatus::Inconsistent
+        };
+
+        let mut notes = Vec::new();
+        if let Some(model) = &result.counterexample {
+            notes.push(format!("Counterexample: {}", assignments(model)));
+        }
+        if result.truncated {
+            notes.push(TRUNCATED_NOTE.to_string());
+        }
+
+        Self {
+            name: name.to_string(),
+            status,
+            verdict: match status {
+                VerificationStatus::Consistent => "VALID",
+                VerificationStatus::Inconsistent => "INVALID",
+                VerificationStatus::Unknown => "UNDETERMINED",
+            }.to_string(),
+            summary: match status {
+                VerificationStatus::Consistent => "Conclusion logically follows from premises",
+                VerificationStatus::Inconsistent => "Conclusion does not follow from premises",
+                VerificationStatus::Unknown if result.unknown_reason == Some(UnknownReason::Timeout) => {
+                    "Z3 timed out before deciding validity"
+                }
+                VerificationStatus::Unknown if result.unknown_reason == Some(UnknownReason::OutsideLogic) => {
+                    OUTSIDE_LOGIC_SUMMARY
+                }
+                VerificationStatus::Unknown => "Z3 could not decide validity",
+            }.to_string(),
+            confidence: result.confidence,
+            proof: result.proof.clone(),
+            notes,
+            findings: result.unparsed_statements.iter().map(|id| unparsed_finding(id)).collect(),
+            inputs: vec![],
+            result: serde_json::to_value(result).ok(),
+        }
+    }
+
+    /// Entry for a comparison of an old and a new statement set. Only equivalent sets
+    /// pass, so a refactoring that changes meaning fails the run.
+    pub fn from_comparison(name: &str, result: &EquivalenceResult) -> Self {
+        let status = match result.strength {
+            Strength::Equivalent => VerificationStatus::Consistent,
+            Strength::Unknown => VerificationStatus::Unknown,
+            Strength::Stronger | Strength::Weaker | Strength::Incomparable => VerificationStatus::Inconsistent,
+        };
+
+        let mut notes = Vec::new();
+        if let Some(model) = &result.old_entails_new.counterexample {
+            notes.push(format!("Old holds, new does not: {}", assignments(model)));
+        }
+        if let Some(model) = &result.new_entails_old.counterexample {
+            notes.push(format!("New holds, old does not: {}", assignments(model)));
+        }
+        if result.old_entails_new.truncated || result.new_entails_old.truncated {
+            notes.push(TRUNCATED_NOTE.to_string());
+        }
+
+        let mut unparsed = result.old_entails_new.unparsed_statements.clone();
+        unparsed.sort();
+        unparsed.dedup();
+
+        Self {
+            name: name.to_string(),
+            status,
+            verdict: match result.strength {
+                Strength::Equivalent => "EQUIVALENT",
+                Strength::Stronger => "STRONGER",
+                Strength::Weaker => "WEAKER",
+                Strength::Incomparable => "INCOMPARABLE",
+                Strength::Unknown => "UNDETERMINED",
+            }.to_string(),
+            summary: match result.strength {
+                Strength::Equivalent => "Both sets entail each other",
+                Strength::Stronger => "The new set entails the old one but says more",
+                Strength::Weaker => "The old set entails the new one but the new one says less",
+                Strength::Incomparable => "Neither set entails the other",
+                Strength::Unknown => "Z3 could not decide whether the sets entail each other",
+            }.to_string(),
+            confidence: if result.strength == Strength::Unknown { 0.0 } else { 1.0 },
+            proof: None,
+            notes,
+            findings: unparsed.iter().map(|id| unparsed_finding(id)).collect(),
+            inputs: vec![],
+            result: serde_json::to_value(result).ok(),
+        }
+    }
+
+    /// Entry for a built-in test case: passes when the check reached the `expected`
+    /// status. A check that could not decide is an error rather than a failure.
+    pub fn test_case(name: &str, expected: VerificationStatus, actual: VerificationStatus) -> Self {
+        let status = if actual == expected {
+            VerificationStatus::Consistent
+        } else if actual == VerificationStatus::Unknown {
+            VerificationStatus::Unknown
+        } else {
+            VerificationStatus::Inconsistent
+        };
+        Self {
+            name: name.to_string(),
+            status,
+            verdict: match status {
+                VerificationStatus::Consistent => "PASS",
+                VerificationStatus::Inconsistent => "FAIL",
+                VerificationStatus::Unknown => "ERROR",
+            }.to_string(),
+            summary: format!("Expected {}, got {}", status_verdict(expected), status_verdict(actual)),
+            confidence: 1.0,
+            proof: None,
+            notes: vec![],
+            findings: vec![],
+            inputs: vec![],
+            result: None,
+        }
+    }
+
+    /// Entry for a full [`VerificationReport`]
+    pub fn from_report(name: &str, report: &VerificationReport) -> Self {
+        Self {
+            name: name.to_string(),
+            status: report.status,
+            verdict: status_verdict(report.status).to_string(),
+            summary: status_summary(report.status).to_string(),
+            confidence: report.confidence,
+            proof: report.proof.clone(),
+            notes: if report.truncated { vec![TRUNCATED_NOTE.to_string()] } else { vec![] },
+            findings: report.contradictions
+                .iter()
+                .map(contradiction_finding)
+                .chain(report.unparsed_statements.iter().map(|id| unparsed_finding(id)))
+                .collect(),
+            inputs: vec![],
+            result: serde_json::to_value(report).ok(),
+        }
+    }
+
+    /// Record the statements that were verified as the entry's inputs
+    pub fn with_statements(mut self, statements: &[Statement]) -> Self {
+        self.inputs = statements
+            .iter()
+            .map(|statement| InputSummary {
+                id: statement.id.clone(),
+                text: statement.text.clone(),
+                predicates: statement
+                    .atoms()
+                    .iter()
+                    .map(|predicate| format!("{}{}", if predicate.negated { "~" } else { "" }, predicate_key(predicate)))
+                    .collect(),
+            })
+            .collect();
+        self
+    }
+}
+
+const TRUNCATED_NOTE: &str = "Truncated: the deadline passed before the analysis finished";
+
+const OUTSIDE_LOGIC_SUMMARY: &str = "A statement uses constructs outside the configured Z3 logic (--logic)";
+
+fn status_verdict(status: VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Consistent => "CONSISTENT",
+        VerificationStatus::Inconsistent => "INCONSISTENT",
+        VerificationStatus::Unknown => "UNDETERMINED",
+    }
+}
+
+fn status_summary(status: VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Consistent => "Statements are logically consistent",
+        VerificationStatus::Inconsistent => "Logical contradictions detected",
+        VerificationStatus::Unknown => "Z3 could not decide consistency",
+    }
+}
+
+fn contradiction_finding(contradiction: &Contradiction) -> Finding {
+    let message = if contradiction.statements.len() > 2 {
+        contradiction.statements.join(" → ")
+    } else {
+        format!("{} ↔ {}", contradiction.statement1, contradiction.statement2)
+    };
+
+    Finding {
+        rule: "contradiction".to_string(),
+        title: "Contradiction".to_string(),
+        level: Level::Error,
+        message,
+        location: Some(contradiction.statements.join(", ")),
+        file: None,
+        line: None,
+        column: None,
+        details: vec![
+            format!("Reason: {}", contradiction.reason),
+            format!("Formal: {}", contradiction.formal_proof),
+        ],
+    }
+}
+
+/// A statement true on its own is harmless to consistency but says nothing, which
+/// usually means it was mistyped or mis-parsed
+fn tautology_finding(id: &str) -> Finding {
+    Finding {
+        rule: "tautology".to_string(),
+        title: "Tautology".to_string(),
+        level: Level::Warning,
+        message: format!("{} is true whatever the other statements say", id),
+        location: Some(id.to_string()),
+        file: None,
+        line: None,
+        column: None,
+        details: vec![format!("Formal: ¬({}) is unsatisfiable", id)],
+    }
+}
+
+/// A model's predicate values as sorted `predicate = value` pairs
+fn assignments(model: &std::collections::HashMap<String, bool>) -> String {
+    let mut values: Vec<String> = model
+        .iter()
+        .map(|(predicate, value)| format!("{} = {}", predicate, value))
+        .collect();
+    values.sort();
+    values.join(", ")
+}
+
+/// A statement no predicate was extracted from constrains nothing, so it cannot
+/// contradict anything and a verdict involving it passes vacuously
+fn unparsed_finding(id: &str) -> Finding {
+    Finding {
+        rule: "unparsed-statement".to_string(),
+        title: "Unparsed statement".to_string(),
+        level: Level::Warning,
+        message: format!("No predicates were extracted from {}; the sentence was not understood", id),
+        location: Some(id.to_string()),
+        file: None,
+        line: None,
+        column: None,
+        details: vec!["Rephrase it so the parser recognises it, or state it formally".to_string()],
+    }
+}
+
+/// Formats verification output. `entry` is called once per verified unit and `finish`
+/// once at the end, where document formats (JSON, SARIF, JUnit) write their output.
+pub trait Reporter {
+    /// Introduce a unit before it is verified (title plus input lines)
+    fn section(&mut self, _out: &mut dyn Write, _title: &str, _lines: &[String]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn entry(&mut self, out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()>;
+
+    fn finish(&mut self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Process exit code for CI over the statuses of everything verified: 1 if anything
+/// is inconsistent, otherwise 2 if anything is undecided, otherwise 0
+pub fn exit_code(statuses: impl IntoIterator<Item = VerificationStatus>) -> i32 {
+    let statuses: Vec<VerificationStatus> = statuses.into_iter().collect();
+    if statuses.contains(&VerificationStatus::Inconsistent) {
+        1
+    } else if statuses.contains(&VerificationStatus::Unknown) {
+        2
+    } else {
+        0
+    }
+}
+
+/// Output format selected with `--format`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Human,
+    Json,
+    Sarif,
+    Junit,
+    Markdown,
+    Quiet,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" | "text" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "sarif" => Ok(Format::Sarif),
+            "junit" => Ok(Format::Junit),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "quiet" => Ok(Format::Quiet),
+            other => Err(format!(
+                "unknown format '{}' (expected human/text, json, sarif, junit, markdown or quiet)",
+                other
+            )),
+        }
+    }
+}
+
+impl Format {
+    /// Create the reporter for this format; `tool` and `version` name the producing
+    /// binary. `style` only affects the human format, the others are never decorated.
+    pub fn reporter(self, tool: &str, version: &str, style: Style) -> Box<dyn Reporter> {
+        match self {
+            Format::Human => Box::new(HumanReporter::new(style)),
+            Format::Json => Box::new(JsonReporter::default()),
+            Format::Sarif => Box::new(SarifReporter::new(tool, version)),
+            Format::Junit => Box::new(JunitReporter::new(tool)),
+            Format::Markdown => Box::new(MarkdownReporter::new(tool)),
+            Format::Quiet => Box::new(QuietReporter::default()),
+        }
+    }
+
+    /// Create the reporter for `--quiet`: nothing per entry and one summary at the end,
+    /// a JSON object with `--format json` and otherwise a line such as
+    /// `3/5 coherent, 2 violations`. `passed` names a passing entry and `findings` the
+    /// errors counted over all entries; warnings such as unparsed statements are not
+    /// counted, so a passing run never reports any.
+    pub fn summary_reporter(self, passed: &str, findings: &str) -> Box<dyn Reporter> {
+        Box::new(SummaryReporter {
+            json: self == Format::Json,
+            passed: passed.to_string(),
+            findings: findings.to_string(),
+            statuses: Vec::new(),
+            finding_count: 0,
+        })
+    }
+}
+
+/// Whether human-readable output is decorated, selected with `--color`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Decorate when stdout is a terminal and `NO_COLOR` is unset or empty
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            other => Err(format!("unknown color choice '{}' (expected auto, always or never)", other)),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// The style for this choice in the current process; see
+    /// [no-color.org](https://no-color.org) for `NO_COLOR`
+    pub fn style(self) -> Style {
+        let decorated = match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && io::stdout().is_terminal()
+            }
+        };
+        Style { decorated }
+    }
+}
+
+/// How human-readable text is printed: as written, or plain with emoji and ANSI
+/// escape sequences stripped for logs, pipes and screen readers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    decorated: bool,
+}
+
+impl Style {
+    pub const DECORATED: Style = Style { decorated: true };
+    pub const PLAIN: Style = Style { decorated: false };
+
+    pub fn is_decorated(self) -> bool {
+        self.decorated
+    }
+
+    /// The text as this style prints it. Plain text drops each emoji together with
+    /// the spaces after it, so `"✅ CONSISTENT"` becomes `"CONSISTENT"`.
+    pub fn paint(self, text: &str) -> Cow<'_, str> {
+        if self.decorated || !text.chars().any(|c| c == '\x1b' || is_emoji(c)) {
+            return Cow::Borrowed(text);
+        }
+        let mut plain = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // CSI sequence: ESC [ parameters final-byte
+                if chars.next_if_eq(&'[').is_some() {
+                    while chars.next().is_some_and(|c| !('\x40'..='\x7e').contains(&c)) {}
+                }
+            } else if is_emoji(c) {
+                while chars.next_if(|&c| is_emoji(c) || c == ' ').is_some() {}
+            } else {
+                plain.push(c);
+            }
+        }
+        Cow::Owned(plain)
+    }
+}
+
+/// Emoji and the joiners and selectors that combine them; arrows and bullets are
+/// ordinary text
+fn is_emoji(c: char) -> bool {
+    matches!(c, '\u{1F000}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}' | '\u{2B00}'..='\u{2BFF}' | '\u{FE0F}' | '\u{200D}')
+}
+
+/// Emoji-decorated text for terminals, stripped to plain text by a plain [`Style`]
+pub struct HumanReporter {
+    style: Style,
+}
+
+impl HumanReporter {
+    pub fn new(style: Style) -> Self {
+        Self { style }
+    }
+
+    fn write_painted(&self, out: &mut dyn Write, text: &[u8]) -> io::Result<()> {
+        out.write_all(self.style.paint(&String::from_utf8_lossy(text)).as_bytes())
+    }
+
+    fn render(&self, out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        let icon = match entry.status {
+            VerificationStatus::Consistent => "✅",
+            VerificationStatus::Inconsistent => "❌",
+            VerificationStatus::Unknown => "⚠️ ",
+        };
+        writeln!(out, "{} {}: {}", icon, entry.verdict, entry.summary)?;
+        if let Some(proof) = &entry.proof {
+            writeln!(out, "   Proof: {}", proof)?;
+        }
+        for note in &entry.notes {
+            writeln!(out, "   {}", note)?;
+        }
+        writeln!(out, "   Confidence: {:.1}%", entry.confidence * 100.0)?;
+
+        for (level, heading) in [(Level::Error, "🚨 Violations:"), (Level::Warning, "⚠️  Warnings:"), (Level::Note, "🔗 Notes:")] {
+            let findings: Vec<&Finding> = entry.findings.iter().filter(|f| f.level == level).collect();
+            if findings.is_empty() {
+                continue;
+            }
+            writeln!(out, "{}", heading)?;
+            for finding in findings {
+                writeln!(out, "   • {}: {}", finding.title, finding.message)?;
+                match (&finding.file, finding.line, finding.column) {
+                    (Some(file), Some(line), Some(column)) => writeln!(out, "     File: {}:{}:{}", file, line, column)?,
+                    (Some(file), Some(line), None) => writeln!(out, "     File: {}:{}", file, line)?,
+                    (Some(file), None, _) => writeln!(out, "     File: {}", file)?,
+                    (None, Some(line), Some(column)) => writeln!(out, "     Line: {}:{}", line, column)?,
+                    (None, Some(line), None) => writeln!(out, "     Line: {}", line)?,
+                    (None, None, _) => {}
+                }
+                if let Some(location) = &finding.location {
+                    writeln!(out, "     Location: {}", location)?;
+                }
+                for detail in &finding.details {
+                    writeln!(out, "     {}", detail)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Reporter for HumanReporter {
+    fn section(&mut self, out: &mut dyn Write, title: &str, lines: &[String]) -> io::Result<()> {
+        let title = self.style.paint(title);
+        let mut text = Vec::new();
+        writeln!(text, "{}", title)?;
+        writeln!(text, "{}", "=".repeat(title.chars().count()))?;
+        for line in lines {
+            writeln!(text, "{}", line)?;
+        }
+        writeln!(text)?;
+        self.write_painted(out, &text)
+    }
+
+    fn entry(&mut self, out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        let mut text = Vec::new();
+        self.render(&mut text, entry)?;
+        self.write_painted(out, &text)
+    }
+}
+
+/// A JSON array of all entries
+#[derive(Default)]
+pub struct JsonReporter {
+    entries: Vec<ReportEntry>,
+}
+
+impl Reporter for JsonReporter {
+    fn entry(&mut self, _out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        self.entries.push(entry.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, &self.entries)?;
+        writeln!(out)
+    }
+}
+
+/// A SARIF 2.1.0 log with one result per finding, for IDEs and CI code scanning.
+/// Findings in a file get a physical location, which code scanning shows as an
+/// annotation on that line.
+pub struct SarifReporter {
+    tool: String,
+    version: String,
+    findings: Vec<Finding>,
+}
+
+impl SarifReporter {
+    pub fn new(tool: &str, version: &str) -> Self {
+        Self { tool: tool.to_string(), version: version.to_string(), findings: Vec::new() }
+    }
+}
+
+impl Reporter for SarifReporter {
+    fn entry(&mut self, _out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        self.findings.extend(entry.findings.iter().cloned());
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        let mut rules: Vec<serde_json::Value> = Vec::new();
+        for finding in &self.findings {
+            if !rules.iter().any(|rule| rule["id"] == finding.rule.as_str()) {
+                rules.push(serde_json::json!({ "id": finding.rule, "name": finding.title }));
+            }
+        }
+
+        let results: Vec<serde_json::Value> = self.findings.iter().map(|finding| {
+            let mut text = finding.message.clone();
+            for detail in &finding.details {
+                text.push('\n');
+                text.push_str(detail);
+            }
+            let mut result = serde_json::json!({
+                "ruleId": finding.rule,
+                "level": match finding.level {
+                    Level::Error => "error",
+                    Level::Warning => "warning",
+                    Level::Note => "note",
+                },
+                "message": { "text": text },
+            });
+            let mut location = serde_json::Map::new();
+            if let Some(file) = &finding.file {
+                let mut physical = serde_json::json!({ "artifactLocation": { "uri": file } });
+                if let Some(line) = finding.line {
+                    physical["region"] = serde_json::json!({ "startLine": line });
+                    if let Some(column) = finding.column {
+                        physical["region"]["startColumn"] = serde_json::json!(column);
+                    }
+                }
+                location.insert("physicalLocation".to_string(), physical);
+            }
+            if let Some(name) = &finding.location {
+                location.insert("logicalLocations".to_string(), serde_json::json!([{ "name": name }]));
+            }
+            if !location.is_empty() {
+                result["locations"] = serde_json::json!([location]);
+            }
+            result
+        }).collect();
+
+        let log = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": { "name": self.tool, "version": self.version, "rules": rules } },
+                "results": results,
+            }],
+        });
+        serde_json::to_writer_pretty(&mut *out, &log)?;
+        writeln!(out)
+    }
+}
+
+/// A JUnit XML test suite with one test case per entry, for CI dashboards
+pub struct JunitReporter {
+    tool: String,
+    entries: Vec<ReportEntry>,
+}
+
+impl JunitReporter {
+    pub fn new(tool: &str) -> Self {
+        Self { tool: tool.to_string(), entries: Vec::new() }
+    }
+}
+
+impl Reporter for JunitReporter {
+    fn entry(&mut self, _out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        self.entries.push(entry.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        let count = |status| self.entries.iter().filter(|e| e.status == status).count();
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<testsuite name="{}" tests="{}" failures="{}" errors="{}">"#,
+            xml_escape(&self.tool),
+            self.entries.len(),
+            count(VerificationStatus::Inconsistent),
+            count(VerificationStatus::Unknown),
+        )?;
+        for entry in &self.entries {
+            write!(out, r#"  <testcase classname="{}" name="{}""#, xml_escape(&self.tool), xml_escape(&entry.name))?;
+            let body: Vec<String> = entry.findings.iter()
+                .map(|finding| format!("{}: {}", finding.title, finding.message))
+                .collect();
+            match entry.status {
+                VerificationStatus::Consistent => writeln!(out, "/>")?,
+                VerificationStatus::Inconsistent => {
+                    writeln!(out, ">")?;
+                    writeln!(
+                        out,
+                        r#"    <failure message="{}">{}</failure>"#,
+                        xml_escape(&format!("{}: {}", entry.verdict, entry.summary)),
+                        xml_escape(&body.join("\n")),
+                    )?;
+                    writeln!(out, "  </testcase>")?;
+                }
+                VerificationStatus::Unknown => {
+                    writeln!(out, ">")?;
+                    writeln!(out, r#"    <error message="{}"/>"#, xml_escape(&entry.summary))?;
+                    writeln!(out, "  </testcase>")?;
+                }
+            }
+        }
+        writeln!(out, "</testsuite>")
+    }
+}
+
+/// A Markdown document for pull requests and docs: a summary line, a results table,
+/// the verified statements with their predicates, then the findings by level, each
+/// grouped by the unit (statement set or function) they belong to. Plain text only,
+/// so the same input always renders the same document.
+pub struct MarkdownReporter {
+    tool: String,
+    entries: Vec<ReportEntry>,
+}
+
+impl MarkdownReporter {
+    pub fn new(tool: &str) -> Self {
+        Self { tool: tool.to_string(), entries: Vec::new() }
+    }
+}
+
+impl Reporter for MarkdownReporter {
+    fn entry(&mut self, _out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        self.entries.push(entry.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        let count = |status| self.entries.iter().filter(|entry| entry.status == status).count();
+        writeln!(out, "# {} report", self.tool)?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "**Summary:** {} checked: {} passed, {} failed, {} unknown",
+            self.entries.len(),
+            count(VerificationStatus::Consistent),
+            count(VerificationStatus::Inconsistent),
+            count(VerificationStatus::Unknown),
+        )?;
+
+        writeln!(out)?;
+        writeln!(out, "## Results")?;
+        writeln!(out)?;
+        writeln!(out, "| Unit | Verdict | Summary | Confidence |")?;
+        writeln!(out, "| --- | --- | --- | --- |")?;
+        for entry in &self.entries {
+            writeln!(
+                out,
+                "| {} | {} | {} | {:.1}% |",
+                markdown_cell(&entry.name),
+                entry.verdict,
+                markdown_cell(&entry.summary),
+                entry.confidence * 100.0
+            )?;
+        }
+
+        if self.entries.iter().any(|entry| !entry.inputs.is_empty()) {
+            writeln!(out)?;
+            writeln!(out, "## Statements")?;
+            for entry in self.entries.iter().filter(|entry| !entry.inputs.is_empty()) {
+                writeln!(out)?;
+                writeln!(out, "### {}", entry.name)?;
+                writeln!(out)?;
+                writeln!(out, "| ID | Statement | Predicates |")?;
+                writeln!(out, "| --- | --- | --- |")?;
+                for input in &entry.inputs {
+                    let predicates: Vec<String> = input.predicates.iter().map(|p| format!("`{}`", p)).collect();
+                    writeln!(
+                        out,
+                        "| {} | {} | {} |",
+                        markdown_cell(&input.id),
+                        markdown_cell(&input.text),
+                        markdown_cell(&predicates.join(", "))
+                    )?;
+                }
+            }
+        }
+
+        for (level, heading) in [(Level::Error, "Contradictions"), (Level::Warning, "Warnings"), (Level::Note, "Notes")] {
+            let entries: Vec<&ReportEntry> = self.entries
+                .iter()
+                .filter(|entry| entry.findings.iter().any(|finding| finding.level == level))
+                .collect();
+            if entries.is_empty() {
+                continue;
+            }
+            writeln!(out)?;
+            writeln!(out, "## {}", heading)?;
+            for entry in entries {
+                writeln!(out)?;
+                writeln!(out, "### {}", entry.name)?;
+                writeln!(out)?;
+                if let Some(proof) = entry.proof.as_ref().filter(|_| level == Level::Error) {
+                    writeln!(out, "Proof: {}", proof)?;
+                    writeln!(out)?;
+                }
+                for finding in entry.findings.iter().filter(|finding| finding.level == level) {
+                    write!(out, "- **{}**: {}", finding.title, finding.message)?;
+                    match (&finding.location, finding.line) {
+                        (Some(location), Some(line)) => write!(out, " (`{}`, line {})", location, line)?,
+                        (Some(location), None) => write!(out, " (`{}`)", location)?,
+                        (None, Some(line)) => write!(out, " (line {})", line)?,
+                        (None, None) => {}
+                    }
+                    writeln!(out)?;
+                    for detail in &finding.details {
+                        writeln!(out, "  - {}", detail)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Text safe inside a Markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Nothing per entry, one summary line at the end
+#[derive(Default)]
+pub struct QuietReporter {
+    statuses: Vec<VerificationStatus>,
+}
+
+impl Reporter for QuietReporter {
+    fn entry(&mut self, _out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        self.statuses.push(entry.status);
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        let count = |status| self.statuses.iter().filter(|&&s| s == status).count();
+        writeln!(
+            out,
+            "{} checked: {} passed, {} failed, {} unknown",
+            self.statuses.len(),
+            count(VerificationStatus::Consistent),
+            count(VerificationStatus::Inconsistent),
+            count(VerificationStatus::Unknown),
+        )
+    }
+}
+
+/// Nothing per entry, one summary line or object at the end; see `Format::summary_reporter`
+pub struct SummaryReporter {
+    json: bool,
+    passed: String,
+    findings: String,
+    statuses: Vec<VerificationStatus>,
+    finding_count: usize,
+}
+
+impl Reporter for SummaryReporter {
+    fn entry(&mut self, _out: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        self.statuses.push(entry.status);
+        self.finding_count += entry.findings.iter().filter(|finding| finding.level == Level::Error).count();
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        let count = |status| self.statuses.iter().filter(|&&s| s == status).count();
+        if self.json {
+            let summary = serde_json::json!({
+                "checked": self.statuses.len(),
+                "passed": count(VerificationStatus::Consistent),
+                "failed": count(VerificationStatus::Inconsistent),
+                "unknown": count(VerificationStatus::Unknown),
+                self.findings.as_str(): self.finding_count,
+            });
+            serde_json::to_writer(&mut *out, &summary)?;
+            return writeln!(out);
+        }
+        writeln!(
+            out,
+            "{}/{} {}, {} {}",
+            count(VerificationStatus::Consistent),
+            self.statuses.len(),
+            self.passed,
+            self.finding_count,
+            self.findings,
+        )
+    }
+}
+
+/// Graphviz digraph of the contradictions: one node per statement ID and an undirected
+/// edge, labeled with the reason, between every two statements of each contradiction.
+/// Statements with many edges are the most entangled.
+pub fn contradictions_to_dot(contradictions: &[Contradiction]) -> String {
+    let mut nodes: Vec<&str> = Vec::new();
+    let mut edges = Vec::new();
+    for contradiction in contradictions {
+        for (i, a) in contradiction.statements.iter().enumerate() {
+            if !nodes.contains(&a.as_str()) {
+                nodes.push(a);
+            }
+            for b in &contradiction.statements[i + 1..] {
+                edges.push(format!(
+                    "    {} -> {} [label={}];",
+                    dot_quote(a),
+                    dot_quote(b),
+                    dot_quote(&contradiction.reason)
+                ));
+            }
+        }
+    }
+
+    let mut dot = String::from("digraph contradictions {\n    edge [dir=none];\n");
+    for node in nodes {
+        dot.push_str(&format!("    {};\n", dot_quote(node)));
+    }
+    for edge in edges {
+        dot.push_str(&edge);
+        dot.push('\n');
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
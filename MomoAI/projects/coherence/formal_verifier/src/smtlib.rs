@@ -0,0 +1,46 @@
+/*!
+SMT-LIB2 input checks
+
+Z3 reports a malformed script through the error code of its context, which the `z3`
+crate does not expose: `Solver::from_string` silently loads nothing. The script is
+therefore first loaded into a solver of a scratch context through the C API, whose
+error code says whether Z3 could read it.
+*/
+
+use std::ffi::{CStr, CString};
+use z3_sys::*;
+
+/// Z3's message for an SMT-LIB2 script it cannot read, such as
+/// "line 1 column 14: unknown constant p"; None when the script loads
+pub(crate) fn syntax_error(source: &str) -> Option<String> {
+    let Ok(source) = CString::new(source) else {
+        return Some("script contains a NUL byte".to_string());
+    };
+
+    // SAFETY: every object is created in, and released with, the scratch context,
+    // whose error handler is unset so that errors are recorded instead of aborting
+    unsafe {
+        let config = Z3_mk_config();
+        let context = Z3_mk_context_rc(config);
+        Z3_del_config(config);
+        Z3_set_error_handler(context, None);
+
+        let solver = Z3_mk_solver(context);
+        Z3_solver_inc_ref(context, solver);
+        Z3_solver_from_string(context, solver, source.as_ptr());
+        let code = Z3_get_error_code(context);
+        let error = (code != ErrorCode::OK).then(|| {
+            let message = CStr::from_ptr(Z3_get_error_msg(context, code)).to_string_lossy();
+            // Z3 wraps parser messages as `(error "...")`
+            let message = message.trim();
+            message
+                .strip_prefix("(error \"")
+                .and_then(|message| message.strip_suffix("\")"))
+                .unwrap_or(message)
+                .to_string()
+        });
+        Z3_solver_dec_ref(context, solver);
+        Z3_del_context(context);
+        error
+    }
+}
@@ -0,0 +1,55 @@
+/*!
+WebAssembly exports
+
+Built with the `wasm` feature as a `cdylib` for wasm32 (see the README for the commands).
+Inputs and outputs are JSON strings; failures come back as `{"error": "..."}`.
+
+Z3 does not build for `wasm32-unknown-unknown`, so browser builds check statements
+with the propositional SAT backend: statements with quantifiers or arithmetic come
+back `Unknown`. [`backend`] names the backend in use. The code checker's `wasm`
+feature builds a module with these exports plus one checking Python functions.
+*/
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::{Statement, VerificationResult};
+
+/// Name of the backend `verify_statements_json` uses: "z3", or "sat" without Z3
+#[wasm_bindgen]
+pub fn backend() -> String {
+    if cfg!(feature = "z3") { "z3" } else { "sat" }.to_string()
+}
+
+/// Check a JSON array of statements for consistency; returns the verification result
+/// as JSON
+#[wasm_bindgen]
+pub fn verify_statements_json(input: &str) -> String {
+    match serde_json::from_str::<Vec<Statement>>(input) {
+        Ok(statements) => to_json(verify(&statements)),
+        Err(error) => error_json(&format!("Invalid statements: {}", error)),
+    }
+}
+
+#[cfg(feature = "z3")]
+fn verify(statements: &[Statement]) -> anyhow::Result<VerificationResult> {
+    let context = z3::Context::new(&z3::Config::new());
+    let mut verifier = crate::CoherenceVerifier::new(&context);
+    verifier.verify_statements(statements)
+}
+
+#[cfg(not(feature = "z3"))]
+fn verify(statements: &[Statement]) -> anyhow::Result<VerificationResult> {
+    crate::propositional::PropositionalVerifier::new().verify_statements(statements)
+}
+
+fn to_json(result: anyhow::Result<VerificationResult>) -> String {
+    match result.and_then(|result| Ok(serde_json::to_string(&result)?)) {
+        Ok(json) => json,
+        Err(error) => error_json(&format!("{:#}", error)),
+    }
+}
+
+fn error_json(message: &str) -> String {
+    json!({ "error": message }).to_string()
+}
@@ -0,0 +1,268 @@
+#![cfg(feature = "z3")]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn coherence(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_coherence"))
+        .args(args)
+        .output()
+        .expect("failed to run coherence")
+}
+
+/// Write `contents` to a file in the temp directory unique to this test process
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("coherence-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn verify_file_with_statements() {
+    let path = temp_file(
+        "statements.json",
+        r#"[{"id": "lower", "text": "x is at least 0",
+             "predicates": [{"name": ">=", "args": ["x", "0"], "negated": false}]}]"#,
+    );
+    let file = path.to_str().unwrap();
+
+    let output = coherence(&["verify", "--file", file]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("CONSISTENT"));
+
+    // Statements from the file and the command line are verified together
+    let output = coherence(&["verify", "--file", file, "-s", "x is less than 0"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("INCONSISTENT"), "{}", stdout);
+
+    let malformed = temp_file("malformed.json", r#"{"id": "lower"}"#);
+    let output = coherence(&["verify", "--file", malformed.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a JSON array of statements"));
+
+    std::fs::remove_file(path).unwrap();
+    std::fs::remove_file(malformed).unwrap();
+}
+
+#[test]
+fn json_format_round_trips_results() {
+    use coherence_verifier::{ReasoningResult, VerificationResult, VerificationStatus};
+
+    let output = coherence(&["--format", "json", "verify", "-s", "x is at least 3", "-s", "x is less than 3"]);
+    assert_eq!(output.status.code(), Some(1));
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let result: VerificationResult = serde_json::from_value(entries[0]["result"].clone()).unwrap();
+    assert_eq!(result.status, VerificationStatus::Inconsistent);
+    assert_eq!(result.contradictions.len(), 1);
+
+    let output = coherence(&[
+        "--format", "json", "reasoning",
+        "-p", "All humans are mortal", "-p", "Socrates is human", "-c", "Socrates is mortal",
+    ]);
+    assert!(output.status.success());
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let result: ReasoningResult = serde_json::from_value(entries[0]["result"].clone()).unwrap();
+    assert!(result.is_valid);
+}
+
+#[test]
+fn exit_code_reflects_verdict() {
+    let consistent = ["verify", "-s", "x is at least 3", "-s", "x is less than 5"];
+    let inconsistent = ["verify", "-s", "x is at least 3", "-s", "x is less than 3"];
+
+    assert_eq!(coherence(&consistent).status.code(), Some(0));
+    assert_eq!(coherence(&inconsistent).status.code(), Some(1));
+    assert_eq!(coherence(&["--no-fail", "verify", "-s", "x is at least 3", "-s", "x is less than 3"]).status.code(), Some(0));
+
+    // An invalid conclusion fails like an inconsistency
+    let invalid = ["reasoning", "-p", "Socrates is human", "-c", "Socrates is mortal"];
+    assert_eq!(coherence(&invalid).status.code(), Some(1));
+
+    // Grading a quantified inconsistency leaves Z3's optimizer, and its warnings, out
+    let output = coherence(&["verify", "-s", "All humans are mortal", "-s", "Socrates is a human", "-s", "Socrates is not mortal"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn no_color_strips_emoji() {
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_coherence"))
+            .env("NO_COLOR", "1")
+            .args(args)
+            .args(["verify", "-s", "x is at least 3", "-s", "x is less than 3"])
+            .output()
+            .expect("failed to run coherence");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let is_emoji = |c: char| matches!(c, '\u{1F000}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}' | '\u{FE0F}');
+
+    let plain = run(&[]);
+    assert!(plain.contains("\nINCONSISTENT: Logical contradictions detected"), "{}", plain);
+    assert!(!plain.chars().any(is_emoji), "{}", plain);
+    assert!(!plain.contains('\x1b'));
+
+    // An explicit flag overrides the environment
+    assert!(run(&["--color", "always"]).contains("❌ INCONSISTENT"));
+    assert!(!run(&["--color", "never"]).chars().any(is_emoji));
+}
+
+#[test]
+fn emit_smtlib_prints_problem_without_checking() {
+    let output = coherence(&["verify", "--emit-smtlib", "-s", "Socrates is human", "-s", "Socrates is not human"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("(declare-fun human (Object) Bool)"), "{}", stdout);
+    assert!(stdout.contains("(assert (not (human socrates)))"), "{}", stdout);
+    assert!(!stdout.contains("INCONSISTENT"), "{}", stdout);
+}
+
+#[test]
+fn check_smtlib_file() {
+    let unsat = temp_file("unsat.smt2", "(declare-const x Int)\n(assert (> x 5))\n(assert (< x 3))\n(check-sat)\n");
+    let output = coherence(&["check-smtlib", "--path", unsat.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("INCONSISTENT"));
+
+    let malformed = temp_file("malformed.smt2", "(assert (> y");
+    let output = coherence(&["check-smtlib", "--path", malformed.to_str().unwrap()]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Malformed SMT-LIB2") && !stderr.contains("panicked"), "{}", stderr);
+
+    std::fs::remove_file(unsat).unwrap();
+    std::fs::remove_file(malformed).unwrap();
+}
+
+#[test]
+fn verify_batch_reports_each_problem() {
+    let batch = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/batch.json");
+
+    let output = coherence(&["verify-batch", "--file", batch]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Problem syllogism:") && stdout.contains("Problem either:"), "{}", stdout);
+    assert!(stdout.contains("Consistent: 2") && stdout.contains("Inconsistent: 3"), "{}", stdout);
+
+    assert_eq!(coherence(&["--no-fail", "verify-batch", "--file", batch]).status.code(), Some(0));
+
+    let output = coherence(&["--format", "json", "verify-batch", "--file", batch]);
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let statuses: Vec<(&str, &str)> = entries.iter()
+        .map(|entry| (entry["name"].as_str().unwrap(), entry["status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(statuses, vec![
+        ("syllogism", "Consistent"),
+        ("bounds", "Inconsistent"),
+        ("cycle", "Inconsistent"),
+        ("mixed", "Inconsistent"),
+        ("either", "Consistent"),
+    ]);
+}
+
+#[test]
+fn test_suite_as_junit() {
+    let output = coherence(&["test", "--format", "junit"]);
+    assert_eq!(output.status.code(), Some(0));
+    let xml = String::from_utf8_lossy(&output.stdout);
+    assert!(xml.contains(r#"<testsuite name="coherence" tests="4" failures="0""#), "{}", xml);
+    assert_eq!(xml.matches("<testcase ").count(), 4, "{}", xml);
+    assert_eq!(xml.matches("<failure").count(), 0, "{}", xml);
+}
+
+#[test]
+fn stream_writes_one_result_per_line() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_coherence"))
+        .arg("stream")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run coherence");
+    child.stdin.take().unwrap().write_all(concat!(
+        r#"{"id": "bounds", "statements": ["x is at least 3", "x is less than 3"]}"#, "\n",
+        r#"{"id": "lower", "statements": ["x is at least 3"]}"#, "\n",
+        "not json\n",
+    ).as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<serde_json::Value> = stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 3, "{}", stdout);
+    assert_eq!(lines[0]["id"], "bounds");
+    assert_eq!(lines[0]["status"], "Inconsistent");
+    assert_eq!(lines[1]["id"], "lower");
+    assert_eq!(lines[1]["status"], "Consistent");
+    assert!(lines[2]["error"].is_string());
+}
+
+/// Run an interactive session fed `input` on stdin
+fn interactive(input: &str) -> Output {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_coherence"))
+        .args(["--color", "never", "interactive"])
+        .env("HOME", std::env::temp_dir())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run coherence");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn interactive_session_saves_and_loads() {
+    let saved = std::env::temp_dir().join(format!("coherence-{}-session.json", std::process::id()));
+    let restored = std::env::temp_dir().join(format!("coherence-{}-restored.json", std::process::id()));
+    let (saved, restored) = (saved.to_str().unwrap(), restored.to_str().unwrap());
+
+    let output = interactive(&format!("add x is greater than 0\nadd p is true\n:save {}\nquit\n", saved));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Saved 2 statements"));
+
+    // A fresh session has nothing until the file is loaded
+    let output = interactive(&format!(":load {}\nstatus\n:save {}\nquit\n", saved, restored));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Loaded 2 statements") && !stdout.contains("Merge with or replace"), "{}", stdout);
+    assert_eq!(std::fs::read_to_string(saved).unwrap(), std::fs::read_to_string(restored).unwrap());
+
+    // Loading into a non-empty session asks whether to merge or replace
+    let output = interactive(&format!("add x is less than 0\n:load {}\nr\n:save {}\nquit\n", saved, restored));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Merge with or replace the 1 statements"));
+    assert_eq!(std::fs::read_to_string(saved).unwrap(), std::fs::read_to_string(restored).unwrap());
+
+    let output = interactive(&format!("add x is less than 0\n:load {}\nm\nstatus\nquit\n", saved));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("fact0 ↔ fact0_loaded"), "{}", stdout);
+
+    std::fs::remove_file(saved).unwrap();
+    std::fs::remove_file(restored).unwrap();
+}
+
+#[test]
+fn completions_for_bash() {
+    let output = coherence(&["completions", "bash"]);
+    assert!(output.status.success());
+    let script = String::from_utf8_lossy(&output.stdout);
+    assert!(script.contains("coherence"));
+    for subcommand in ["verify", "verify-batch", "reasoning", "compare", "stream", "interactive"] {
+        assert!(script.contains(subcommand), "{} missing from {}", subcommand, script);
+    }
+
+    assert!(!String::from_utf8_lossy(&coherence(&["--help"]).stdout).contains("completions"));
+}
+
+#[test]
+fn version_names_z3() {
+    let output = coherence(&["--version"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(&format!("coherence {} (Z3 ", env!("CARGO_PKG_VERSION"))), "{}", stdout);
+    let z3 = stdout.trim_end().split("(Z3 ").nth(1).and_then(|rest| rest.strip_suffix(')')).unwrap();
+    assert_eq!(z3.split('.').filter(|part| part.parse::<u32>().is_ok()).count(), 3, "{}", stdout);
+}
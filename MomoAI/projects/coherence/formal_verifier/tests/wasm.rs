@@ -0,0 +1,24 @@
+//! Headless WebAssembly tests of the JSON API:
+//! `cargo test --target wasm32-unknown-unknown --no-default-features --features wasm --test wasm`
+//! with `wasm-bindgen-test-runner` as the target runner (see README)
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use coherence_verifier::wasm::{backend, verify_statements_json};
+use serde_json::Value;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn contradiction_round_trips_through_json() {
+    assert_eq!(backend(), "sat");
+
+    let input = r#"[
+        {"id": "s1", "text": "valid", "predicates": [{"name": "valid", "args": ["x"], "negated": false}]},
+        {"id": "s2", "text": "not valid", "predicates": [{"name": "valid", "args": ["x"], "negated": true}]}
+    ]"#;
+    let result: Value = serde_json::from_str(&verify_statements_json(input)).unwrap();
+    assert_eq!(result["status"], "Inconsistent");
+    assert_eq!(result["contradictions"][0]["statements"], serde_json::json!(["s1", "s2"]));
+
+    let error: Value = serde_json::from_str(&verify_statements_json("not json")).unwrap();
+    assert!(error["error"].as_str().unwrap().starts_with("Invalid statements"));
+}
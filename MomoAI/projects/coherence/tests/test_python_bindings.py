@@ -0,0 +1,54 @@
+"""Tests for the `coherence` extension module.
+
+Build and install it first: `pip install .` (or `maturin develop`) in
+projects/coherence.
+"""
+
+import pytest
+
+coherence = pytest.importorskip("coherence")
+
+
+def statement(id, name, negated=False):
+    return {
+        "id": id,
+        "text": f"{'not ' if negated else ''}{name}",
+        "predicates": [{"name": name, "args": ["tool"], "negated": negated}],
+    }
+
+
+def test_verify_statements_finds_contradiction():
+    result = coherence.verify_statements(
+        [statement("s1", "coherent"), statement("s2", "coherent", negated=True)]
+    )
+
+    assert result["status"] == "Inconsistent"
+    assert result["contradictions"][0]["statements"] == ["s1", "s2"]
+
+
+def test_verify_function_reports_violation():
+    code = '''def absolute(x):
+    """Returns a non-negative value"""
+    return -5
+'''
+    result = coherence.verify_function(code)
+
+    assert result["is_coherent"] is False
+    assert result["violations"]
+
+
+def test_verifier_keeps_pushed_statements():
+    verifier = coherence.CoherenceVerifier()
+    verifier.push_statements([statement("s1", "coherent")])
+    assert verifier.check_pushed()["status"] == "Consistent"
+
+    verifier.push_statements([statement("s2", "coherent", negated=True)])
+    assert verifier.check_pushed()["status"] == "Inconsistent"
+
+    verifier.pop()
+    assert verifier.check_pushed()["status"] == "Consistent"
+
+
+def test_malformed_statement_is_value_error():
+    with pytest.raises(ValueError):
+        coherence.verify_statements([{"id": "s1"}])